@@ -37,7 +37,9 @@ pub fn clean_cfg(mut method: cfg::CfgMethod) -> cfg::CfgMethod {
     }
     for basic_block in &mut new_basic_blocks {
         match &mut basic_block.successor {
-            cfg::Successor::Undefined | cfg::Successor::Return => {}
+            cfg::Successor::Undefined
+            | cfg::Successor::Return
+            | cfg::Successor::IntentionalUnreachable(_) => {}
             cfg::Successor::Goto(target) => {
                 target.block_index = new_indices[&target.index()];
             }
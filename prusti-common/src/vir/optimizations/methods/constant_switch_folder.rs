@@ -0,0 +1,10 @@
+use crate::vir::polymorphic_vir::cfg;
+
+/// Replace the switches whose guards are boolean literals with jumps to the
+/// taken targets and remove the blocks that became unreachable.
+pub fn fold_constant_switches(mut method: cfg::CfgMethod) -> cfg::CfgMethod {
+    if method.fold_constant_switches() {
+        method.remove_unreachable_blocks();
+    }
+    method
+}
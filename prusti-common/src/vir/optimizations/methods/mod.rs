@@ -8,9 +8,12 @@
 
 mod assert_remover;
 mod cfg_cleaner;
+mod constant_switch_folder;
 mod empty_if_remover;
 mod purifier;
 mod quantifier_fixer;
+mod single_use_block_inliner;
+mod switch_arm_sorter;
 mod unfolding_fixer;
 mod var_remover;
 
@@ -19,7 +22,9 @@ use crate::{config::Optimizations, vir::polymorphic_vir::cfg::CfgMethod};
 
 use self::{
     assert_remover::remove_trivial_assertions, cfg_cleaner::clean_cfg,
-    empty_if_remover::remove_empty_if, purifier::purify_vars, quantifier_fixer::fix_quantifiers,
+    constant_switch_folder::fold_constant_switches, empty_if_remover::remove_empty_if,
+    purifier::purify_vars, quantifier_fixer::fix_quantifiers,
+    single_use_block_inliner::inline_single_use_blocks, switch_arm_sorter::sort_switch_arms,
     unfolding_fixer::fix_unfoldings, var_remover::remove_unused_vars,
 };
 
@@ -58,9 +63,12 @@ pub fn optimize_method_encoding(
     };
     let cfg = apply!(fix_unfoldings, cfg);
     let cfg = apply!(fix_quantifiers, cfg);
+    let cfg = apply!(fold_constant_switches, cfg);
+    let cfg = apply!(sort_switch_arms, cfg);
     let cfg = apply!(remove_empty_if, cfg);
     let cfg = apply!(remove_unused_vars, cfg);
     let cfg = apply!(remove_trivial_assertions, cfg);
+    let cfg = apply!(inline_single_use_blocks, cfg);
     let cfg = apply!(clean_cfg, cfg);
 
     cfg
@@ -32,7 +32,10 @@ pub fn purify_vars(mut method: cfg::CfgMethod) -> cfg::CfgMethod {
         ast::StmtWalker::walk(&mut collector, stmt);
     });
     method.walk_successors(|successor| match successor {
-        cfg::Successor::Undefined | cfg::Successor::Return | cfg::Successor::Goto(_) => {}
+        cfg::Successor::Undefined
+        | cfg::Successor::Return
+        | cfg::Successor::IntentionalUnreachable(_)
+        | cfg::Successor::Goto(_) => {}
         cfg::Successor::GotoSwitch(conditional_targets, _) => {
             for (expr, _) in conditional_targets {
                 ast::ExprWalker::walk(&mut collector, expr);
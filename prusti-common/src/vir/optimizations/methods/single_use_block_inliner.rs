@@ -0,0 +1,15 @@
+use crate::{config, vir::polymorphic_vir::cfg};
+
+/// Merge the blocks that are jumped to from exactly one `Goto` into the block
+/// that jumps to them. Nothing is merged if blocks are selected by label with
+/// `delete_basic_blocks` or `verify_only_basic_block_path`, because the
+/// merged blocks would no longer be found.
+pub fn inline_single_use_blocks(mut method: cfg::CfgMethod) -> cfg::CfgMethod {
+    let selects_blocks = !config::delete_basic_blocks().is_empty()
+        || (config::enable_verify_only_basic_block_path()
+            && !config::verify_only_basic_block_path().is_empty());
+    if !selects_blocks {
+        method.inline_single_use_blocks();
+    }
+    method
+}
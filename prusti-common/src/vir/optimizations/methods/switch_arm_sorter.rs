@@ -0,0 +1,8 @@
+use crate::vir::polymorphic_vir::cfg;
+
+/// Sort the arms of the switches with disjoint literal guards, so that the
+/// encoding does not depend on the order in which the arms were built.
+pub fn sort_switch_arms(mut method: cfg::CfgMethod) -> cfg::CfgMethod {
+    method.sort_switch_arms();
+    method
+}
@@ -119,17 +119,44 @@ impl ToGraphViz for CfgMethod {
             }
         }
 
+        // The end of the method is not a basic block, so it gets its own node.
+        writeln!(graph, "\"return\" [shape=doublecircle,label=\"return\"];").unwrap();
+
         for (index, block) in self.basic_blocks.iter().enumerate() {
-            let block_label = &self.index_to_label(index);
-            for target in block.successor.get_following() {
-                let target_label = self.index_to_label(target.index());
-                writeln!(
-                    graph,
-                    "\"block_{}\" -> \"block_{}\";",
-                    escape_html(block_label),
-                    escape_html(target_label),
-                )
-                .unwrap();
+            let block_label = escape_html(self.index_to_label(index));
+            match &block.successor {
+                Successor::Undefined | Successor::IntentionalUnreachable(_) => {}
+                Successor::Return => {
+                    writeln!(graph, "\"block_{block_label}\" -> \"return\";").unwrap();
+                }
+                Successor::Goto(target) => {
+                    writeln!(
+                        graph,
+                        "\"block_{}\" -> \"block_{}\";",
+                        block_label,
+                        escape_html(self.index_to_label(target.index())),
+                    )
+                    .unwrap();
+                }
+                Successor::GotoSwitch(guarded_targets, default_target) => {
+                    for (guard, target) in guarded_targets {
+                        writeln!(
+                            graph,
+                            "\"block_{}\" -> \"block_{}\" [label=<{}>];",
+                            block_label,
+                            escape_html(self.index_to_label(target.index())),
+                            escape_html(guard),
+                        )
+                        .unwrap();
+                    }
+                    writeln!(
+                        graph,
+                        "\"block_{}\" -> \"block_{}\" [label=\"default\"];",
+                        block_label,
+                        escape_html(self.index_to_label(default_target.index())),
+                    )
+                    .unwrap();
+                }
             }
         }
 
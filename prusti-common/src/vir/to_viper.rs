@@ -10,7 +10,7 @@ use crate::{
     vir::{
         ast::*,
         borrows::borrow_id,
-        cfg::{CfgBlock, CfgMethod, Successor, RETURN_LABEL},
+        cfg::{CfgBlock, CfgBlockIndex, CfgMethod, Successor},
         Program,
     },
 };
@@ -1065,16 +1065,26 @@ impl<'a, 'v> ToViper<'v, viper::Method<'v>> for &'a CfgMethod {
             );
             // self.convert_basic_block_path(path, ast, &mut blocks_ast, &mut declarations);
         } else {
-            // Sort blocks by label, except for the first block
-            let mut blocks: Vec<_> = self.basic_blocks.iter().enumerate().skip(1).collect();
-            blocks.sort_by_key(|(index, _)| index_to_label(self.basic_blocks_labels(), *index));
-            blocks.insert(0, (0, &self.basic_blocks[0]));
+            let blocks: Vec<_> = if config::canonical_block_order() {
+                self.get_canonical_order()
+                    .into_iter()
+                    .map(|index| (index.index(), &self.basic_blocks[index.index()]))
+                    .collect()
+            } else {
+                // Sort blocks by label, except for the first block
+                let mut blocks: Vec<_> = self.basic_blocks.iter().enumerate().skip(1).collect();
+                blocks.sort_by_key(|(index, _)| index_to_label(self.basic_blocks_labels(), *index));
+                blocks.insert(0, (0, &self.basic_blocks[0]));
+                blocks
+            };
 
             for (index, block) in blocks.into_iter() {
                 blocks_ast.push(block_to_viper(
                     context,
                     ast,
+                    &self.basic_blocks,
                     self.basic_blocks_labels(),
+                    self.return_label(),
                     block,
                     index,
                 ));
@@ -1084,8 +1094,8 @@ impl<'a, 'v> ToViper<'v, viper::Method<'v>> for &'a CfgMethod {
                 );
             }
         }
-        blocks_ast.push(ast.label(RETURN_LABEL, &[]));
-        declarations.push(ast.label(RETURN_LABEL, &[]).into());
+        blocks_ast.push(ast.label(self.return_label(), &[]));
+        declarations.push(ast.label(self.return_label(), &[]).into());
 
         let method_body = Some(ast.seqn(&blocks_ast, &declarations));
 
@@ -1098,8 +1108,8 @@ impl<'a, 'v> ToViper<'v, viper::Method<'v>> for &'a CfgMethod {
             &self.name(),
             &[],
             &formal_returns_decl,
-            &[],
-            &[],
+            &self.get_preconditions().to_viper(context, ast),
+            &self.get_postconditions().to_viper(context, ast),
             method_body,
         )
     }
@@ -1130,7 +1140,9 @@ fn cfg_method_convert_basic_block_path<'v>(
         blocks_ast.push(block_to_viper(
             context,
             ast,
+            &cfg_method.basic_blocks,
             cfg_method.basic_blocks_labels(),
+            cfg_method.return_label(),
             block,
             index,
         ));
@@ -1176,7 +1188,9 @@ fn cfg_method_convert_basic_block_path<'v>(
                 context,
                 ast,
                 index,
+                &cfg_method.basic_blocks,
                 cfg_method.basic_blocks_labels(),
+                cfg_method.return_label(),
                 &block.successor,
             ),
         ];
@@ -1188,7 +1202,9 @@ fn cfg_method_convert_basic_block_path<'v>(
         blocks_ast.push(block_to_viper(
             context,
             ast,
+            &cfg_method.basic_blocks,
             cfg_method.basic_blocks_labels(),
+            cfg_method.return_label(),
             block,
             index,
         ));
@@ -1206,11 +1222,40 @@ fn index_to_label(basic_block_labels: &[String], index: usize) -> String {
     basic_block_labels[index].clone()
 }
 
+/// Jumps to the target block after exhaling the invariants that the target
+/// block requires on entry.
+fn goto_to_viper<'a>(
+    context: Context,
+    ast: &'a AstFactory,
+    basic_blocks: &[CfgBlock],
+    basic_block_labels: &[String],
+    target: CfgBlockIndex,
+) -> viper::Stmt<'a> {
+    let goto = ast.goto(&basic_block_labels[target.index()]);
+    let invariants = &basic_blocks[target.index()].exhale_on_entry;
+    if invariants.is_empty() {
+        return goto;
+    }
+    let mut stmts: Vec<viper::Stmt<'a>> = invariants
+        .iter()
+        .map(|invariant| {
+            ast.exhale(
+                invariant.to_viper(context, ast),
+                invariant.pos().to_viper(context, ast),
+            )
+        })
+        .collect();
+    stmts.push(goto);
+    ast.seqn(&stmts, &[])
+}
+
 fn successor_to_viper<'a>(
     context: Context,
     ast: &'a AstFactory,
     index: usize,
+    basic_blocks: &[CfgBlock],
     basic_block_labels: &[String],
+    return_label: &str,
     successor: &Successor,
 ) -> viper::Stmt<'a> {
     match *successor {
@@ -1218,27 +1263,183 @@ fn successor_to_viper<'a>(
             "CFG block '{}' has no successor.",
             basic_block_labels[index].clone()
         ),
-        Successor::Return => ast.goto(RETURN_LABEL),
-        Successor::Goto(target) => ast.goto(&basic_block_labels[target.index()]),
-        Successor::GotoSwitch(ref successors, ref default_target) => {
-            let mut stmts: Vec<viper::Stmt<'a>> = vec![];
-            for (test, target) in successors {
-                let goto = ast.seqn(&[ast.goto(&basic_block_labels[target.index()])], &[]);
-                let skip = ast.seqn(&[], &[]);
-                let conditional_goto = ast.if_stmt(test.to_viper(context, ast), goto, skip);
-                stmts.push(conditional_goto);
+        Successor::Return => ast.goto(return_label),
+        Successor::IntentionalUnreachable(ref position) => ast.assert(
+            ast.false_lit_with_pos(position.to_viper(context, ast)),
+            position.to_viper(context, ast),
+        ),
+        Successor::Goto(target) => {
+            goto_to_viper(context, ast, basic_blocks, basic_block_labels, target)
+        }
+        Successor::GotoSwitch(ref successors, default_target) => {
+            if config::balanced_switch_lowering() {
+                if let Some((scrutinee, mut cases)) = switch_integer_cases(successors) {
+                    cases.sort_by_key(|(value, ..)| *value);
+                    return balanced_switch_to_viper(
+                        context,
+                        ast,
+                        basic_blocks,
+                        basic_block_labels,
+                        scrutinee,
+                        &cases,
+                        default_target,
+                    );
+                }
             }
-            let default_goto = ast.goto(&basic_block_labels[default_target.index()]);
-            stmts.push(default_goto);
-            ast.seqn(&stmts, &[])
+            linear_switch_to_viper(
+                context,
+                ast,
+                basic_blocks,
+                basic_block_labels,
+                successors,
+                default_target,
+            )
+        }
+    }
+}
+
+fn linear_switch_to_viper<'a>(
+    context: Context,
+    ast: &'a AstFactory,
+    basic_blocks: &[CfgBlock],
+    basic_block_labels: &[String],
+    successors: &[(Expr, CfgBlockIndex)],
+    default_target: CfgBlockIndex,
+) -> viper::Stmt<'a> {
+    // The guards are lowered to a sequence of `if`s in their original
+    // order, so the first guard that holds wins. This implements the
+    // first-match semantics of `GotoSwitch`.
+    let mut stmts: Vec<viper::Stmt<'a>> = vec![];
+    for (test, target) in successors {
+        let goto = ast.seqn(
+            &[goto_to_viper(
+                context,
+                ast,
+                basic_blocks,
+                basic_block_labels,
+                *target,
+            )],
+            &[],
+        );
+        let skip = ast.seqn(&[], &[]);
+        let conditional_goto = ast.if_stmt(test.to_viper(context, ast), goto, skip);
+        stmts.push(conditional_goto);
+    }
+    let default_goto = goto_to_viper(
+        context,
+        ast,
+        basic_blocks,
+        basic_block_labels,
+        default_target,
+    );
+    stmts.push(default_goto);
+    ast.seqn(&stmts, &[])
+}
+
+/// A switch case: the value of the constant, the constant itself, the original
+/// guard, and the target.
+type SwitchCase<'e> = (i64, &'e Expr, &'e Expr, CfgBlockIndex);
+
+/// If all guards are equality tests of the same expression against distinct
+/// integer constants, returns the tested expression and the cases.
+fn switch_integer_cases(
+    successors: &[(Expr, CfgBlockIndex)],
+) -> Option<(&Expr, Vec<SwitchCase<'_>>)> {
+    if successors.len() < 3 {
+        return None;
+    }
+    let mut scrutinee = None;
+    let mut cases = Vec::new();
+    for (test, target) in successors {
+        let Expr::BinOp(BinaryOpKind::EqCmp, left, right, _) = test else {
+            return None;
+        };
+        let Expr::Const(Const::Int(value), _) = right.as_ref() else {
+            return None;
+        };
+        match scrutinee {
+            None => scrutinee = Some(left.as_ref()),
+            Some(scrutinee) if scrutinee == left.as_ref() => {}
+            Some(_) => return None,
+        }
+        if cases.iter().any(|(other, ..)| other == value) {
+            return None;
         }
+        cases.push((*value, right.as_ref(), test, *target));
     }
+    scrutinee.map(|scrutinee| (scrutinee, cases))
+}
+
+/// Lowers a switch over distinct integer constants into a balanced binary
+/// search. Because the constants are distinct, at most one guard holds, so
+/// the order in which they are checked does not matter.
+fn balanced_switch_to_viper<'a>(
+    context: Context,
+    ast: &'a AstFactory,
+    basic_blocks: &[CfgBlock],
+    basic_block_labels: &[String],
+    scrutinee: &Expr,
+    sorted_cases: &[SwitchCase],
+    default_target: CfgBlockIndex,
+) -> viper::Stmt<'a> {
+    if let [(_, _, test, target)] = sorted_cases {
+        let goto = ast.seqn(
+            &[goto_to_viper(
+                context,
+                ast,
+                basic_blocks,
+                basic_block_labels,
+                *target,
+            )],
+            &[],
+        );
+        let default_goto = ast.seqn(
+            &[goto_to_viper(
+                context,
+                ast,
+                basic_blocks,
+                basic_block_labels,
+                default_target,
+            )],
+            &[],
+        );
+        return ast.if_stmt(test.to_viper(context, ast), goto, default_goto);
+    }
+    let middle = sorted_cases.len() / 2;
+    let (lower_cases, upper_cases) = sorted_cases.split_at(middle);
+    let (_, pivot, _, _) = upper_cases[0];
+    let test = Expr::lt_cmp(scrutinee.clone(), pivot.clone());
+    let lower = balanced_switch_to_viper(
+        context,
+        ast,
+        basic_blocks,
+        basic_block_labels,
+        scrutinee,
+        lower_cases,
+        default_target,
+    );
+    let upper = balanced_switch_to_viper(
+        context,
+        ast,
+        basic_blocks,
+        basic_block_labels,
+        scrutinee,
+        upper_cases,
+        default_target,
+    );
+    ast.if_stmt(
+        test.to_viper(context, ast),
+        ast.seqn(&[lower], &[]),
+        ast.seqn(&[upper], &[]),
+    )
 }
 
 fn block_to_viper<'a>(
     context: Context,
     ast: &'a AstFactory,
+    basic_blocks: &[CfgBlock],
     basic_block_labels: &[String],
+    return_label: &str,
     block: &CfgBlock,
     index: usize,
 ) -> viper::Stmt<'a> {
@@ -1246,14 +1447,31 @@ fn block_to_viper<'a>(
     let mut stmts: Vec<viper::Stmt> = vec![
         // To put a bit of white space between blocks.
         ast.comment(""),
-        ast.label(label, &[]),
     ];
+    if index == 0 {
+        // The entry block is reached by falling through, not by a `goto`.
+        for invariant in &block.exhale_on_entry {
+            stmts.push(ast.exhale(
+                invariant.to_viper(context, ast),
+                invariant.pos().to_viper(context, ast),
+            ));
+        }
+    }
+    stmts.push(ast.label_with_pos(label, &[], block.position.to_viper(context, ast)));
+    for invariant in &block.inhale_after_label {
+        stmts.push(ast.inhale(
+            invariant.to_viper(context, ast),
+            invariant.pos().to_viper(context, ast),
+        ));
+    }
     stmts.extend(block.stmts.to_viper(context, ast));
     stmts.push(successor_to_viper(
         context,
         ast,
         index,
+        basic_blocks,
         basic_block_labels,
+        return_label,
         &block.successor,
     ));
     ast.seqn(&stmts, &[])
@@ -21,6 +21,9 @@ pub use prusti_contracts_proc_macros::trusted;
 /// A macro for marking a function as opted into verification.
 pub use prusti_contracts_proc_macros::verified;
 
+/// A macro for requiring that a function never panics.
+pub use prusti_contracts_proc_macros::no_panic;
+
 /// A macro for type invariants.
 pub use prusti_contracts_proc_macros::invariant;
 
@@ -135,6 +138,14 @@ mod private {
         unreachable!();
     }
 
+    pub fn prusti_unfold_owned<T>(_arg: T) {
+        unreachable!();
+    }
+
+    pub fn prusti_fold_owned<T>(_arg: T) {
+        unreachable!();
+    }
+
     #[pure]
     pub fn prusti_terminates_trusted() -> Int {
         Int::new(1)
@@ -368,4 +379,27 @@ pub fn snapshot_equality<T>(_l: T, _r: T) -> bool {
     true
 }
 
+/// A lemma stating that the snapshot of `x` is equal to itself. Evaluates to
+/// `true` and makes the fact `snapshot_equality(x, x)` available to the
+/// verifier. Supported only with the `unsafe_core_proof` encoding.
+pub fn snapshot_equality_reflexivity<T>(_x: T) -> bool {
+    true
+}
+
+/// A lemma stating that `l` and `r` have equal snapshots if their fields have
+/// equal snapshots. The equality of the fields is checked at the call site.
+/// Evaluates to `true` and makes the fact `snapshot_equality(l, r)` available
+/// to the verifier. Supported only with the `unsafe_core_proof` encoding and
+/// only for primitive types, raw pointers, and structs.
+pub fn snapshot_equality_congruence<T>(_l: T, _r: T) -> bool {
+    true
+}
+
+/// Asserts that the raw pointer points to a valid, initialized location that
+/// can be read. A raw pointer can be dereferenced only in states in which its
+/// validity was established, for example, by a precondition.
+pub fn ptr_valid<T>(_ptr: *const T) -> bool {
+    true
+}
+
 pub use private::*;
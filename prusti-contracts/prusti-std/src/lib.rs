@@ -12,3 +12,123 @@ where
         K: ::core::borrow::Borrow<Q>,
         Q: ::core::hash::Hash + Eq;
 }
+
+// Strings are modelled by their UTF-8 bytes: `as_bytes` exposes the contents
+// of a string as a slice, whose length is the length of the string.
+
+#[extern_spec]
+impl str {
+    #[pure]
+    #[ensures(result == self.as_bytes().len())]
+    pub fn len(&self) -> usize;
+
+    #[pure]
+    #[ensures(result == (self.len() == 0))]
+    pub fn is_empty(&self) -> bool;
+
+    #[pure]
+    pub fn as_bytes(&self) -> &[u8];
+}
+
+#[extern_spec]
+impl ::std::string::String {
+    #[ensures(result.len() == 0)]
+    pub fn new() -> ::std::string::String;
+
+    #[pure]
+    #[ensures(result == self.as_bytes().len())]
+    pub fn len(&self) -> usize;
+
+    #[pure]
+    #[ensures(result == (self.len() == 0))]
+    pub fn is_empty(&self) -> bool;
+
+    #[pure]
+    #[ensures(result.as_bytes() === self.as_bytes())]
+    pub fn as_str(&self) -> &str;
+
+    #[pure]
+    pub fn as_bytes(&self) -> &[u8];
+
+    #[ensures(self.len() == old(self.len()) + string.len())]
+    #[ensures(forall(|i: usize| i < old(self.len()) ==> self.as_bytes()[i] == old(self.as_bytes()[i])))]
+    #[ensures(forall(|i: usize| i < string.len() ==> self.as_bytes()[old(self.len()) + i] == string.as_bytes()[i]))]
+    pub fn push_str(&mut self, string: &str);
+}
+
+#[extern_spec]
+impl<'a> ::core::ops::Add<&'a str> for ::std::string::String {
+    #[ensures(result.len() == self.len() + other.len())]
+    #[ensures(forall(|i: usize| i < self.len() ==> result.as_bytes()[i] == self.as_bytes()[i]))]
+    #[ensures(forall(|i: usize| i < other.len() ==> result.as_bytes()[self.len() + i] == other.as_bytes()[i]))]
+    fn add(self, other: &'a str) -> ::std::string::String;
+}
+
+#[extern_spec]
+impl PartialEq for str {
+    #[pure]
+    #[ensures(result == (self.as_bytes() === other.as_bytes()))]
+    fn eq(&self, other: &str) -> bool;
+}
+
+// Vectors are modelled by their elements: `as_slice` exposes the contents of
+// a vector as a slice, whose length is the length of the vector. The capacity
+// is not modelled.
+
+#[extern_spec]
+impl<T> ::std::vec::Vec<T> {
+    #[ensures(result.len() == 0)]
+    pub fn new() -> ::std::vec::Vec<T>;
+
+    #[pure]
+    #[ensures(result == self.as_slice().len())]
+    pub fn len(&self) -> usize;
+
+    #[pure]
+    #[ensures(result == (self.len() == 0))]
+    pub fn is_empty(&self) -> bool;
+
+    #[pure]
+    pub fn as_slice(&self) -> &[T];
+
+    #[ensures(self.len() == old(self.len()) + 1)]
+    #[ensures(forall(|i: usize| i < old(self.len()) ==> self.as_slice()[i] === old(self.as_slice())[i]))]
+    #[ensures(self.as_slice()[old(self.len())] === value)]
+    pub fn push(&mut self, value: T);
+
+    #[ensures(old(self.len()) == 0 ==> self.len() == 0 && matches!(result, None))]
+    #[ensures(old(self.len()) > 0 ==> self.len() == old(self.len()) - 1)]
+    #[ensures(forall(|i: usize| i < self.len() ==> self.as_slice()[i] === old(self.as_slice())[i]))]
+    #[ensures(old(self.len()) > 0 ==> match result {
+        Some(ref last) => *last === old(self.as_slice())[self.len()],
+        None => false,
+    })]
+    pub fn pop(&mut self) -> Option<T>;
+}
+
+// Options are modelled by their variants: the payload of `Some` is exposed by
+// matching on the option in the specifications.
+
+#[extern_spec]
+impl<T> ::std::option::Option<T> {
+    #[pure]
+    #[ensures(result == matches!(*self, Some(_)))]
+    pub fn is_some(&self) -> bool;
+
+    #[pure]
+    #[ensures(result == !self.is_some())]
+    pub fn is_none(&self) -> bool;
+
+    #[requires(self.is_some())]
+    #[ensures(match self {
+        Some(ref value) => result === *value,
+        None => false,
+    })]
+    pub fn unwrap(self) -> T;
+
+    #[ensures(match self {
+        Some(ref value) => result === *value,
+        None => result === default,
+    })]
+    pub fn unwrap_or(self, default: T) -> T;
+}
@@ -29,4 +29,25 @@ pub enum VerificationResult {
     /// Verification failed. Errors should have been already emitted by
     /// the verifier.
     Failure,
+    /// Verification of some methods timed out, and all the other ones
+    /// succeeded. Errors should have been already emitted by the verifier.
+    Timeout,
+}
+
+/// A diagnostic reported while verifying a method.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct VerificationDiagnostic {
+    pub message: String,
+    /// The source location of the diagnostic, if it could be mapped to one.
+    pub span: Option<String>,
+}
+
+/// The result of verifying a single method, that is, a single Viper program.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct MethodVerificationResult {
+    pub method_name: String,
+    pub outcome: VerificationResult,
+    /// The errors reported for the method. They have also been emitted by the
+    /// verifier.
+    pub diagnostics: Vec<VerificationDiagnostic>,
 }
@@ -153,6 +153,14 @@ impl PrustiError {
         matches!(self.kind, PrustiErrorKind::Error)
     }
 
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn span(&self) -> &MultiSpan {
+        &self.span
+    }
+
     // FIXME: This flag is a temporary workaround for having duplicate errors
     // coming from verifying functions multiple times. We should verify each
     // function only once.
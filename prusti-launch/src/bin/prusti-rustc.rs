@@ -32,7 +32,13 @@ fn process(mut args: Vec<String>) -> Result<(), i32> {
     let libjvm_path =
         launch::find_libjvm(&java_home).expect("Failed to find JVM library. Check JAVA_HOME");
 
-    let prusti_sysroot = launch::prusti_sysroot().expect("Failed to find Rust's sysroot");
+    let prusti_sysroot = match launch::prusti_sysroot() {
+        Ok(prusti_sysroot) => prusti_sysroot,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return Err(1);
+        }
+    };
 
     let compiler_bin = prusti_sysroot.join("bin");
     let compiler_lib = prusti_sysroot.join("lib");
@@ -94,7 +100,9 @@ fn process(mut args: Vec<String>) -> Result<(), i32> {
     }
     cmd.args(&args);
 
-    let has_no_sysroot_arg = !args.iter().any(|s| s == "--sysroot");
+    let has_no_sysroot_arg = !args
+        .iter()
+        .any(|s| s == "--sysroot" || s.starts_with("--sysroot="));
     if has_no_sysroot_arg {
         cmd.arg("--sysroot");
         cmd.arg(
@@ -19,6 +19,7 @@ pub extern crate rustc_infer as infer;
 pub extern crate rustc_interface as interface;
 pub extern crate rustc_macros as macros;
 pub extern crate rustc_metadata as metadata;
+pub extern crate rustc_parse as parse;
 pub extern crate rustc_serialize as serialize;
 pub extern crate rustc_session as session;
 pub extern crate rustc_span as span;
@@ -1,6 +1,5 @@
-use crate::dump_viper_program;
+use crate::{dump_viper_program, should_dump_viper_program};
 use prusti_common::{
-    config,
     vir::{LoweringContext, ToViper},
     Stopwatch,
 };
@@ -23,7 +22,7 @@ impl<'a> Backend<'a> {
                     let ast_factory = context.new_ast_factory();
                     let viper_program = program.to_viper(LoweringContext::default(), &ast_factory);
 
-                    if config::dump_viper_program() {
+                    if should_dump_viper_program() {
                         stopwatch.start_next("dumping viper program");
                         dump_viper_program(
                             &ast_utils,
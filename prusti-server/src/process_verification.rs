@@ -5,7 +5,7 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use crate::{Backend, VerificationRequest, ViperBackendConfig};
-use log::info;
+use log::{info, warn};
 use once_cell::sync::Lazy;
 use prusti_common::{
     config,
@@ -13,7 +13,11 @@ use prusti_common::{
     vir::{program_normalization::NormalizationInfo, ToViper},
     Stopwatch,
 };
-use std::{fs::create_dir_all, path::PathBuf};
+use std::{
+    fs::{create_dir_all, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
 use viper::{
     smt_manager::SmtManager, Cache, VerificationBackend, VerificationContext, VerificationResult,
 };
@@ -53,7 +57,7 @@ pub fn process_verification_request<'v, 't: 'v>(
             .program
             .to_viper(prusti_common::vir::LoweringContext::default(), &ast_factory);
 
-        if config::dump_viper_program() {
+        if should_dump_viper_program() {
             stopwatch.start_next("dumping viper program");
             dump_viper_program(
                 &ast_utils,
@@ -73,7 +77,7 @@ pub fn process_verification_request<'v, 't: 'v>(
         );
         println!("Hash of the request is: {hash}");
         // Some tests need the dump to report a diff of the Viper programs.
-        if config::dump_viper_program() {
+        if should_dump_viper_program() {
             ast_utils.with_local_frame(16, || {
                 let _ = build_or_dump_viper_program();
             });
@@ -89,7 +93,7 @@ pub fn process_verification_request<'v, 't: 'v>(
                 &result,
                 request.program.get_name()
             );
-            if config::dump_viper_program() {
+            if should_dump_viper_program() {
                 ast_utils.with_local_frame(16, || {
                     let _ = build_or_dump_viper_program();
                 });
@@ -117,8 +121,14 @@ pub fn process_verification_request<'v, 't: 'v>(
     stopwatch.start_next("backend verification");
     let mut result = backend.verify(&request.program);
 
-    // Don't cache Java exceptions, which might be due to misconfigured paths.
-    if config::enable_cache() && !matches!(result, VerificationResult::JavaException(_)) {
+    // Don't cache Java exceptions, which might be due to misconfigured paths,
+    // and timeouts, which might not occur on a less loaded machine.
+    if config::enable_cache()
+        && !matches!(
+            result,
+            VerificationResult::JavaException(_) | VerificationResult::Timeout
+        )
+    {
         info!(
             "Storing new cached result {:?} for program {}",
             &result,
@@ -131,15 +141,45 @@ pub fn process_verification_request<'v, 't: 'v>(
     result
 }
 
+/// Whether `dump_viper_program` has to be called for the programs that are
+/// verified.
+pub fn should_dump_viper_program() -> bool {
+    config::dump_viper_program() || config::dump_viper().is_some()
+}
+
 pub fn dump_viper_program(
     ast_utils: &viper::AstUtils,
     program: viper::Program,
     program_name: &str,
 ) {
-    let namespace = "viper_program";
-    let filename = format!("{program_name}.vpr");
-    info!("Dumping Viper program to '{}/{}'", namespace, filename);
-    report(namespace, filename, ast_utils.pretty_print(program));
+    let text = ast_utils.pretty_print(program);
+    if config::dump_viper_program() {
+        let namespace = "viper_program";
+        let filename = format!("{program_name}.vpr");
+        info!("Dumping Viper program to '{}/{}'", namespace, filename);
+        report(namespace, filename, &text);
+    }
+    if let Some(dump_path) = config::dump_viper() {
+        if let Err(error) = write_viper_program(Path::new(&dump_path), program_name, &text) {
+            warn!("Failed to dump Viper program {program_name} to '{dump_path}': {error}");
+        }
+    }
+}
+
+/// Writes the program to its own file if `dump_path` is a directory and
+/// appends it to the file `dump_path` otherwise. The file is truncated by the
+/// driver when the compilation starts.
+fn write_viper_program(dump_path: &Path, program_name: &str, text: &str) -> std::io::Result<()> {
+    if dump_path.is_dir() {
+        let filename = format!("{}.vpr", to_legal_file_name(program_name));
+        std::fs::write(dump_path.join(filename), text)
+    } else {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dump_path)?;
+        writeln!(file, "// Program {program_name}\n{text}")
+    }
 }
 
 fn new_viper_verifier<'v, 't: 'v>(
@@ -60,11 +60,14 @@ impl ViperBackendConfig {
                     "--proverConfigArgs".to_string(),
                     // model.partial changes the default case of functions in counterexamples
                     // to #unspecified
-                    format!(
+                    std::iter::once(format!(
                         "smt.qi.eager_threshold={} model.partial={}",
                         config::smt_qi_eager_threshold(),
                         config::counterexample()
-                    ),
+                    ))
+                    .chain(config::smt_options())
+                    .collect::<Vec<_>>()
+                    .join(" "),
                     "--logLevel".to_string(),
                     "ERROR".to_string(),
                 ]);
@@ -73,9 +76,27 @@ impl ViperBackendConfig {
                     verifier_args.push("--checkTimeout".to_string());
                     verifier_args.push(check_timeout.to_string());
                 }
+
+                if let Some(method_timeout) = config::method_timeout() {
+                    // Silicon expects the timeout in seconds.
+                    verifier_args.push("--timeout".to_string());
+                    verifier_args.push(((method_timeout + 999) / 1000).to_string());
+                }
             }
             VerificationBackend::Carbon => {
                 verifier_args.extend(vec!["--disableAllocEncoding".to_string()]);
+                let mut boogie_options: Vec<_> = config::smt_options()
+                    .iter()
+                    .map(|option| format!("/proverOpt:O:{option}"))
+                    .collect();
+                if let Some(method_timeout) = config::method_timeout() {
+                    // Boogie expects the time limit in seconds.
+                    boogie_options.push(format!("/timeLimit:{}", (method_timeout + 999) / 1000));
+                }
+                if !boogie_options.is_empty() {
+                    verifier_args.push("--boogieOpt".to_string());
+                    verifier_args.push(boogie_options.join(" "));
+                }
             }
         }
         Self {
@@ -0,0 +1,6 @@
+use prusti_contracts::*;
+
+#[extern_spec(math)]
+#[ensures(result >= a && result >= b)]
+#[ensures(result == a || result == b)]
+fn max(a: i32, b: i32) -> i32;
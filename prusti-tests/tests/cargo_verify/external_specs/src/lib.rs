@@ -0,0 +1,15 @@
+pub mod math {
+    pub fn max(a: i32, b: i32) -> i32 {
+        if a > b {
+            a
+        } else {
+            b
+        }
+    }
+}
+
+pub fn test(a: i32) {
+    let m = math::max(a, 3);
+    assert!(m >= 3);
+    assert!(m == a || m == 3);
+}
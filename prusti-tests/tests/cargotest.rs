@@ -175,6 +175,11 @@ fn test_symlinks() {
 // TODO: automatically create a test for each folder in `test/cargo_verify`.
 // Each of the following functions, listed in alphabetic order, test a crate in `cargo_verify/`.
 
+#[cargo_test]
+fn test_external_specs() {
+    test_local_project("external_specs");
+}
+
 #[cargo_test]
 fn test_failing_crate() {
     test_local_project("failing_crate");
@@ -0,0 +1,28 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+#[terminates]
+fn not_decreasing(mut i: i64) {
+    while i > 0 {
+        body_variant!(Int::new(i)); //~ ERROR: The loop variant might not have decreased
+        i += 0;
+    }
+}
+
+#[terminates]
+fn increasing(mut i: i64) {
+    while i > 0 && i < 100 {
+        body_variant!(Int::new(i)); //~ ERROR: The loop variant might not have decreased
+        i += 1;
+    }
+}
+
+#[terminates]
+fn missing_variant(mut i: i64) {
+    while i > 0 { //~ ERROR: this loop might not terminate
+        i -= 1;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+// compile-flags: -Pcheck_panics=false
+
+use prusti_contracts::*;
+
+#[no_panic]
+#[requires(i <= s.len())]
+fn get(s: &[i32], i: usize) -> i32 {
+    s[i] //~ ERROR the array or slice index may be out of bounds
+}
+
+#[no_panic]
+fn unchecked_get(s: &[i32], i: usize) -> i32 {
+    if i > s.len() {
+        unreachable!() //~ ERROR unreachable!(..) statement might be reachable
+    }
+    s[i] //~ ERROR the array or slice index may be out of bounds
+}
+
+// Without the attribute, panics are not checked.
+#[requires(i <= s.len())]
+fn get_unchecked(s: &[i32], i: usize) -> i32 {
+    s[i]
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+extern crate prusti_std;
+use prusti_contracts::*;
+
+#[ensures(result == 0)] //~ ERROR postcondition might not hold
+fn value_or_zero(opt: Option<u32>) -> u32 {
+    opt.unwrap_or(0)
+}
+
+fn test_unwrap_none() {
+    let none: Option<u32> = None;
+    let _ = none.unwrap(); //~ ERROR precondition might not hold
+}
+
+fn test_client() {
+    let some = Some(3);
+    assert!(some.unwrap_or(5) == 5); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use prusti_contracts::*;
+
+#[requires(*a < 1000 && *b < 1000)]
+#[ensures(result == *a + *b)]
+fn sum(a: &u32, b: &u32) -> u32 {
+    *a + *b
+}
+
+#[ensures(*a != *b)] //~ ERROR postcondition might not hold
+fn distinct(a: &u32, b: &u32) {}
+
+fn test_aliased() {
+    let x = 7;
+    let r1 = &x;
+    let r2 = &x;
+    assert!(sum(r1, r2) == 15); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
@@ -0,0 +1,50 @@
+use prusti_contracts::*;
+
+#[ensures(x < 16 ==> result == x * 3)]
+#[ensures(x >= 16 ==> result == 0)]
+fn select(x: u32) -> u32 {
+    match x {
+        0 => 0,
+        1 => 3,
+        2 => 6,
+        3 => 9,
+        4 => 12,
+        5 => 15,
+        6 => 18,
+        7 => 21,
+        8 => 24,
+        9 => 27,
+        10 => 30,
+        11 => 33,
+        12 => 36,
+        13 => 39,
+        14 => 42,
+        15 => 45,
+        _ => 0,
+    }
+}
+
+#[ensures(x < 16 ==> result == x * 3)] //~ ERROR postcondition might not hold
+fn select_wrong(x: u32) -> u32 {
+    match x {
+        0 => 0,
+        1 => 3,
+        2 => 6,
+        3 => 9,
+        4 => 12,
+        5 => 15,
+        6 => 18,
+        7 => 21,
+        8 => 24,
+        9 => 27,
+        10 => 30,
+        11 => 32,
+        12 => 36,
+        13 => 39,
+        14 => 42,
+        15 => 45,
+        _ => 0,
+    }
+}
+
+fn main() {}
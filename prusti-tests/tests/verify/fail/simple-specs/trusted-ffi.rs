@@ -0,0 +1,19 @@
+use prusti_contracts::*;
+
+#[trusted]
+#[requires(len <= 1024)]
+#[ensures(result <= len)]
+fn read_bytes(len: usize) -> usize {
+    unimplemented!()
+}
+
+fn caller_obligation() {
+    read_bytes(2048); //~ ERROR precondition might not hold
+}
+
+fn caller_assumption() {
+    let n = read_bytes(16);
+    assert!(n < 16); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+extern crate prusti_std;
+use prusti_contracts::*;
+
+#[ensures(result.len() == a.len())] //~ ERROR postcondition might not hold
+fn concat(a: &str, b: &str) -> String {
+    let mut result = String::new();
+    result.push_str(a);
+    result.push_str(b);
+    result
+}
+
+fn client(a: &str, b: &str) {
+    let mut s = String::new();
+    s.push_str(a);
+    s.push_str(b);
+    assert!(s.len() == a.len() + b.len());
+    assert!(s.len() == a.len()); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+// compile-flags: -Punsafe_core_proof=true
+
+#![allow(unused)]
+
+use prusti_contracts::*;
+
+fn main() {}
+
+#[requires(n >= 0)]
+#[terminates(Int::new(n))]
+fn factorial(n: i64) -> i64 {
+    if n == 0 {
+        1
+    } else {
+        n * factorial(n - 1)
+    }
+}
+
+#[requires(n >= 0)]
+#[terminates(Int::new(n))]
+fn factorial_without_decrease(n: i64) -> i64 {
+    if n == 0 {
+        1
+    } else {
+        n * factorial_without_decrease(n) //~ ERROR
+    }
+}
@@ -0,0 +1,40 @@
+use prusti_contracts::*;
+
+trait Counter {
+    #[requires(step <= 10)]
+    #[ensures(result >= start)] //~ ERROR postcondition
+    fn advance(&self, start: u32, step: u32) -> u32;
+}
+
+struct Wrapping;
+
+impl Counter for Wrapping {
+    fn advance(&self, start: u32, step: u32) -> u32 {
+        if start > 100 {
+            0
+        } else {
+            start + step
+        }
+    }
+}
+
+trait Bounded {
+    #[requires(x <= 10)] //~ ERROR precondition may not be a valid weakening
+    fn check(&self, x: u32) -> bool;
+}
+
+struct Strict;
+
+#[refine_trait_spec]
+impl Bounded for Strict {
+    #[requires(x <= 5)]
+    fn check(&self, x: u32) -> bool {
+        x <= 5
+    }
+}
+
+fn test_call_through_bound<C: Counter>(counter: &C) {
+    counter.advance(0, 20); //~ ERROR precondition might not hold
+}
+
+fn main() {}
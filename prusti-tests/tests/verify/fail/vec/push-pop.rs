@@ -0,0 +1,18 @@
+extern crate prusti_std;
+use prusti_contracts::*;
+
+#[ensures(v.len() == old(v.len()) + 1)] //~ ERROR postcondition might not hold
+fn push_pop(v: &mut Vec<i32>, x: i32) {
+    v.push(x);
+    v.pop();
+}
+
+fn client() {
+    let mut v = Vec::new();
+    v.push(1);
+    v.push(2);
+    let last = v.pop();
+    prusti_assert!(last === Some(1)); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
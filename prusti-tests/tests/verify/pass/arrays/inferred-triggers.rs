@@ -0,0 +1,62 @@
+// compile-flags: -Pverification_deadline=60
+
+use prusti_contracts::*;
+
+fn main() {}
+
+predicate! {
+    fn non_negative(a: &[i32; 16]) -> bool {
+        forall(|i: usize| (0 <= i && i < 16) ==> a[i] >= 0)
+    }
+}
+
+#[ensures(non_negative(&result))]
+fn zeroes() -> [i32; 16] {
+    let mut a = [0; 16];
+    let mut i = 0;
+    while i < a.len() {
+        body_invariant!(i < 16);
+        body_invariant!(forall(|j: usize| (0 <= j && j < 16) ==> a[j] >= 0));
+        a[i] = 0;
+        i += 1;
+    }
+    a
+}
+
+#[requires(non_negative(a))]
+#[ensures(result >= 0)]
+fn first(a: &[i32; 16]) -> i32 {
+    a[0]
+}
+
+#[requires(non_negative(a) && non_negative(b))]
+#[ensures(forall(|i: usize| (0 <= i && i < 16) ==> result[i] == a[i] || result[i] == b[i]))]
+#[ensures(non_negative(&result))]
+fn pointwise_max(a: &[i32; 16], b: &[i32; 16]) -> [i32; 16] {
+    let mut result = *a;
+    let mut i = 0;
+    while i < 16 {
+        body_invariant!(i < 16);
+        body_invariant!(forall(|j: usize| (0 <= j && j < 16) ==> result[j] == a[j] || result[j] == b[j]));
+        body_invariant!(forall(|j: usize| (0 <= j && j < 16) ==> result[j] >= 0));
+        if b[i] > result[i] {
+            result[i] = b[i];
+        }
+        i += 1;
+    }
+    result
+}
+
+#[requires(forall(|i: usize, j: usize| (0 <= i && i < j && j < s.len()) ==> s[i] <= s[j]))]
+fn sorted(s: &[i32]) {
+    if s.len() > 3 {
+        assert!(s[0] <= s[3]);
+    }
+}
+
+fn client() {
+    let a = zeroes();
+    assert!(first(&a) >= 0);
+    let b = pointwise_max(&a, &a);
+    assert!(b[5] >= 0);
+}
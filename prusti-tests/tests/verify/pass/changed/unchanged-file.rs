@@ -0,0 +1,10 @@
+// compile-flags: -Pchanged=src/other.rs
+
+use prusti_contracts::*;
+
+// This file is not in the list of changed files, so the false postcondition
+// is not checked.
+#[ensures(false)]
+fn wrong() {}
+
+fn main() {}
@@ -0,0 +1,52 @@
+//! Example: snapshots of tuples of various arities, including nested ones
+
+use prusti_contracts::*;
+
+#[ensures(result.0 === p.1)]
+#[ensures(result.1 === p.0)]
+fn swap<A, B>(p: (A, B)) -> (B, A) {
+    (p.1, p.0)
+}
+
+#[pure]
+fn first(t: (i32, bool, u32)) -> i32 {
+    t.0
+}
+
+#[pure]
+fn rotate(t: (i32, bool, u32)) -> (bool, u32, i32) {
+    (t.1, t.2, t.0)
+}
+
+#[pure]
+fn inner(t: ((i32, bool), (u32, (i32, i32)))) -> (i32, i32) {
+    (t.1).1
+}
+
+#[requires(t.0 == 3)]
+#[ensures(result.2 == first(t))]
+#[ensures(result === rotate(t))]
+fn test_triple(t: (i32, bool, u32)) -> (bool, u32, i32) {
+    (t.1, t.2, t.0)
+}
+
+#[ensures(result.0 == ((t.1).1).1 && result.1 == ((t.1).1).0)]
+#[ensures(swap_pure(inner(t)) === result)]
+fn test_nested(t: ((i32, bool), (u32, (i32, i32)))) -> (i32, i32) {
+    let (a, b) = (t.1).1;
+    (b, a)
+}
+
+#[pure]
+fn swap_pure(p: (i32, i32)) -> (i32, i32) {
+    (p.1, p.0)
+}
+
+fn test_swap() {
+    let p = swap((1, true));
+    assert!(p.0 && p.1 == 1);
+    let q = swap(((1, 2), 3u32));
+    assert!(q.0 == 3 && (q.1).1 == 2);
+}
+
+fn main() {}
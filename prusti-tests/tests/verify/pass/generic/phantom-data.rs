@@ -0,0 +1,31 @@
+use prusti_contracts::*;
+use std::marker::PhantomData;
+
+struct Tagged<T> {
+    value: i32,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Tagged<T> {
+    #[ensures(result.value == value)]
+    fn new(value: i32) -> Self {
+        Tagged {
+            value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+// The marker has no state, so tags with equal values have equal snapshots.
+#[requires(a.value == b.value)]
+#[ensures(a === b)]
+fn same_value<T>(a: &Tagged<T>, b: &Tagged<T>) {}
+
+fn client() {
+    let a: Tagged<u64> = Tagged::new(3);
+    let b = Tagged::new(3);
+    same_value(&a, &b);
+    prusti_assert!(a === b);
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+#[terminates]
+fn count_down(mut i: i64) {
+    while i > 0 {
+        body_variant!(Int::new(i));
+        i -= 1;
+    }
+}
+
+#[terminates]
+fn count_down_by_two(mut i: i64) {
+    while i > 1 {
+        body_variant!(Int::new(i));
+        i -= 2;
+    }
+}
+
+#[terminates]
+#[requires(0 <= start && start <= end)]
+fn count_up(start: i64, end: i64) {
+    let mut i = start;
+    while i < end {
+        body_invariant!(i < end);
+        body_variant!(Int::new(end) - Int::new(i));
+        i += 1;
+    }
+}
+
+fn main() {}
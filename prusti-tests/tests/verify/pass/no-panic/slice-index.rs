@@ -0,0 +1,26 @@
+// compile-flags: -Pcheck_panics=false
+
+use prusti_contracts::*;
+
+#[no_panic]
+#[requires(i < s.len())]
+fn get(s: &[i32], i: usize) -> i32 {
+    s[i]
+}
+
+#[no_panic]
+#[requires(s.len() > 0)]
+fn first(s: &[i32]) -> i32 {
+    get(s, 0)
+}
+
+#[no_panic]
+fn checked_get(s: &[i32], i: usize) -> Option<i32> {
+    if i < s.len() {
+        Some(s[i])
+    } else {
+        None
+    }
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+extern crate prusti_std;
+use prusti_contracts::*;
+
+#[ensures(opt.is_some() ==> result == opt.unwrap())]
+#[ensures(opt.is_none() ==> result == 0)]
+fn value_or_zero(opt: Option<u32>) -> u32 {
+    opt.unwrap_or(0)
+}
+
+#[ensures(match opt {
+    Some(value) => result == value,
+    None => result == default,
+})]
+fn value_or(opt: Option<i32>, default: i32) -> i32 {
+    match opt {
+        Some(value) => value,
+        None => default,
+    }
+}
+
+fn test_client() {
+    let some = Some(3);
+    assert!(some.is_some());
+    assert!(some.unwrap_or(5) == 3);
+    let none: Option<u32> = None;
+    assert!(none.is_none());
+    assert!(value_or_zero(none) == 0);
+    assert!(value_or(Some(-1), 2) == -1);
+}
+
+fn main() {}
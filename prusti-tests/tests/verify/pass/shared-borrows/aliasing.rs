@@ -0,0 +1,32 @@
+use prusti_contracts::*;
+
+#[requires(*a < 1000 && *b < 1000)]
+#[ensures(result == *a + *b)]
+fn sum(a: &u32, b: &u32) -> u32 {
+    *a + *b
+}
+
+#[ensures(result == *a)]
+fn first<'a>(a: &'a u32, _b: &'a u32) -> u32 {
+    *a
+}
+
+fn test_aliased() {
+    let x = 7;
+    // Both references share the permission to `x`.
+    let r1 = &x;
+    let r2 = &x;
+    assert!(sum(r1, r2) == 14);
+    assert!(*r1 == *r2);
+    assert!(first(&x, &x) == 7);
+}
+
+fn test_full_permission_after_drop() {
+    let mut x = 1;
+    let s = sum(&x, &x);
+    // The shared borrows ended, so `x` can be written again.
+    x = s;
+    assert!(x == 2);
+}
+
+fn main() {}
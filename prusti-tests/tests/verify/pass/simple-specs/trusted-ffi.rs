@@ -0,0 +1,29 @@
+use prusti_contracts::*;
+
+/// Models a foreign function whose implementation is not visible to Prusti.
+#[trusted]
+#[requires(len <= 1024)]
+#[ensures(result <= len)]
+fn read_bytes(len: usize) -> usize {
+    unimplemented!()
+}
+
+#[trusted]
+#[ensures(result > 0)]
+fn positive() -> i32 {
+    panic!("implemented in C")
+}
+
+#[ensures(result <= 1024)]
+fn read_all() -> usize {
+    let first = read_bytes(512);
+    let second = read_bytes(512);
+    first + second
+}
+
+fn test_positive() {
+    let x = positive();
+    assert!(x != 0);
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+extern crate prusti_std;
+use prusti_contracts::*;
+
+#[ensures(result.len() == a.len() + b.len())]
+#[ensures(forall(|i: usize| i < a.len() ==> result.as_bytes()[i] == a.as_bytes()[i]))]
+fn concat(a: &str, b: &str) -> String {
+    let mut result = String::new();
+    result.push_str(a);
+    result.push_str(b);
+    result
+}
+
+#[ensures(result.len() == old(a.len()) + b.len())]
+fn append(a: String, b: &str) -> String {
+    a + b
+}
+
+#[requires(a.len() > 0)]
+#[ensures(result.len() > 0)]
+#[ensures(result.as_bytes()[0] == a.as_bytes()[0])]
+fn prefix(a: &str, b: &str) -> String {
+    concat(a, b)
+}
+
+fn client(a: &str) {
+    let s = concat(a, a);
+    assert!(s.len() == 2 * a.len());
+    let t = append(s, a);
+    assert!(t.len() == 3 * a.len());
+    assert!(!t.is_empty() || a.is_empty());
+}
+
+fn main() {}
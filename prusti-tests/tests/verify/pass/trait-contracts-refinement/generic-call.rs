@@ -0,0 +1,48 @@
+use prusti_contracts::*;
+
+trait Counter {
+    #[requires(step <= 10)]
+    #[ensures(result >= start)]
+    #[ensures(result <= start + step)]
+    fn advance(&self, start: u32, step: u32) -> u32;
+}
+
+struct Exact;
+
+impl Counter for Exact {
+    fn advance(&self, start: u32, step: u32) -> u32 {
+        start + step
+    }
+}
+
+struct Lazy;
+
+#[refine_trait_spec]
+impl Counter for Lazy {
+    #[requires(true)]
+    #[ensures(result == start)]
+    fn advance(&self, start: u32, _step: u32) -> u32 {
+        start
+    }
+}
+
+#[requires(start <= 100)]
+#[ensures(result <= start + 10)]
+fn advance_twice<C: Counter>(counter: &C, start: u32) -> u32 {
+    let middle = counter.advance(start, 5);
+    counter.advance(middle, 5)
+}
+
+fn test_lazy() {
+    let lazy = Lazy;
+    assert!(lazy.advance(7, 1000) == 7);
+    assert!(advance_twice(&lazy, 3) <= 13);
+}
+
+fn test_exact() {
+    let exact = Exact;
+    let result = advance_twice(&exact, 100);
+    assert!(result >= 100 && result <= 110);
+}
+
+fn main() {}
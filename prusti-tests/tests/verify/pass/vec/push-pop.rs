@@ -0,0 +1,40 @@
+extern crate prusti_std;
+use prusti_contracts::*;
+
+#[ensures(v.len() == old(v.len()))]
+#[ensures(forall(|i: usize| i < v.len() ==> v.as_slice()[i] == old(v.as_slice())[i]))]
+fn push_pop(v: &mut Vec<i32>, x: i32) {
+    v.push(x);
+    let last = v.pop();
+    prusti_assert!(last === Some(x));
+}
+
+#[ensures(result.len() == 2)]
+#[ensures(result.as_slice()[0] == a && result.as_slice()[1] == b)]
+fn pair(a: i32, b: i32) -> Vec<i32> {
+    let mut v = Vec::new();
+    v.push(a);
+    v.push(b);
+    v
+}
+
+#[requires(!v.is_empty())]
+#[ensures(v.len() == old(v.len()) - 1)]
+fn drop_last(v: &mut Vec<i32>) {
+    let last = v.pop();
+    prusti_assert!(matches!(last, Some(_)));
+}
+
+fn client() {
+    let mut v = pair(1, 2);
+    push_pop(&mut v, 3);
+    assert!(v.len() == 2);
+    assert!(v.as_slice()[1] == 2);
+    drop_last(&mut v);
+    drop_last(&mut v);
+    assert!(v.is_empty());
+    let last = v.pop();
+    prusti_assert!(matches!(last, None));
+}
+
+fn main() {}
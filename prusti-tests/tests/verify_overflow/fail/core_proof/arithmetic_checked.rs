@@ -0,0 +1,17 @@
+// compile-flags: -Punsafe_core_proof=true -Parithmetic_mode=checked
+
+use prusti_contracts::*;
+
+#[requires(a == 255 && b == 1)]
+#[ensures(result == 0)]
+fn test1(a: u8, b: u8) -> u8 {
+    a + b   //~ ERROR assertion might fail with "attempt to add with overflow"
+}
+
+#[requires(a == 254 && b == 1)]
+#[ensures(result == 255)]
+fn test2(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+fn main() {}
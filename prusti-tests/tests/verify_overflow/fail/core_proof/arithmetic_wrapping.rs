@@ -0,0 +1,29 @@
+// compile-flags: -Punsafe_core_proof=true -Parithmetic_mode=wrapping
+
+use prusti_contracts::*;
+
+#[requires(a == 255 && b == 1)]
+#[ensures(result == 0)]
+fn test1(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+#[requires(a == 127 && b == 1)]
+#[ensures(result == -128)]
+fn test2(a: i8, b: i8) -> i8 {
+    a + b
+}
+
+#[requires(a == 0 && b == 1)]
+#[ensures(result == 255)]
+fn test3(a: u8, b: u8) -> u8 {
+    a - b
+}
+
+#[requires(a == 255 && b == 1)]
+#[ensures(result == 256)]   //~ ERROR: postcondition might not hold.
+fn test4(a: u8, b: u8) -> u32 {
+    (a + b) as u32
+}
+
+fn main() {}
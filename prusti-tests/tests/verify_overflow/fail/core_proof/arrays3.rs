@@ -0,0 +1,37 @@
+// compile-flags: -Punsafe_core_proof=true -Puse_smt_wrapper=true -Psmt_qi_bound_global=10000 -Psmt_qi_bound_trace=2000 -Psmt_qi_bound_trace_kind=150 -Psmt_qi_bound_global_kind=150
+
+use prusti_contracts::*;
+
+#[requires(a[0] <= a[1])]
+fn test1(mut a: [i32; 2]) {
+    let tmp = a[0];
+    a[0] = a[1];
+    a[1] = tmp;
+    assert!(a[1] <= a[0]);
+}
+
+#[requires(a[0] <= a[1])]
+fn test2(mut a: [i32; 2]) {
+    let tmp = a[0];
+    a[0] = a[1];
+    a[1] = tmp;
+    assert!(a[0] <= a[1]);     //~ ERROR: the asserted expression might not hold
+}
+
+#[requires(a[0] == a[1] && a[1] <= a[2])]
+fn test3(mut a: [i32; 3]) {
+    let tmp = a[0];
+    a[0] = a[1];
+    a[1] = tmp;
+    assert!(a[0] <= a[1] && a[1] <= a[2]);
+}
+
+#[requires(a[0] <= a[1] && a[1] <= a[2])]
+fn test4(mut a: [i32; 3]) {
+    let tmp = a[0];
+    a[0] = a[1];
+    a[1] = tmp;
+    assert!(a[0] <= a[1] && a[1] <= a[2]);     //~ ERROR: the asserted expression might not hold
+}
+
+fn main() {}
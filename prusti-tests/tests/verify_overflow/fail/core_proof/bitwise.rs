@@ -0,0 +1,26 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+#[requires(x != 0)]
+#[ensures(result < x - 1)]   //~ ERROR: postcondition might not hold.
+fn clear_lowest_set_bit(x: u32) -> u32 {
+    x & (x - 1)
+}
+
+#[requires(x <= 1000)]
+#[ensures(result == x * 4)]   //~ ERROR: postcondition might not hold.
+fn shift_left(x: u32) -> u32 {
+    x << 3
+}
+
+fn shift_left_by(x: u32, n: u32) -> u32 {
+    x << n  //~ ERROR assertion might fail with "attempt to shift left with overflow"
+}
+
+#[requires(n <= 32)]
+fn shift_right_by(x: u32, n: u32) -> u32 {
+    x >> n  //~ ERROR assertion might fail with "attempt to shift right with overflow"
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+#[requires(*b < 100)]
+#[ensures(*result == old(*b) + 1)]
+fn increment(mut b: Box<i32>) -> Box<i32> {
+    *b += 1;
+    b
+}
+
+#[requires(*b < 100)]
+#[ensures(*result == old(*b))] //~ ERROR postcondition might not hold.
+fn increment_wrong(mut b: Box<i32>) -> Box<i32> {
+    *b += 1;
+    b
+}
+
+fn test1() {
+    let b = Box::new(5);
+    let b = increment(b);
+    assert!(*b == 5); //~ ERROR the asserted expression might not hold
+}
+
+fn test2(b: Box<i32>) {
+    let _ = increment(b); //~ ERROR precondition might not hold.
+}
+
+fn main() {}
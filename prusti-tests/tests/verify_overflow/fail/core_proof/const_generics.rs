@@ -0,0 +1,36 @@
+// compile-flags: -Punsafe_core_proof=true -Puse_smt_wrapper=true -Psmt_qi_bound_global=10000 -Psmt_qi_bound_trace=2000 -Psmt_qi_bound_trace_kind=150 -Psmt_qi_bound_global_kind=150
+
+use prusti_contracts::*;
+
+#[ensures(result == N)]
+fn length<const N: usize>(a: [i32; N]) -> usize {
+    a.len()
+}
+
+#[ensures(result == N + 1)]     //~ ERROR: postcondition might not hold.
+fn length_wrong<const N: usize>(a: [i32; N]) -> usize {
+    a.len()
+}
+
+#[requires(N > 0)]
+fn first<const N: usize>(a: [i32; N]) -> i32 {
+    a[0]
+}
+
+fn first_unchecked<const N: usize>(a: [i32; N]) -> i32 {
+    a[0]        //~ ERROR: the array or slice index may be out of bounds
+}
+
+fn test1() {
+    let a = [1, 2, 3];
+    let n = length(a);
+    assert!(n == 3);
+}
+
+fn test2() {
+    let a = [1, 2, 3];
+    let n = length(a);
+    assert!(n == 4);     //~ ERROR: the asserted expression might not hold
+}
+
+fn main() {}
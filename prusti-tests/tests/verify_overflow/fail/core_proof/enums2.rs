@@ -0,0 +1,42 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+enum Tree {
+    Leaf(i32),
+    Node(Box<Tree>, Box<Tree>),
+}
+
+#[ensures(result == 5)]
+fn test1() -> u32 {
+    let r: Result<u32, u32> = Ok(5);
+    match r {
+        Ok(v) => v,
+        Err(e) => e,
+    }
+}
+
+#[ensures(result == 6)]     //~ ERROR: postcondition might not hold.
+fn test2() -> u32 {
+    let r: Result<u32, u32> = Ok(5);
+    match r {
+        Ok(v) => v,
+        Err(e) => e,
+    }
+}
+
+fn test3(r: Result<u32, i32>) {
+    match r {
+        Ok(v) => assert!(v >= 0),
+        Err(e) => assert!(e >= 0),      //~ ERROR: the asserted expression might not hold
+    }
+}
+
+fn test4(t: Tree) {
+    match t {
+        Tree::Leaf(v) => assert!(v == 0),      //~ ERROR: the asserted expression might not hold
+        Tree::Node(_, _) => {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,42 @@
+// compile-flags: -Punsafe_core_proof=true -Pelide_guarded_overflow_checks=true
+
+use prusti_contracts::*;
+
+// The overflow checks are kept whenever the guard does not bound the value
+// that is incremented.
+
+fn modified_after_guard(n: u8) {
+    let mut i = 0;
+    while i < n {
+        i = n;
+        i += 1;     //~ ERROR assertion might fail with "attempt to add with overflow"
+    }
+}
+
+fn reset(i: &mut u8) {
+    *i = 255;
+}
+
+fn mutably_borrowed(n: u8) {
+    let mut i = 0;
+    while i < n {
+        reset(&mut i);
+        i += 1;     //~ ERROR assertion might fail with "attempt to add with overflow"
+    }
+}
+
+fn wrong_direction(n: u8) {
+    let mut i = n;
+    while i > 0 {
+        i += 1;     //~ ERROR assertion might fail with "attempt to add with overflow"
+    }
+}
+
+fn not_one(n: u8) {
+    let mut i = 0;
+    while i < n {
+        i += 2;     //~ ERROR assertion might fail with "attempt to add with overflow"
+    }
+}
+
+fn main() {}
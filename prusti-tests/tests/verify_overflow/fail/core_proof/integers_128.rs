@@ -0,0 +1,30 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+#[requires(a <= 18446744073709551616 && b <= 18446744073709551615)]
+fn test1(a: u128, b: u128) -> u128 {
+    a * b
+}
+
+fn test2(a: u128, b: u128) -> u128 {
+    a * b   //~ ERROR assertion might fail with "attempt to multiply with overflow"
+}
+
+#[requires(-10 <= a && a <= 10)]
+fn test3(a: i128) -> i128 {
+    a * 17014118346046923173168730371588410572
+}
+
+#[requires(-11 <= a && a <= 11)]
+fn test4(a: i128) -> i128 {
+    a * 17014118346046923173168730371588410572  //~ ERROR assertion might fail with "attempt to multiply with overflow"
+}
+
+#[requires(a == 340282366920938463463374607431768211455)]
+#[ensures(result == 340282366920938463463374607431768211454)]
+fn test5(a: u128) -> u128 {
+    a - 1
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+fn main() {}
+
+#[requires(xs.len() > 0)]
+#[ensures(*result == xs[0])]
+fn first<'a>(xs: &'a [i32]) -> &'a i32 {
+    &xs[0]
+}
+
+#[requires(xs.len() > 1)]
+#[ensures(*result == xs[0])]     //~ ERROR: postcondition might not hold.
+fn second<'a>(xs: &'a [i32]) -> &'a i32 {
+    &xs[1]
+}
+
+#[ensures(*result == *y)]
+fn pick_second<'a, 'b>(x: &'a i32, y: &'b i32) -> &'b i32 {
+    y
+}
+
+fn call_first() {
+    let a = [1, 2, 3];
+    let x = first(&a);
+    assert!(*x == 1);
+}
+
+fn call_first_assert_false() {
+    let a = [1, 2, 3];
+    let x = first(&a);
+    assert!(*x == 2);      //~ ERROR: the asserted expression might not hold
+}
+
+fn call_pick_second() {
+    let a = 1;
+    let b = 2;
+    let y = pick_second(&a, &b);
+    assert!(*y == 2);
+}
@@ -48,4 +48,42 @@ fn test9() {
     test7(a);    //~ ERROR: precondition might not hold
 }
 
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[pure]
+#[terminates]
+fn get(x: &Point) -> i32 {
+    x.x
+}
+
+#[requires(get(p) == 3)]
+#[ensures(result == 3)]
+fn test10(p: &Point) -> i32 {
+    p.x
+}
+
+fn test11() {
+    let p = Point { x: 3, y: 4 };
+    test10(&p);
+    prusti_assert!(get(&p) == 3);
+}
+
+fn test12() {
+    let p = Point { x: 4, y: 3 };
+    test10(&p);    //~ ERROR: precondition might not hold
+}
+
+#[ensures(get(&result) == 5)]
+fn test13() -> Point {
+    Point { x: 5, y: 6 }
+}
+
+#[ensures(get(&result) == 6)]   //~ ERROR: postcondition might not hold
+fn test14() -> Point {
+    Point { x: 5, y: 6 }
+}
+
 fn main() {}
@@ -0,0 +1,20 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+#[requires(ptr_valid(p))]
+fn read_valid(p: *const i32) -> i32 {
+    unsafe { *p }
+}
+
+fn read_unchecked(p: *const i32) -> i32 {
+    unsafe { *p }   //~ ERROR: the raw pointer might not be valid
+}
+
+#[requires(!ptr_valid(p))] //~ ERROR: ptr_valid cannot be negated
+fn negated(p: *const i32) {}
+
+#[requires(ptr_valid(p) || true)] //~ ERROR: ptr_valid cannot be negated
+fn disjunction(p: *const i32) {}
+
+fn main() {}
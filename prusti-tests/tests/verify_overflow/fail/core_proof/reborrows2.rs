@@ -0,0 +1,34 @@
+// compile-flags: -Punsafe_core_proof=true -Pverify_types=false
+
+use prusti_contracts::*;
+
+#[requires(**x < 100)]
+#[ensures(**x == old(**x) + 1)]
+fn increment_inner(x: &mut &mut i32) {
+    let inner = &mut **x;
+    *inner += 1;
+}
+
+#[requires(**x < 100)]
+#[ensures(**x == old(**x))]     //~ ERROR: postcondition might not hold.
+fn increment_inner_wrong(x: &mut &mut i32) {
+    let inner = &mut **x;
+    *inner += 1;
+}
+
+fn test1() {
+    let mut a = 1;
+    let mut b = &mut a;
+    increment_inner(&mut b);
+    assert!(*b == 2);
+    assert!(a == 2);
+}
+
+fn test2() {
+    let mut a = 1;
+    let mut b = &mut a;
+    increment_inner(&mut b);
+    assert!(a == 1);     //~ ERROR: the asserted expression might not hold
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn reflexivity(a: Point) {
+    prusti_assert!(snapshot_equality_reflexivity(a));
+}
+
+fn congruence(a: Point, b: Point) {
+    prusti_assume!(a.x == b.x && a.y == b.y);
+    prusti_assert!(snapshot_equality_congruence(a, b));
+}
+
+fn congruence_different_fields(a: Point, b: Point) {
+    prusti_assume!(a.x == b.x);
+    prusti_assert!(snapshot_equality_congruence(a, b));   //~ ERROR: the precondition of a function called in the asserted expression might not hold
+}
+
+fn main() {}
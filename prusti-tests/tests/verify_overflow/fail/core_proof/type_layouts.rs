@@ -0,0 +1,38 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+use std::mem::{align_of, size_of};
+
+struct Pair {
+    a: u32,
+    b: u64,
+}
+
+#[requires(n == size_of::<u32>())]
+fn test1(n: usize) {
+    assert!(n == 4);
+}
+
+#[requires(n == size_of::<u32>())]
+fn test2(n: usize) {
+    assert!(n == 8);     //~ ERROR: the asserted expression might not hold
+}
+
+#[requires(n == size_of::<Pair>())]
+fn test3(n: usize) {
+    assert!(n >= 12);
+}
+
+#[requires(n == size_of::<Pair>())]
+#[requires(m == align_of::<Pair>())]
+fn test4(n: usize, m: usize) {
+    assert!(m > 0);
+    assert!(n % m == 0);
+}
+
+#[requires(n == size_of::<Pair>())]
+fn test5(n: usize) {
+    assert!(n == 16);     //~ ERROR: the asserted expression might not hold
+}
+
+fn main() {}
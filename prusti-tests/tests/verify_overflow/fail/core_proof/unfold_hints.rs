@@ -0,0 +1,47 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+struct LinkedList {
+    val: i32,
+    next: Box<LinkedList>,
+}
+
+fn test1(mut list: LinkedList) {
+    #[allow(unused_must_use, unused_variables)]
+    if false {
+        #[prusti::spec_only]
+        || -> bool { true };
+        unsafe { prusti_unfold_owned(std::ptr::addr_of!(list)) };
+    }
+    list.val = 1;
+    #[allow(unused_must_use, unused_variables)]
+    if false {
+        #[prusti::spec_only]
+        || -> bool { true };
+        unsafe { prusti_fold_owned(std::ptr::addr_of!(list)) };
+    }
+    assert!(list.val == 1);
+}
+
+fn test2(mut list: LinkedList) {
+    #[allow(unused_must_use, unused_variables)]
+    if false {
+        #[prusti::spec_only]
+        || -> bool { true };
+        unsafe { prusti_unfold_owned(std::ptr::addr_of!(list)) };
+    }
+    list.val = 1;
+    assert!(list.val == 2);     //~ ERROR: the asserted expression might not hold
+}
+
+fn test3(list: LinkedList) {
+    #[allow(unused_must_use, unused_variables)]
+    if false {
+        #[prusti::spec_only]
+        || -> bool { true };
+        unsafe { prusti_unfold_owned(std::ptr::addr_of!(list.val)) };     //~ ERROR: fold and unfold hints are supported only for structs, not for `i32`
+    }
+}
+
+fn main() {}
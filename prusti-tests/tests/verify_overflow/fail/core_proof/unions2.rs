@@ -0,0 +1,35 @@
+// compile-flags: -Punsafe_core_proof=true -Puse_smt_wrapper=true -Psmt_qi_bound_global=10000 -Psmt_qi_bound_trace=200 -Psmt_qi_bound_trace_kind=5 -Psmt_qi_bound_global_kind=20
+
+use prusti_contracts::*;
+
+#[repr(C)]
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+fn test1() {
+    let mut a = Bits { int: 1 };
+    a.int = 2;
+    assert!(unsafe { a.int == 2 });
+}
+
+fn test2() {
+    let mut a = Bits { int: 1 };
+    a.float = 2.0;
+    let _x = unsafe { a.int };   //~ ERROR: failed to unpack the capability of union's field
+}
+
+fn test3() {
+    let mut a = Bits { float: 1.0 };
+    a.int = 3;
+    assert!(unsafe { a.int == 3 });
+}
+
+fn test4() {
+    let mut a = Bits { float: 1.0 };
+    a.int = 3;
+    assert!(unsafe { a.int == 4 });   //~ ERROR: the asserted expression might not hold
+}
+
+fn main() {}
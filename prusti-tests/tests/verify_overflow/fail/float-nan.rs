@@ -0,0 +1,27 @@
+use prusti_contracts::*;
+
+fn test_self_eq(a: f64) {
+    assert!(a == a); //~ ERROR
+}
+
+#[ensures(result == a)] //~ ERROR
+fn test_identity(a: f32) -> f32 {
+    a
+}
+
+fn test_trichotomy(a: f64, b: f64) {
+    assert!(a < b || a == b || a > b); //~ ERROR
+}
+
+fn test_nan_ne() {
+    let nan = 0.0f64 / 0.0f64;
+    assert!(nan == nan); //~ ERROR
+}
+
+fn test_nan_not_comparable() {
+    let nan = 0.0f64 / 0.0f64;
+    assert!(nan != nan);
+    assert!(!(nan < 0.0) && !(nan >= 0.0));
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+// compile-flags: -Penable_type_invariants=true
+use prusti_contracts::*;
+
+// field writes that break the invariant
+
+#[invariant(self.0 != 0)]
+struct NonZero(u32);
+
+impl NonZero {
+    fn clear(&mut self) { //~ ERROR type invariants
+        self.0 = 0;
+    }
+
+    fn decrement(&mut self) { //~ ERROR type invariants
+        self.0 -= 1;
+    }
+}
+
+fn make_zero() -> NonZero { //~ ERROR type invariants
+    NonZero(0)
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use prusti_contracts::*;
+
+#[trusted]
+fn opaque() -> u32 {
+    42
+}
+
+fn client() {
+    let x = opaque();
+    // Nothing is known about the result of `opaque`.
+    prusti_assume!(x == 42);
+    assert!(x + 1 == 43);
+    prusti_assert!(x > 0);
+}
+
+#[requires(a.len() > 0)]
+fn first(a: &[u32]) -> u32 {
+    a[0]
+}
+
+fn slice_client(a: &[u32]) {
+    prusti_assume!(a.len() > 3);
+    let x = first(a);
+    let y = a[3];
+    prusti_assert!(x == a[0] && y == a[3]);
+}
+
+fn main() {}
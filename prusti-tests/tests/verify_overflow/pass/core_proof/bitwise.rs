@@ -0,0 +1,47 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+#[requires(x != 0)]
+#[ensures(result < x)]
+fn clear_lowest_set_bit(x: u32) -> u32 {
+    x & (x - 1)
+}
+
+#[requires(x <= 1000)]
+#[ensures(result == x * 8)]
+fn shift_left(x: u32) -> u32 {
+    x << 3
+}
+
+#[requires(n < 64)]
+fn shift_left_by(x: u64, n: u8) -> u64 {
+    x << n
+}
+
+#[ensures(result == x / 2)]
+fn shift_right(x: u16) -> u16 {
+    x >> 1
+}
+
+#[ensures(result == x)]
+fn or_zero(x: u8) -> u8 {
+    x | 0
+}
+
+#[ensures(result == 0)]
+fn xor_self(x: u8) -> u8 {
+    x ^ x
+}
+
+#[ensures(result == x)]
+fn and_all_ones(x: i32) -> i32 {
+    x & -1
+}
+
+#[ensures(result == (a != b))]
+fn xor_bool(a: bool, b: bool) -> bool {
+    a ^ b
+}
+
+fn main() {}
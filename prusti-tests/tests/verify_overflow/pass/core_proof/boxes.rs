@@ -0,0 +1,36 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+#[requires(*b < 100)]
+#[ensures(*result == old(*b) + 1)]
+fn increment(mut b: Box<i32>) -> Box<i32> {
+    *b += 1;
+    b
+}
+
+fn consume(b: Box<i32>) {
+    drop(b);
+}
+
+fn test1() {
+    let b = Box::new(5);
+    assert!(*b == 5);
+    let b = increment(b);
+    assert!(*b == 6);
+}
+
+fn test2() {
+    let b = Box::new(5);
+    let c = b;
+    assert!(*c == 5);
+    consume(c);
+}
+
+fn test3() {
+    let mut b = Box::new(Box::new(1));
+    **b = 2;
+    assert!(**b == 2);
+}
+
+fn main() {}
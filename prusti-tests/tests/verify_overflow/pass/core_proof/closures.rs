@@ -0,0 +1,41 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+fn call_once<F: FnOnce() -> u32>(f: F) -> u32 {
+    f()
+}
+
+fn call<F: Fn() -> u32>(f: &F) -> u32 {
+    f()
+}
+
+fn call_mut<F: FnMut()>(f: &mut F) {
+    f()
+}
+
+fn test1() {
+    let counter = 5u32;
+    let get = move || counter;
+    let _ = call_once(get);
+}
+
+fn test2() {
+    let counter = 5u32;
+    let get = || counter;
+    let _ = call(&get);
+    let _ = get();
+}
+
+fn test3() {
+    let mut counter = 0u32;
+    let mut increment = || {
+        if counter < 100 {
+            counter += 1;
+        }
+    };
+    increment();
+    call_mut(&mut increment);
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+// compile-flags: -Punsafe_core_proof=true -Pelide_guarded_overflow_checks=true
+
+use prusti_contracts::*;
+
+// The overflow checks of the induction variables are guarded by the loop
+// conditions, so they are assumed instead of being verified.
+
+#[ensures(result == n)]
+fn count_up(n: u64) -> u64 {
+    let mut i = 0;
+    while i < n {
+        body_invariant!(i < n);
+        i += 1;
+    }
+    i
+}
+
+#[ensures(result == lower)]
+fn count_down(lower: i8, upper: i8) -> i8 {
+    let mut i = upper;
+    if i < lower {
+        return lower;
+    }
+    while i > lower {
+        body_invariant!(i > lower);
+        i -= 1;
+    }
+    i
+}
+
+fn sum_to(n: u32) -> u32 {
+    let mut i = 0;
+    let mut steps = 0;
+    while n > i {
+        body_invariant!(steps == i);
+        let j = i;
+        i += 1;
+        steps = j + 1;
+    }
+    steps
+}
+
+fn main() {}
@@ -0,0 +1,48 @@
+// compile-flags: -Punsafe_core_proof=true -Pverify_types=false
+
+use prusti_contracts::*;
+
+#[requires(*x < 100)]
+#[ensures(*x == old(*x) + 1)]
+fn increment(x: &mut i32) {
+    *x += 1;
+}
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[requires(p.x < 100)]
+#[ensures(p.x == old(p.x) + 1 && p.y == old(p.y))]
+fn move_right(p: &mut Point) {
+    p.x += 1;
+}
+
+#[ensures(*a == old(*b) && *b == old(*a))]
+fn swap(a: &mut i32, b: &mut i32) {
+    let tmp = *a;
+    *a = *b;
+    *b = tmp;
+}
+
+fn test1() {
+    let mut a = 1;
+    increment(&mut a);
+    assert!(a == 2);
+}
+
+fn test2() {
+    let mut p = Point { x: 1, y: 2 };
+    move_right(&mut p);
+    assert!(p.x == 2 && p.y == 2);
+}
+
+fn test3() {
+    let mut a = 1;
+    let mut b = 2;
+    swap(&mut a, &mut b);
+    assert!(a == 2 && b == 1);
+}
+
+fn main() {}
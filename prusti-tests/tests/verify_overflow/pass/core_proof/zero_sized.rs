@@ -0,0 +1,28 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+use std::{marker::PhantomData, mem::size_of};
+
+struct Tagged<T> {
+    value: i32,
+    _marker: PhantomData<T>,
+}
+
+struct Empty;
+
+#[requires(n == size_of::<PhantomData<u64>>())]
+fn test1(n: usize) {
+    assert!(n == 0);
+}
+
+#[requires(n == size_of::<Empty>())]
+fn test2(n: usize) {
+    assert!(n == 0);
+}
+
+#[requires(n == size_of::<Tagged<u64>>())]
+fn test3(n: usize) {
+    assert!(n >= 4);
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+// compile-flags: -Pieee_floats=false
+
+use prusti_contracts::*;
+
+fn test_self_eq(a: f64) {
+    assert!(a == a);
+}
+
+#[ensures(result == a)]
+fn test_identity(a: f32) -> f32 {
+    a
+}
+
+fn test_trichotomy(a: f64, b: f64) {
+    assert!(a < b || a == b || a > b);
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+// compile-flags: -Penable_type_invariants=true
+use prusti_contracts::*;
+
+// field writes that re-establish the invariant
+
+#[invariant(self.0 != 0)]
+struct NonZero(u32);
+
+impl NonZero {
+    #[requires(value != 0)]
+    fn new(value: u32) -> Self {
+        NonZero(value)
+    }
+
+    fn set(&mut self, value: u32) {
+        if value != 0 {
+            self.0 = value;
+        }
+    }
+
+    fn decrement(&mut self) {
+        if self.0 > 1 {
+            self.0 -= 1;
+        }
+    }
+
+    fn get(&self) -> u32 {
+        self.0
+    }
+}
+
+fn client() {
+    let mut x = NonZero::new(2);
+    x.decrement();
+    x.set(0);
+    assert!(x.get() != 0);
+}
+
+fn main() {}
@@ -21,11 +21,14 @@ pub struct Optimizations {
     pub delete_unused_predicates: bool,
     pub optimize_folding: bool,
     pub remove_empty_if: bool,
+    pub fold_constant_switches: bool,
+    pub sort_switch_arms: bool,
     pub purify_vars: bool,
     pub fix_quantifiers: bool,
     pub fix_unfoldings: bool,
     pub remove_unused_vars: bool,
     pub remove_trivial_assertions: bool,
+    pub inline_single_use_blocks: bool,
     pub clean_cfg: bool,
 }
 
@@ -36,11 +39,14 @@ impl Optimizations {
             delete_unused_predicates: false,
             optimize_folding: false,
             remove_empty_if: false,
+            fold_constant_switches: false,
+            sort_switch_arms: false,
             purify_vars: false,
             fix_quantifiers: false,
             fix_unfoldings: false,
             remove_unused_vars: false,
             remove_trivial_assertions: false,
+            inline_single_use_blocks: false,
             clean_cfg: false,
         }
     }
@@ -51,12 +57,19 @@ impl Optimizations {
             delete_unused_predicates: true,
             optimize_folding: true,
             remove_empty_if: true,
+            // Opt-in: has to be listed explicitly in `optimizations`
+            fold_constant_switches: false,
+            // Opt-in: has to be listed explicitly in `optimizations`
+            sort_switch_arms: false,
             purify_vars: true,
             fix_quantifiers: true,
             // Disabled because https://github.com/viperproject/prusti-dev/issues/892 has been fixed
             fix_unfoldings: false,
             remove_unused_vars: true,
             remove_trivial_assertions: true,
+            // Opt-in: merged blocks disappear from the labels that other
+            // flags, such as `delete_basic_blocks`, refer to
+            inline_single_use_blocks: false,
             clean_cfg: true,
         }
     }
@@ -70,24 +83,38 @@ lazy_static::lazy_static! {
         // 0. Default values
         settings.set_default("be_rustc", false).unwrap();
         settings.set_default("viper_backend", "Silicon").unwrap();
+        settings.set_default::<Option<String>>("backend", None).unwrap();
         settings.set_default::<Option<String>>("smt_solver_path", env::var("Z3_EXE").ok()).unwrap();
         settings.set_default::<Option<String>>("smt_solver_wrapper_path", None).unwrap();
+        settings.set_default::<Option<String>>("z3_path", None).unwrap();
+        settings.set_default::<Vec<String>>("smt_options", vec![]).unwrap();
         settings.set_default::<Option<String>>("boogie_path", env::var("BOOGIE_EXE").ok()).unwrap();
         settings.set_default::<Option<String>>("viper_home", None).unwrap();
         settings.set_default::<Option<String>>("java_home", None).unwrap();
 
         settings.set_default::<Option<u32>>("check_timeout", None).unwrap();
+        settings.set_default::<Option<u64>>("method_timeout", None).unwrap();
         settings.set_default("check_foldunfold_state", false).unwrap();
+        settings.set_default("check_unassigned_returns", false).unwrap();
         settings.set_default("check_overflows", true).unwrap();
+        settings.set_default("arithmetic_mode", "checked").unwrap();
+        settings
+            .set_default("elide_guarded_overflow_checks", false)
+            .unwrap();
         settings.set_default("check_panics", true).unwrap();
         settings.set_default("encode_unsigned_num_constraint", true).unwrap();
         settings.set_default("encode_bitvectors", false).unwrap();
+        settings.set_default("ieee_floats", true).unwrap();
         settings.set_default("simplify_encoding", true).unwrap();
+        settings.set_default("balanced_switch_lowering", false).unwrap();
+        settings.set_default("canonical_block_order", false).unwrap();
         settings.set_default("log", "").unwrap();
         settings.set_default("log_style", "auto").unwrap();
         settings.set_default("log_dir", "log").unwrap();
         settings.set_default("log_tracing", false).unwrap();
         settings.set_default("cache_path", "").unwrap();
+        settings.set_default::<Option<String>>("cache_dir", None).unwrap();
+        settings.set_default("cache_lowering", false).unwrap();
         settings.set_default("dump_debug_info", false).unwrap();
         settings.set_default("dump_debug_info_during_fold", false).unwrap();
         settings.set_default("dump_nll_facts", false).unwrap();
@@ -96,11 +123,14 @@ lazy_static::lazy_static! {
         settings.set_default("dump_path_ctxt_in_debug_info", false).unwrap();
         settings.set_default("dump_reborrowing_dag_in_debug_info", false).unwrap();
         settings.set_default("dump_borrowck_info", false).unwrap();
+        settings.set_default::<Option<String>>("dump_viper", None).unwrap();
         settings.set_default("dump_viper_program", false).unwrap();
+        settings.set_default("selftest", false).unwrap();
         settings.set_default("foldunfold_state_filter", "").unwrap();
         settings.set_default::<Vec<String>>("extra_jvm_args", vec![]).unwrap();
         settings.set_default::<Vec<String>>("extra_verifier_args", vec![]).unwrap();
         settings.set_default("quiet", false).unwrap();
+        settings.set_default("summary", false).unwrap();
         settings.set_default("assert_timeout", 10_000).unwrap();
         settings.set_default("smt_qi_eager_threshold", 1000).unwrap();
         settings.set_default("use_more_complete_exhale", true).unwrap();
@@ -111,7 +141,12 @@ lazy_static::lazy_static! {
         settings.set_default("no_verify_deps", false).unwrap();
         settings.set_default("opt_in_verification", false).unwrap();
         settings.set_default("full_compilation", false).unwrap();
+        settings.set_default("check_only", false).unwrap();
         settings.set_default("json_communication", false).unwrap();
+        settings.set_default("json_results", false).unwrap();
+        settings.set_default("stats", false).unwrap();
+        settings.set_default("stop_on_first_error", false).unwrap();
+        settings.set_default::<Option<String>>("specs", None).unwrap();
         settings.set_default("optimizations", "all").unwrap();
         settings.set_default("intern_names", true).unwrap();
         settings.set_default("enable_purification_optimization", false).unwrap();
@@ -133,6 +168,7 @@ lazy_static::lazy_static! {
 
         settings.set_default("print_desugared_specs", false).unwrap();
         settings.set_default("print_typeckd_specs", false).unwrap();
+        settings.set_default("print_cfg", false).unwrap();
         settings.set_default("print_collected_verification_items", false).unwrap();
         settings.set_default("hide_uuids", false).unwrap();
         settings.set_default("counterexample", false).unwrap();
@@ -167,7 +203,10 @@ lazy_static::lazy_static! {
         settings.set_default("verify_only_preamble", false).unwrap();
         settings.set_default("enable_verify_only_basic_block_path", false).unwrap();
         settings.set_default::<Vec<String>>("verify_only_basic_block_path", vec![]).unwrap();
+        settings.set_default::<Vec<String>>("verify_only_items", vec![]).unwrap();
+        settings.set_default::<Vec<String>>("changed", vec![]).unwrap();
         settings.set_default::<Vec<String>>("delete_basic_blocks", vec![]).unwrap();
+        settings.set_default::<Vec<String>>("disable_pass", vec![]).unwrap();
 
         // Get the list of all allowed flags.
         let mut allowed_keys = get_keys(&settings);
@@ -208,18 +247,42 @@ lazy_static::lazy_static! {
                 .ignore_empty(true)
                 .try_parsing(true)
                 .with_list_parse_key("delete_basic_blocks")
+                .with_list_parse_key("disable_pass")
                 .with_list_parse_key("extra_jvm_args")
                 .with_list_parse_key("extra_verifier_args")
+                .with_list_parse_key("smt_options")
                 .with_list_parse_key("verify_only_basic_block_path")
+                .with_list_parse_key("verify_only_items")
+                .with_list_parse_key("changed")
                 .list_separator(" ")
         ).unwrap();
         check_keys(&settings, &allowed_keys, "the `PRUSTI_*` environment variables");
 
-        // 4. Override with command-line arguments -P<arg>=<val>
+        // 4. Override with command-line arguments -P<arg>=<val> and --prusti-<arg>[=<val>]
+        settings.merge(
+            CommandLine::with_prefix("-P")
+                .ignore_invalid(true)
+                .with_alias("json", "json_results")
+                .with_alias("only", "verify_only_items")
+                .with_alias("timeout", "method_timeout")
+                .with_list_parse_key("disable_pass")
+                .with_list_parse_key("smt_options")
+                .with_list_parse_key("verify_only_items")
+                .with_list_parse_key("changed")
+        ).unwrap();
         settings.merge(
-            CommandLine::with_prefix("-P").ignore_invalid(true)
+            CommandLine::with_prefix("--prusti-")
+                .ignore_invalid(true)
+                .bare_flags(true)
+                .with_alias("json", "json_results")
+                .with_alias("only", "verify_only_items")
+                .with_alias("timeout", "method_timeout")
+                .with_list_parse_key("disable_pass")
+                .with_list_parse_key("smt_options")
+                .with_list_parse_key("verify_only_items")
+                .with_list_parse_key("changed")
         ).unwrap();
-        check_keys(&settings, &allowed_keys, "the `-P` command line arguments");
+        check_keys(&settings, &allowed_keys, "the `-P` and `--prusti-` command line arguments");
 
         settings
     });
@@ -246,8 +309,10 @@ fn check_keys(settings: &Config, allowed_keys: &FxHashSet<String>, source: &str)
 
 /// Return vector of arguments filtered out by prefix
 pub fn get_filtered_args() -> Vec<String> {
+    let long_args = CommandLine::with_prefix("--prusti-").bare_flags(true);
     CommandLine::with_prefix("-P")
         .get_remaining_args()
+        .filter(|arg| !long_args.is_valid_arg(arg))
         .collect::<Vec<String>>()
 }
 
@@ -305,26 +370,51 @@ pub fn check_foldunfold_state() -> bool {
     read_setting("check_foldunfold_state")
 }
 
+/// When enabled, a warning is reported for every formal return of an encoded
+/// Viper method that might not be assigned before the method returns.
+pub fn check_unassigned_returns() -> bool {
+    read_setting("check_unassigned_returns")
+}
+
 /// Verification backend to use. Possible values:
 ///
 /// - `Carbon` - verification-condition-generation-based backend
 ///   [Carbon](https://github.com/viperproject/carbon).
 /// - `Silicon` - symbolic-execution-based backend
 ///   [Silicon](https://github.com/viperproject/silicon/).
+///
+/// The `backend` flag, which is meant to be passed to the driver as
+/// `--prusti-backend=<name>`, takes precedence over `viper_backend`.
 pub fn viper_backend() -> String {
-    read_setting::<String>("viper_backend")
+    read_setting::<Option<String>>("backend")
+        .unwrap_or_else(|| read_setting("viper_backend"))
         .to_lowercase()
         .trim()
         .to_string()
 }
 
 /// The path to the SMT solver to use. `prusti-rustc` is expected to set this
-/// configuration flag to the correct path to Z3.
+/// configuration flag to the correct path to Z3. It is overridden by
+/// `z3_path`.
 pub fn smt_solver_path() -> String {
-    read_setting::<Option<String>>("smt_solver_path")
+    z3_path()
+        .or_else(|| read_setting::<Option<String>>("smt_solver_path"))
         .expect("Please set the smt_solver_path configuration flag")
 }
 
+/// A user-provided path to Z3, usually passed to the driver as
+/// `--prusti-z3-path=<path>`. When set, it takes precedence over
+/// `smt_solver_path`.
+pub fn z3_path() -> Option<String> {
+    read_setting("z3_path")
+}
+
+/// Additional options of the form `key=value` that are passed to Z3, usually
+/// given to the driver as `--prusti-smt-options=<key=value>,...`.
+pub fn smt_options() -> Vec<String> {
+    read_setting("smt_options")
+}
+
 /// The path to the SMT solver wrapper. `prusti-rustc` is expected to set this
 /// configuration flag to the correct path.
 pub fn smt_solver_wrapper_path() -> String {
@@ -373,6 +463,22 @@ pub fn simplify_encoding() -> bool {
     read_setting("simplify_encoding")
 }
 
+/// When enabled, a `GotoSwitch` whose guards compare the same expression with
+/// distinct integer constants is lowered into a balanced binary search instead
+/// of a linear chain of conditionals.
+///
+/// **Note:** This flag is internal and may be removed without notice.
+pub fn balanced_switch_lowering() -> bool {
+    read_setting("balanced_switch_lowering")
+}
+
+/// When enabled, the blocks of a method are emitted in a canonical order that
+/// follows the control flow from the first block, with ties broken by label,
+/// instead of in the order of their labels.
+pub fn canonical_block_order() -> bool {
+    read_setting("canonical_block_order")
+}
+
 /// When enabled, debug files will be created.
 pub fn dump_debug_info() -> bool {
     read_setting("dump_debug_info")
@@ -416,6 +522,14 @@ pub fn dump_borrowck_info() -> bool {
     read_setting("dump_borrowck_info")
 }
 
+/// A path to which the encoded Viper programs are written, usually passed to
+/// the driver as `--prusti-dump-viper=<path>`. If the path is a directory,
+/// each program is written to its own file in it; otherwise all programs are
+/// written to the given file.
+pub fn dump_viper() -> Option<String> {
+    read_setting("dump_viper")
+}
+
 /// When enabled, the encoded Viper program will be output.
 pub fn dump_viper_program() -> bool {
     read_setting("dump_viper_program")
@@ -449,9 +563,31 @@ pub fn log_tracing() -> bool {
 /// Path to a cache file, where verification cache will be loaded from and
 /// saved to. The default empty string disables saving any cache to disk.
 /// A path to a file which does not yet exist will result in using an empty
-/// cache, but then creating and saving to that location on exit.
+/// cache, but then creating and saving to that location on exit. When
+/// `cache_dir` is set, the cache file `cache.bin` in that directory is used
+/// instead.
 pub fn cache_path() -> PathBuf {
-    PathBuf::from(read_setting::<String>("cache_path"))
+    match cache_dir() {
+        Some(dir) => dir.join("cache.bin"),
+        None => PathBuf::from(read_setting::<String>("cache_path")),
+    }
+}
+
+/// Path to a directory in which the verification cache is stored, in the file
+/// `cache.bin`. The directory is created when the cache is saved. Takes
+/// precedence over `cache_path`.
+pub fn cache_dir() -> Option<PathBuf> {
+    read_optional_setting::<String>("cache_dir")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+/// When enabled, the lowered core proof encoding of a procedure is reused when
+/// the same procedure is lowered again within one compilation, for example when
+/// a tool requests the encoding of a procedure that is also verified. The cache
+/// is kept only in memory.
+pub fn cache_lowering() -> bool {
+    read_setting("cache_lowering")
 }
 
 /// When enabled, binary operations and numeric casts will be checked for
@@ -460,12 +596,70 @@ pub fn check_overflows() -> bool {
     read_setting("check_overflows")
 }
 
+/// The semantics of integer arithmetic operations that are checked for
+/// overflows. Possible values:
+///
+/// - `checked` - an overflowing operation is reported as a potential overflow.
+/// - `wrapping` - addition, subtraction, and multiplication wrap around on
+///   overflow.
+/// - `unchecked` - operations are assumed to never overflow.
+///
+/// **Note:** This option is taken into account only when `unsafe_core_proof` is
+/// true.
+///
+/// The driver rejects other values with [check_arithmetic_mode] before
+/// compiling anything.
+pub fn arithmetic_mode() -> String {
+    let mode = read_arithmetic_mode();
+    assert!(
+        matches!(mode.as_str(), "checked" | "wrapping" | "unchecked"),
+        "Unknown arithmetic mode '{mode}'. Possible values: checked, wrapping, unchecked"
+    );
+    mode
+}
+
+/// Check that `arithmetic_mode` is set to one of its possible values.
+pub fn check_arithmetic_mode() -> Result<(), String> {
+    let mode = read_arithmetic_mode();
+    if matches!(mode.as_str(), "checked" | "wrapping" | "unchecked") {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unknown arithmetic mode '{mode}'. Possible values: checked, wrapping, unchecked"
+        ))
+    }
+}
+
+fn read_arithmetic_mode() -> String {
+    read_setting::<String>("arithmetic_mode")
+        .to_lowercase()
+        .trim()
+        .to_string()
+}
+
+/// When enabled, the overflow checks of `i + 1` and `i - 1` are assumed
+/// instead of asserted if they are guarded by `i < n` and `i > n`
+/// respectively, and `i` is not modified in between, as in counted loops.
+///
+/// **Note:** This option is taken into account only when `unsafe_core_proof` is
+/// true.
+pub fn elide_guarded_overflow_checks() -> bool {
+    read_setting("elide_guarded_overflow_checks")
+}
+
 /// When enabled, non-negativity of unsigned integers will be encoded and
 /// checked.
 pub fn encode_unsigned_num_constraint() -> bool {
     read_setting("encode_unsigned_num_constraint")
 }
 
+/// When enabled, floats are encoded as IEEE values, which may be NaN or
+/// infinite. Otherwise, floats are assumed to be finite, which approximates
+/// them by reals.
+pub fn ieee_floats() -> bool {
+    read_setting("ieee_floats")
+}
+
 /// When enabled, bitwise integer operations are encoded using bitvectors.
 ///
 /// **Note:** this option is highly experimental.
@@ -489,6 +683,14 @@ pub fn quiet() -> bool {
     read_setting("quiet")
 }
 
+/// When enabled, the per-crate progress messages are replaced by a single
+/// line that reports how many methods were verified, how many failed, and
+/// how long the verification took. Verification errors are still reported in
+/// full. The summary is printed even if `quiet` is enabled.
+pub fn summary() -> bool {
+    read_setting("summary")
+}
+
 /// Maximum time (in milliseconds) for the verifier to spend on a single
 /// assertion. Set to `0` to disable timeout. Maps to the verifier command-line
 /// argument `--assertTimeout`.
@@ -510,6 +712,15 @@ pub fn check_timeout() -> Option<u32> {
     read_setting("check_timeout")
 }
 
+/// Maximum time (in milliseconds) for the verifier to spend on a single
+/// Viper program, which usually contains a single method. A program that
+/// exceeds it is reported as timed out, without affecting the other programs.
+/// The timeout is rounded up to whole seconds and passed to Silicon as
+/// `--timeout` or to Boogie as `/timeLimit`.
+pub fn method_timeout() -> Option<u64> {
+    read_setting("method_timeout")
+}
+
 /// When enabled, a more complete `exhale` version is used in the verifier.
 /// See [`consolidate`](https://github.com/viperproject/silicon/blob/f48de7f6e2d90d9020812869c713a5d3e2035995/src/main/scala/rules/StateConsolidator.scala#L29-L46).
 /// Equivalent to the verifier command-line argument
@@ -533,6 +744,12 @@ pub fn print_typeckd_specs() -> bool {
     read_setting("print_typeckd_specs")
 }
 
+/// When enabled, prints the VIR methods to stderr before they are lowered to
+/// Viper, after they have been optimized if `simplify_encoding` is enabled.
+pub fn print_cfg() -> bool {
+    read_setting("print_cfg")
+}
+
 /// When enabled, UUIDs of expressions and specifications printed with
 /// `PRINT_TYPECKD_SPECS` are hidden.
 pub fn hide_uuids() -> bool {
@@ -646,6 +863,14 @@ pub fn server_max_concurrency() -> Option<usize> {
     read_optional_setting("server_max_concurrency")
 }
 
+/// When enabled and no input file is given, the driver verifies a built-in
+/// trivial program and reports whether the verification backend could be
+/// reached. Ignored if there is an input. Usually passed as
+/// `--prusti-selftest`.
+pub fn selftest() -> bool {
+    read_setting("selftest")
+}
+
 /// When set to an address and port (e.g. `"127.0.0.1:2468"`), Prusti will
 /// connect to the given server and use it for its verification backend.
 ///
@@ -661,6 +886,35 @@ pub fn json_communication() -> bool {
     read_setting("json_communication")
 }
 
+/// When enabled, the verification result of each method is printed to stdout
+/// as a JSON array after verification. The array is printed even if nothing
+/// was verified.
+pub fn json_results() -> bool {
+    read_setting("json_results")
+}
+
+/// When enabled, the size of the encoding and the time spent in the backend
+/// for each verified method are printed to stderr as a tab-separated table
+/// after verification.
+pub fn stats() -> bool {
+    read_setting("stats")
+}
+
+/// When enabled, no further methods are verified after the first method whose
+/// verification failed. By default, all methods are verified and all failures
+/// are reported.
+pub fn stop_on_first_error() -> bool {
+    read_setting("stop_on_first_error")
+}
+
+/// A path to a file with external specifications of the crate being verified,
+/// usually passed to the driver as `--prusti-specs=<path>`. The items of the
+/// file are added to the root module of the crate before macro expansion, as
+/// if they were written inline.
+pub fn specs() -> Option<String> {
+    read_setting("specs")
+}
+
 /// When enabled, Viper name mangling will be disabled.
 ///
 /// **Note:** This is very likely to result in invalid programs being generated
@@ -694,6 +948,19 @@ pub fn verify_only_basic_block_path() -> Vec<String> {
     read_setting("verify_only_basic_block_path")
 }
 
+/// When non-empty, only the functions with one of the given paths (e.g.
+/// `module::function`) are verified, and the other ones are not encoded.
+pub fn verify_only_items() -> Vec<String> {
+    read_setting("verify_only_items")
+}
+
+/// When non-empty, only the functions defined in one of the given source
+/// files are verified, and the other ones are not encoded. A file matches if
+/// the path of the defining file ends with it.
+pub fn changed() -> Vec<String> {
+    read_setting("changed")
+}
+
 /// Comma-separated list of optimizations to enable, or `"all"` to enable all.
 /// Possible values in the list are:
 ///
@@ -701,11 +968,14 @@ pub fn verify_only_basic_block_path() -> Vec<String> {
 /// - `"delete_unused_predicates"`
 /// - `"optimize_folding"`
 /// - `"remove_empty_if"`
+/// - `"fold_constant_switches"` (not enabled by `"all"`)
+/// - `"sort_switch_arms"` (not enabled by `"all"`)
 /// - `"purify_vars"`
 /// - `"fix_quantifiers"`
 /// - `"fix_unfoldings"`
 /// - `"remove_unused_vars"`
 /// - `"remove_trivial_assertions"`
+/// - `"inline_single_use_blocks"` (not enabled by `"all"`)
 /// - `"clean_cfg"`
 pub fn optimizations() -> Optimizations {
     let optimizations_string = read_setting::<String>("optimizations");
@@ -720,11 +990,14 @@ pub fn optimizations() -> Optimizations {
             "delete_unused_predicates" => opt.delete_unused_predicates = true,
             "optimize_folding" => opt.optimize_folding = true,
             "remove_empty_if" => opt.remove_empty_if = true,
+            "fold_constant_switches" => opt.fold_constant_switches = true,
+            "sort_switch_arms" => opt.sort_switch_arms = true,
             "purify_vars" => opt.purify_vars = true,
             "fix_quantifiers" => opt.fix_quantifiers = true,
             "fix_unfoldings" => opt.fix_unfoldings = true,
             "remove_unused_vars" => opt.remove_unused_vars = true,
             "remove_trivial_assertions" => opt.remove_trivial_assertions = true,
+            "inline_single_use_blocks" => opt.inline_single_use_blocks = true,
             "clean_cfg" => opt.clean_cfg = true,
             _ => warn!("Ignoring Unkown optimization '{}'", trimmed),
         }
@@ -959,6 +1232,12 @@ pub fn delete_basic_blocks() -> Vec<String> {
     read_setting("delete_basic_blocks")
 }
 
+/// The names of the transformation passes of the core proof encoding that
+/// should not be run.
+pub fn disable_pass() -> Vec<String> {
+    read_setting("disable_pass")
+}
+
 /// When enabled, features not supported by Prusti will be reported as warnings
 /// rather than errors.
 pub fn skip_unsupported_features() -> bool {
@@ -1002,6 +1281,12 @@ pub fn full_compilation() -> bool {
     read_setting("full_compilation")
 }
 
+/// When enabled, compilation is stopped after verification even if
+/// `full_compilation` is enabled.
+pub fn check_only() -> bool {
+    read_setting("check_only")
+}
+
 /// When enabled, Viper identifiers are interned to shorten them when possible.
 pub fn intern_names() -> bool {
     read_setting("intern_names")
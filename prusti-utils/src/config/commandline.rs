@@ -23,6 +23,27 @@ pub struct CommandLine {
     /// Note: the method get_remaining_args always
     ///       returns the invalid args regardless of this boolean
     ignore_invalid: bool,
+
+    /// Boolean indicating whether args without a value are accepted as flags
+    /// that are set to `true`. When enabled, dashes in keys are also replaced
+    /// by underscores.
+    ///
+    /// Example: The arg --prusti-print-cfg would become print_cfg=true with a
+    ///          prefix of --prusti-
+    bare_flags: bool,
+
+    /// Keys whose values are comma-separated lists. Repeated occurrences of
+    /// such a key are concatenated instead of overriding each other.
+    ///
+    /// Example: The args -Pkey=a,b -Pkey=c would become key=["a", "b", "c"]
+    list_keys: Vec<String>,
+
+    /// Alternative names of keys, as pairs of alias and key. An arg using an
+    /// alias is collected under the key it stands for.
+    ///
+    /// Example: With the alias ("json", "json_results"), the arg --prusti-json
+    ///          would become json_results=true
+    aliases: Vec<(String, String)>,
 }
 
 impl CommandLine {
@@ -55,6 +76,32 @@ impl CommandLine {
         self
     }
 
+    #[must_use]
+    pub fn bare_flags(mut self, bare_flags: bool) -> Self {
+        self.bare_flags = bare_flags;
+        self
+    }
+
+    #[must_use]
+    pub fn with_list_parse_key(mut self, key: &str) -> Self {
+        self.list_keys.push(key.to_lowercase());
+        self
+    }
+
+    #[must_use]
+    pub fn with_alias(mut self, alias: &str, key: &str) -> Self {
+        self.aliases
+            .push((alias.to_lowercase(), key.to_lowercase()));
+        self
+    }
+
+    fn resolve_alias(&self, key: String) -> String {
+        self.aliases
+            .iter()
+            .find(|(alias, _)| *alias == key)
+            .map_or(key, |(_, target)| target.clone())
+    }
+
     /// Return String iterator of arguments that are invalid.
     pub fn get_remaining_args(self) -> impl Iterator<Item = String> {
         env::args().filter(move |arg| !self.is_valid_arg(arg))
@@ -71,12 +118,32 @@ impl CommandLine {
         arg.splitn(2, &self.separator).map(|s| s.to_owned())
     }
 
+    fn split_key_value(&self, arg: &str) -> (String, String) {
+        let arg = &arg[self.get_prefix().len()..];
+        let (key, val) = if self.bare_flags && !arg.contains(&self.separator) {
+            (arg.to_owned(), String::from("true"))
+        } else {
+            // If arg is valid this can't panic
+            self.split_arg(arg).next_tuple().unwrap()
+        };
+        if self.bare_flags {
+            (key.replace('-', "_"), val)
+        } else {
+            (key, val)
+        }
+    }
+
     // An argument is valid if it begins with the optional
     // prefix, the first occurrence of the separator pattern
-    // separates two non-empty strings.
-    fn is_valid_arg(&self, arg: &str) -> bool {
+    // separates two non-empty strings. With `bare_flags`, a
+    // non-empty string without the separator is valid as well.
+    pub fn is_valid_arg(&self, arg: &str) -> bool {
         let prefix = self.get_prefix();
         if arg.starts_with(&prefix) {
+            let rest = &arg[prefix.len()..];
+            if self.bare_flags && !rest.is_empty() && !rest.contains(&self.separator) {
+                return true;
+            }
             return self
                 .split_arg(&arg[prefix.len()..])
                 .map(|s| if s.is_empty() { 3 } else { 1 })
@@ -93,6 +160,9 @@ impl Default for CommandLine {
             prefix: None,
             separator: String::from("="),
             ignore_invalid: false,
+            bare_flags: false,
+            list_keys: vec![],
+            aliases: vec![],
         }
     }
 }
@@ -104,10 +174,9 @@ impl Source for CommandLine {
 
     fn collect(&self) -> Result<HashMap<String, Value>, ConfigError> {
         let mut m = HashMap::new();
+        let mut lists: HashMap<String, Vec<String>> = HashMap::new();
         let uri = String::from("command-line");
 
-        let prefix_pattern = self.get_prefix();
-
         for arg in env::args() {
             if !self.is_valid_arg(&arg) {
                 if !self.ignore_invalid {
@@ -119,12 +188,21 @@ impl Source for CommandLine {
                 continue;
             }
 
-            // If arg is valid this can't panic
-            let (key, val) = self
-                .split_arg(&arg[prefix_pattern.len()..])
-                .next_tuple()
-                .unwrap();
-            m.insert(key.to_lowercase(), Value::new(Some(&uri), val));
+            let (key, val) = self.split_key_value(&arg);
+            let key = self.resolve_alias(key.to_lowercase());
+            if self.list_keys.contains(&key) {
+                lists.entry(key).or_default().extend(
+                    val.split(',')
+                        .map(|s| s.trim())
+                        .filter(|s| !s.is_empty())
+                        .map(|s| s.to_owned()),
+                );
+            } else {
+                m.insert(key, Value::new(Some(&uri), val));
+            }
+        }
+        for (key, values) in lists {
+            m.insert(key, Value::new(Some(&uri), values));
         }
 
         Ok(m)
@@ -5,6 +5,7 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 #![deny(unused_must_use)]
+use once_cell::sync::OnceCell;
 use serde::Deserialize;
 use std::{
     env,
@@ -171,12 +172,27 @@ pub fn get_rust_toolchain_channel() -> String {
     }
 }
 
-/// Find Prusti's sysroot
-pub fn prusti_sysroot() -> Option<PathBuf> {
-    match env::var("RUST_SYSROOT") {
-        Ok(prusti_sysroot) => Some(PathBuf::from(prusti_sysroot)),
-        Err(_) => get_sysroot_from_rustup(),
+/// Find Prusti's sysroot. The `RUST_SYSROOT` and `SYSROOT` environment
+/// variables are checked before falling back to `rustup`, so that no process
+/// needs to be spawned when one of them is set. The environment variables are
+/// read on every call, while the answer of `rustup` is computed at most once
+/// per process.
+pub fn prusti_sysroot() -> Result<PathBuf, String> {
+    if let Ok(sysroot) = env::var("RUST_SYSROOT").or_else(|_| env::var("SYSROOT")) {
+        return Ok(PathBuf::from(sysroot));
     }
+    static RUSTUP_SYSROOT: OnceCell<Option<PathBuf>> = OnceCell::new();
+    RUSTUP_SYSROOT
+        .get_or_init(get_sysroot_from_rustup)
+        .clone()
+        .ok_or_else(|| {
+            format!(
+                "failed to find the sysroot of the Rust toolchain {}; make sure that `rustup` \
+            is on the PATH and that RUSTUP_HOME points to its installation, or set the \
+            SYSROOT environment variable",
+                get_rust_toolchain_channel()
+            )
+        })
 }
 
 fn get_sysroot_from_rustup() -> Option<PathBuf> {
@@ -190,7 +206,11 @@ fn get_sysroot_from_rustup() -> Option<PathBuf> {
         .ok()
         .and_then(|out| {
             print!("{}", String::from_utf8(out.stderr).ok().unwrap());
-            String::from_utf8(out.stdout).ok()
+            if out.status.success() {
+                String::from_utf8(out.stdout).ok()
+            } else {
+                None
+            }
         })
         .map(|s| PathBuf::from(s.trim().to_owned()))
 }
@@ -18,7 +18,7 @@ use prusti_rustc_interface::{
     },
     span::source_map::Spanned,
 };
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::{iter, vec};
 use viper::silicon_counterexample::*;
 
@@ -44,6 +44,116 @@ pub fn backtranslate(
     Counterexample::new(counterexample_entry_vec)
 }
 
+/// A basic block on the path taken by a counterexample.
+#[derive(Debug, Clone)]
+pub struct TraceStep {
+    /// The label of the block as it appears in the encoded procedure.
+    pub label: String,
+    /// The MIR basic block from which the block was encoded, `None` for the
+    /// blocks that the encoding introduces, such as the blocks on edges.
+    pub basic_block: Option<mir::BasicBlock>,
+    /// The span of the first statement of the block, if it has one.
+    pub span: Option<MultiSpan>,
+}
+
+/// Reconstructs the path taken by the counterexample from the values of the
+/// block markers in the model. The blocks are identified by their labels and
+/// the markers by the names of the blocks, so the trace does not depend on the
+/// order in which the blocks were emitted.
+///
+/// The core proof does not encode procedures as `CfgMethod`s, so the blocks
+/// of the trace are mapped back to the MIR basic blocks they were encoded
+/// from instead of to `CfgBlockIndex`es.
+pub fn backtranslate_trace(
+    encoder: &Encoder,
+    position_manager: &PositionManager,
+    def_id: ProcedureDefId,
+    silicon_counterexample: &SiliconCounterexample,
+) -> Vec<TraceStep> {
+    let mut translator = CounterexampleTranslator::new(encoder, def_id, silicon_counterexample);
+    translator.create_mapping(def_id, encoder);
+    let label_markers = translator.get_label_markers(false);
+    translator.get_block_trace(position_manager, &label_markers)
+}
+
+/// The MIR basic block from which the block with the given label was encoded.
+/// The copies of blocks created when desugaring loops are labelled
+/// `loop__{head}__{block}`.
+fn original_basic_block(label: &str) -> Option<mir::BasicBlock> {
+    if label.starts_with("label__from__") {
+        return None;
+    }
+    let block_label = label.rsplit("__").next()?;
+    let index = block_label
+        .strip_prefix("label_bb")?
+        .parse::<usize>()
+        .ok()?;
+    Some(mir::BasicBlock::from_usize(index))
+}
+
+/// Orders the visited blocks as they were executed, starting from the entry
+/// block. When a block has several visited successors, which happens at merge
+/// points and loop heads, the successor from which the others can be reached
+/// is taken first, so that the body of a loop comes before its exit and the
+/// branch of a conditional before the block where it merges. When the path
+/// reaches a block all of whose visited successors are already in the trace,
+/// for example at the end of a loop body, it continues from the latest block
+/// in the trace that has a visited successor that is not. Because a marker
+/// records only whether a block was visited, each block is reported once.
+fn order_visited_blocks(
+    entry: &str,
+    successors: &FxHashMap<String, Vec<String>>,
+    is_visited: impl Fn(&String) -> bool,
+) -> Vec<String> {
+    let visited_successors = |label: &String| -> Vec<&String> {
+        successors
+            .get(label)
+            .into_iter()
+            .flatten()
+            .filter(|successor| is_visited(successor))
+            .collect()
+    };
+    let reaches = |from: &String, to: &String| -> bool {
+        let mut reached = FxHashSet::default();
+        let mut worklist = vec![from];
+        while let Some(label) = worklist.pop() {
+            if label == to {
+                return true;
+            }
+            if reached.insert(label) {
+                worklist.extend(visited_successors(label));
+            }
+        }
+        false
+    };
+    let mut trace: Vec<String> = Vec::new();
+    let mut seen = FxHashSet::default();
+    let mut next = Some(entry.to_string());
+    while let Some(label) = next.take() {
+        seen.insert(label.clone());
+        trace.push(label);
+        for block in trace.iter().rev() {
+            let candidates: Vec<&String> = visited_successors(block)
+                .into_iter()
+                .filter(|successor| !seen.contains(*successor))
+                .collect();
+            if let Some(first) = candidates.first() {
+                let successor = candidates
+                    .iter()
+                    .find(|candidate| {
+                        candidates
+                            .iter()
+                            .all(|other| other == *candidate || reaches(**candidate, *other))
+                    })
+                    .unwrap_or(first);
+                next = Some((*successor).clone());
+                break;
+            }
+        }
+    }
+    trace
+}
+
 pub struct CounterexampleTranslator<'ce, 'tcx, 'v> {
     encoder: &'ce Encoder<'v, 'tcx>,
     silicon_counterexample: &'ce SiliconCounterexample,
@@ -96,6 +206,52 @@ impl<'ce, 'tcx, 'v> CounterexampleTranslator<'ce, 'tcx, 'v> {
             .collect::<FxHashMap<String, bool>>()
     }
 
+    /// Follow the visited blocks starting from the entry block. The blocks
+    /// inserted on edges (`label__from__*__to__*`) have no marker and are
+    /// visited if the block they jump to is.
+    fn get_block_trace(
+        &self,
+        position_manager: &PositionManager,
+        label_markers: &FxHashMap<String, bool>,
+    ) -> Vec<TraceStep> {
+        let Some(entry) = &self.var_mapping.entry_label else {
+            return Vec::new();
+        };
+        let is_visited = |label: &String| -> bool {
+            if label.starts_with("label__from__") {
+                self.var_mapping
+                    .labels_successor_mapping
+                    .get(label)
+                    .map_or(false, |successors| {
+                        successors
+                            .iter()
+                            .any(|successor| label_markers.get(successor) == Some(&true))
+                    })
+            } else {
+                label_markers.get(label) == Some(&true)
+            }
+        };
+        order_visited_blocks(
+            entry,
+            &self.var_mapping.labels_successor_mapping,
+            is_visited,
+        )
+        .into_iter()
+        .map(|label| {
+            let span = self
+                .var_mapping
+                .labels_position_mapping
+                .get(&label)
+                .and_then(|position| position_manager.source_span.get(&position.id).cloned());
+            TraceStep {
+                basic_block: original_basic_block(&label),
+                label,
+                span,
+            }
+        })
+        .collect()
+    }
+
     //Given a MIR var name, it returns all relevant snapshot variables
     fn get_trace_of_var(
         &self,
@@ -747,3 +903,82 @@ fn get_discriminant_of_vardef(vardef: &ty::VariantDef) -> Option<u32> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cfg(edges: &[(&str, &[&str])]) -> FxHashMap<String, Vec<String>> {
+        edges
+            .iter()
+            .map(|(label, successors)| {
+                (
+                    label.to_string(),
+                    successors.iter().map(|s| s.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn order(cfg: &FxHashMap<String, Vec<String>>, visited: &[&str]) -> Vec<String> {
+        order_visited_blocks("entry", cfg, |label| visited.contains(&label.as_str()))
+    }
+
+    #[test]
+    fn trace_follows_the_taken_branch_of_a_diamond() {
+        let cfg = cfg(&[
+            ("entry", &["then", "else"]),
+            ("then", &["merge"]),
+            ("else", &["merge"]),
+            ("merge", &[]),
+        ]);
+        assert_eq!(
+            order(&cfg, &["entry", "else", "merge"]),
+            ["entry", "else", "merge"]
+        );
+    }
+
+    #[test]
+    fn trace_continues_after_a_merge_with_the_branch_first() {
+        let cfg = cfg(&[
+            ("entry", &["merge", "then"]),
+            ("then", &["merge"]),
+            ("merge", &[]),
+        ]);
+        assert_eq!(
+            order(&cfg, &["entry", "then", "merge"]),
+            ["entry", "then", "merge"]
+        );
+    }
+
+    #[test]
+    fn trace_continues_after_a_loop() {
+        let cfg = cfg(&[
+            ("entry", &["head"]),
+            ("head", &["exit", "body"]),
+            ("body", &["head"]),
+            ("exit", &[]),
+        ]);
+        assert_eq!(
+            order(&cfg, &["entry", "head", "body", "exit"]),
+            ["entry", "head", "body", "exit"]
+        );
+    }
+
+    #[test]
+    fn original_basic_blocks_are_recovered_from_labels() {
+        assert_eq!(
+            original_basic_block("label_bb3"),
+            Some(mir::BasicBlock::from_usize(3))
+        );
+        assert_eq!(
+            original_basic_block("loop__label_bb1__label_bb4"),
+            Some(mir::BasicBlock::from_usize(4))
+        );
+        assert_eq!(
+            original_basic_block("label__from__label_bb1__to__label_bb2"),
+            None
+        );
+        assert_eq!(original_basic_block("label_entry"), None);
+    }
+}
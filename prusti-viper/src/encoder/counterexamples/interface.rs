@@ -1,6 +1,6 @@
 use rustc_hash::FxHashMap;
 use vir_crate::{
-    common::check_mode::CheckMode,
+    common::{check_mode::CheckMode, position::Positioned},
     low::{self as vir_low},
 };
 
@@ -15,6 +15,8 @@ pub(crate) struct BasicBlock {
     pub(crate) label: String,
     pub(crate) successor: Vec<String>,
     pub(crate) stmts: Vec<vir_low::Statement>,
+    /// The position of the first statement of the block that has one.
+    pub(crate) position: Option<vir_low::Position>,
 }
 
 impl MirProcedureMapping {
@@ -50,10 +52,16 @@ impl MirProcedureMapping {
                         labels.iter().map(|(_, label)| label.name.clone()).collect()
                     }
                 };
+                let position = basic_block
+                    .statements
+                    .iter()
+                    .map(|statement| statement.position())
+                    .find(|position| !position.is_default());
                 BasicBlock {
                     label: basic_block.label.name.clone(),
                     successor,
                     stmts,
+                    position,
                 }
             })
             .collect::<Vec<BasicBlock>>()
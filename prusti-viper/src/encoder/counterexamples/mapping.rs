@@ -13,8 +13,12 @@ use vir_low::ast::{
 pub(crate) struct VarMapping {
     //Mapping between mir varable names and basic blocks of snapshotvariables
     pub(crate) var_snaphot_mapping: FxHashMap<String, FxHashMap<String, Vec<SnapshotVar>>>,
+    //The label of the entry block
+    pub(crate) entry_label: Option<String>,
     //Mapping of all labels and its successors label
     pub(crate) labels_successor_mapping: FxHashMap<String, Vec<String>>,
+    //Mapping of labels to the position of the first statement of the block
+    pub(crate) labels_position_mapping: FxHashMap<String, vir_low::Position>,
     //Mapping of pure function calls per basic block
     pub(crate) pure_functions_mapping: FxHashMap<String, Vec<PureFunction>>,
 }
@@ -54,12 +58,22 @@ impl<'ce, 'tcx, 'v> VarMappingInterface
     fn create_mapping(&mut self, proc_def_id: ProcedureDefId, encoder: &Encoder) {
         let name = encoder.env().name.get_absolute_item_name(proc_def_id);
         if let Some(mir_procedure_mapping) = encoder.get_mapping(name) {
+            // The blocks on the edges leaving a block are emitted before it.
+            self.var_mapping.entry_label = mir_procedure_mapping
+                .iter()
+                .find(|basic_block| !basic_block.label.starts_with("label__from__"))
+                .map(|basic_block| basic_block.label.clone());
             for basic_block in mir_procedure_mapping {
                 let label = &basic_block.label;
 
                 self.var_mapping
                     .labels_successor_mapping
                     .insert(label.clone(), basic_block.successor.clone());
+                if let Some(position) = basic_block.position {
+                    self.var_mapping
+                        .labels_position_mapping
+                        .insert(label.clone(), position);
+                }
                 for statement in &basic_block.stmts {
                     let snapshot_var_option = match statement {
                         vir_low::Statement::Assume(assume) => self.extract_var_from_assume(assume),
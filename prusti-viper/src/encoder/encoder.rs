@@ -9,10 +9,13 @@ use super::{
     counterexamples::{DiscriminantsState, MirProcedureMapping, MirProcedureMappingInterface},
     high::{
         builtin_functions::HighBuiltinFunctionEncoderState,
+        procedures::HighProcedureEncoderInterface,
         to_typed::types::HighToTypedTypeEncoderState,
         types::{HighTypeEncoderInterface, HighTypeEncoderState},
     },
-    middle::core_proof::{MidCoreProofEncoderInterface, MidCoreProofEncoderState},
+    middle::core_proof::{
+        MidCoreProofEncoderInterface, MidCoreProofEncoderState, ProgramStatistics,
+    },
     mir::{
         contracts::ContractsEncoderState,
         procedures::MirProcedureEncoderState,
@@ -97,6 +100,10 @@ pub struct Encoder<'v, 'tcx: 'v> {
     vir_program_before_foldunfold_writer: Option<RefCell<Box<dyn Write>>>,
     vir_program_before_viper_writer: Option<RefCell<Box<dyn Write>>>,
     encoding_errors_counter: RefCell<usize>,
+    /// Trusted procedures whose bodies were not encoded.
+    skipped_trusted_procedures: RefCell<FxHashSet<ProcedureDefId>>,
+    /// The `prusti_assume!` closures that were encoded as assumptions.
+    encoded_assumptions: RefCell<FxHashSet<DefId>>,
     name_interner: RefCell<NameInterner>,
     /// Maps locals to the local of their discriminant.
     pub(super) discriminants_state: DiscriminantsState,
@@ -174,6 +181,8 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
             snapshot_encoder_state: Default::default(),
             mirror_encoder: RefCell::new(MirrorEncoder::new()),
             encoding_errors_counter: RefCell::new(0),
+            skipped_trusted_procedures: RefCell::new(FxHashSet::default()),
+            encoded_assumptions: RefCell::new(FxHashSet::default()),
             name_interner: RefCell::new(NameInterner::new()),
             is_encoding_trigger: Cell::new(false),
             specifications_state: SpecificationsState::new(def_spec),
@@ -247,6 +256,28 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         std::mem::take(&mut self.programs)
     }
 
+    /// Encodes a single procedure with the core proof encoding and lowers it
+    /// to a Viper program. Unlike the encoding driven by the verifier, the
+    /// program is returned to the caller instead of being queued for
+    /// verification, so that other tools can reuse the encoding.
+    pub fn encode_core_proof_program(
+        &mut self,
+        proc_def_id: ProcedureDefId,
+        check_mode: CheckMode,
+    ) -> Result<vir_crate::low::Program, PrustiError> {
+        let procedure = self.encode_procedure_core_proof(proc_def_id, check_mode)?;
+        let program = self.lower_procedure_core_proof(proc_def_id, procedure)?;
+        Ok(program)
+    }
+
+    /// The metrics that were collected while lowering the core proof
+    /// programs, keyed by the program name and the check mode.
+    pub(crate) fn get_core_proof_statistics(
+        &mut self,
+    ) -> FxHashMap<(String, CheckMode), ProgramStatistics> {
+        self.take_core_proof_statistics()
+    }
+
     pub fn get_core_proof_programs(&mut self) -> Vec<prusti_common::vir::program::Program> {
         if config::counterexample() && config::unsafe_core_proof() {
             self.take_core_proof_programs()
@@ -277,6 +308,23 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         *self.encoding_errors_counter.borrow()
     }
 
+    /// The number of trusted procedures whose bodies were skipped. Only their
+    /// contracts are used, at their call sites.
+    pub fn count_skipped_trusted_procedures(&self) -> usize {
+        self.skipped_trusted_procedures.borrow().len()
+    }
+
+    /// Records that the `prusti_assume!` with the given closure was encoded.
+    pub(super) fn register_encoded_assumption(&self, def_id: DefId) {
+        self.encoded_assumptions.borrow_mut().insert(def_id);
+    }
+
+    /// The number of `prusti_assume!` statements that were encoded. They are
+    /// assumed without being checked, so they need to be audited.
+    pub fn count_encoded_assumptions(&self) -> usize {
+        self.encoded_assumptions.borrow().len()
+    }
+
     pub(super) fn get_mirror_domain(&self) -> Option<vir::Domain> {
         self.mirror_encoder.borrow().get_domain().cloned()
     }
@@ -805,6 +853,12 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
                     info!("Encoding: {} ({})", proc_name, proc_def_path);
                     assert!(substs.is_empty());
 
+                    if self.is_trusted(proc_def_id, None) {
+                        self.skipped_trusted_procedures
+                            .borrow_mut()
+                            .insert(proc_def_id);
+                    }
+
                     if config::unsafe_core_proof() {
                         if self.env.query.is_unsafe_function(proc_def_id) {
                             if let Err(error) =
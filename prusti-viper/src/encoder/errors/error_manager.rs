@@ -80,6 +80,8 @@ pub enum ErrorCtxt {
     AssertLoopInvariantAfterIteration,
     /// An error when assuming the loop invariant on entry.
     UnexpectedAssumeLoopInvariantOnEntry,
+    /// An error when assuming an overflow check that provably succeeds.
+    UnexpectedAssumeOverflowCheck,
     /// A generic loop variant error.
     LoopVariant,
     /// Loop Variant doesn't hold on entry or after iteration
@@ -124,6 +126,8 @@ pub enum ErrorCtxt {
     WritePlace,
     /// An error related to an assignment.
     Assign,
+    /// An error related to a read through a raw pointer.
+    RawPointerDeref,
     /// A pure function definition
     #[allow(dead_code)]
     PureFunctionDefinition,
@@ -181,6 +185,8 @@ pub enum ErrorCtxt {
     CloseFracRef,
     /// Failed to set an active variant of an union.
     SetEnumVariant,
+    /// Failed to apply a user-provided fold or unfold hint.
+    FoldUnfoldHint,
     /// A user assumption raised an error
     Assumption,
     /// The state that fold-unfold algorithm deduced as unreachable, is actually
@@ -282,6 +288,26 @@ impl<'tcx> ErrorManager<'tcx> {
             .and_then(|id| self.position_manager.def_id.get(&id).copied())
     }
 
+    /// Whether the verification error is reported at a potential panic site,
+    /// such as an explicit panic, a failing Rust assertion or an out of bounds
+    /// index.
+    pub fn is_panic_error(&self, ver_error: &VerificationError) -> bool {
+        let error_ctxt = ver_error
+            .offending_pos_id
+            .as_ref()
+            .and_then(|id| id.parse::<u64>().ok())
+            .and_then(|id| self.error_contexts.get(&id));
+        matches!(
+            error_ctxt,
+            Some(
+                ErrorCtxt::Panic(_)
+                    | ErrorCtxt::AssertTerminator(_)
+                    | ErrorCtxt::BoundsCheckAssert
+                    | ErrorCtxt::SliceRangeBoundsCheckAssert(_)
+            )
+        )
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     pub fn translate_verification_error(&self, ver_error: &VerificationError) -> PrustiError {
         let opt_pos_id: Option<u64> = match ver_error.offending_pos_id {
@@ -383,6 +409,13 @@ impl<'tcx> ErrorManager<'tcx> {
                     .set_failing_assertion(opt_cause_span)
             }
 
+            ("application.precondition:assertion.false", ErrorCtxt::Panic(PanicCause::Assert)) => {
+                PrustiError::verification(
+                    "the precondition of a function called in the asserted expression might not hold",
+                    error_span
+                ).set_failing_assertion(opt_cause_span)
+            }
+
             ("assert.failed:assertion.false", ErrorCtxt::Panic(PanicCause::Unreachable)) => {
                 PrustiError::verification("unreachable!(..) statement might be reachable", error_span)
                     .set_failing_assertion(opt_cause_span)
@@ -596,6 +629,23 @@ impl<'tcx> ErrorManager<'tcx> {
                 ).set_failing_assertion(opt_cause_span)
             }
 
+            ("application.precondition:insufficient.permission", ErrorCtxt::RawPointerDeref) => {
+                PrustiError::verification(
+                    "the raw pointer might not be valid".to_string(),
+                    error_span
+                ).set_failing_assertion(opt_cause_span)
+                .set_help("add a precondition `ptr_valid(...)` for the dereferenced pointer.")
+            }
+
+            ("fold.failed:insufficient.permission", ErrorCtxt::FoldUnfoldHint) |
+            ("fold.failed:assertion.false", ErrorCtxt::FoldUnfoldHint) => {
+                PrustiError::verification(
+                    "the fold hint might not be applicable.".to_string(),
+                    error_span
+                ).set_failing_assertion(opt_cause_span)
+                .set_help("check that all fields of the place are initialized.")
+            }
+
             ("unfold.failed:insufficient.permission", ErrorCtxt::UnfoldUnionVariant) => {
                 PrustiError::verification(
                     "failed to unpack the capability of union's field.".to_string(),
@@ -605,6 +655,31 @@ impl<'tcx> ErrorManager<'tcx> {
                 .add_note("Prusti does not support yet reinterpreting memory of Rust unions' fields and allow reading only the field that was previously initialized.", None)
             }
 
+            ("exhale.failed:insufficient.permission", ErrorCtxt::MovePlace) |
+            ("exhale.failed:insufficient.permission", ErrorCtxt::CopyPlace) |
+            ("exhale.failed:insufficient.permission", ErrorCtxt::WritePlace) |
+            ("exhale.failed:insufficient.permission", ErrorCtxt::Assign) |
+            ("exhale.failed:insufficient.permission", ErrorCtxt::OpenMutRef) |
+            ("exhale.failed:insufficient.permission", ErrorCtxt::OpenFracRef) |
+            ("exhale.failed:insufficient.permission", ErrorCtxt::CloseMutRef) |
+            ("exhale.failed:insufficient.permission", ErrorCtxt::CloseFracRef) |
+            ("exhale.failed:insufficient.permission", ErrorCtxt::LifetimeExhale) |
+            ("assert.failed:insufficient.permission", ErrorCtxt::OpenMutRef) |
+            ("assert.failed:insufficient.permission", ErrorCtxt::OpenFracRef) => {
+                let message = match insufficient_permission_access(&ver_error.message) {
+                    Some(access) => format!(
+                        "{} might lack the required permission {}",
+                        permission_failure_subject(error_ctxt), access
+                    ),
+                    None => format!(
+                        "{} might lack the required permission",
+                        permission_failure_subject(error_ctxt)
+                    ),
+                };
+                PrustiError::verification(message, error_span)
+                    .set_failing_assertion(opt_cause_span)
+            }
+
             ("assert.failed:assertion.false", ErrorCtxt::AssertMethodPreconditionWeakening) => {
                 PrustiError::verification("the method's precondition may not be a valid weakening of the trait's precondition.".to_string(), error_span)
                     .set_help("The trait's precondition should imply the implemented method's precondition.")
@@ -743,3 +818,42 @@ impl<'tcx> ErrorManager<'tcx> {
         }
     }
 }
+
+/// Describes the operation whose permission exhale failed.
+fn permission_failure_subject(error_ctxt: &ErrorCtxt) -> &'static str {
+    match error_ctxt {
+        ErrorCtxt::MovePlace => "the moved place",
+        ErrorCtxt::CopyPlace => "the copied place",
+        ErrorCtxt::WritePlace | ErrorCtxt::Assign => "the assigned place",
+        ErrorCtxt::OpenMutRef | ErrorCtxt::OpenFracRef => "the dereferenced borrow",
+        ErrorCtxt::CloseMutRef | ErrorCtxt::CloseFracRef => "the closed borrow",
+        ErrorCtxt::LifetimeExhale => "the borrowed lifetime",
+        _ => "this statement",
+    }
+}
+
+/// Extracts the accessed resource together with its permission amount from
+/// a Viper "insufficient permission" message. For example, for
+/// `Exhale might fail. There might be insufficient permission to access
+/// acc(x.f, 1/2) (@3.5)` it returns `acc(x.f, 1/2)`.
+fn insufficient_permission_access(message: &str) -> Option<&str> {
+    const MARKER: &str = "insufficient permission to access ";
+    let start = message.find(MARKER)? + MARKER.len();
+    let access = message[start..].trim_end();
+    // Strip the trailing Viper position, e.g. ` (@3.5)` or ` (<no position>)`.
+    let access = match access.rfind(" (") {
+        Some(position_start)
+            if access.ends_with(')')
+                && (access[position_start..].contains('@')
+                    || access[position_start..].contains("<no position>")) =>
+        {
+            &access[..position_start]
+        }
+        _ => access,
+    };
+    if access.is_empty() {
+        None
+    } else {
+        Some(access)
+    }
+}
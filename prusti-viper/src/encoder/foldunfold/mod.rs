@@ -1051,6 +1051,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> vir::CfgReplacer<PathCtxt<'p>, ActionVec> for FoldUnf
         let new_succ = match succ {
             vir::Successor::Undefined => vir::Successor::Undefined,
             vir::Successor::Return => vir::Successor::Return,
+            vir::Successor::IntentionalUnreachable(position) => {
+                vir::Successor::IntentionalUnreachable(*position)
+            }
             vir::Successor::Goto(target) => vir::Successor::Goto(*target),
             vir::Successor::GotoSwitch(guarded_targets, default_target) => {
                 vir::Successor::GotoSwitch(
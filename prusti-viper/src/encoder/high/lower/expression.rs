@@ -1,7 +1,10 @@
 use super::super::types::interface::HighTypeEncoderInterfacePrivate;
 
 use super::IntoPolymorphic;
-use vir_crate::{high as vir_high, polymorphic as vir_poly};
+use vir_crate::{
+    high::{self as vir_high, operations::ty::Typed},
+    polymorphic as vir_poly,
+};
 
 impl IntoPolymorphic<Box<vir_poly::Expr>> for Box<vir_high::Expression> {
     fn lower(&self, encoder: &impl HighTypeEncoderInterfacePrivate) -> Box<vir_poly::Expr> {
@@ -187,8 +190,18 @@ impl IntoPolymorphic<vir_poly::UnaryOpKind> for vir_high::expression::UnaryOpKin
 
 impl IntoPolymorphic<vir_poly::BinOp> for vir_high::expression::BinaryOp {
     fn lower(&self, encoder: &impl HighTypeEncoderInterfacePrivate) -> vir_poly::BinOp {
+        let op_kind = if matches!(self.op_kind, vir_high::expression::BinaryOpKind::Shr) {
+            // A right shift of a signed value replicates its sign bit.
+            if is_signed(self.left.get_type()) {
+                vir_poly::BinaryOpKind::AShr
+            } else {
+                vir_poly::BinaryOpKind::LShr
+            }
+        } else {
+            self.op_kind.lower(encoder)
+        };
         vir_poly::BinOp {
-            op_kind: self.op_kind.lower(encoder),
+            op_kind,
             left: self.left.lower(encoder),
             right: self.right.lower(encoder),
             position: self.position.lower(encoder),
@@ -213,11 +226,33 @@ impl IntoPolymorphic<vir_poly::BinaryOpKind> for vir_high::expression::BinaryOpK
             vir_high::expression::BinaryOpKind::And => vir_poly::BinaryOpKind::And,
             vir_high::expression::BinaryOpKind::Or => vir_poly::BinaryOpKind::Or,
             vir_high::expression::BinaryOpKind::Implies => vir_poly::BinaryOpKind::Implies,
+            vir_high::expression::BinaryOpKind::BitAnd => vir_poly::BinaryOpKind::BitAnd,
+            vir_high::expression::BinaryOpKind::BitOr => vir_poly::BinaryOpKind::BitOr,
+            vir_high::expression::BinaryOpKind::BitXor => vir_poly::BinaryOpKind::BitXor,
+            vir_high::expression::BinaryOpKind::Shl => vir_poly::BinaryOpKind::Shl,
+            vir_high::expression::BinaryOpKind::Shr => {
+                unreachable!("right shifts are lowered together with their operands")
+            }
             vir_high::expression::BinaryOpKind::LifetimeIntersection => unreachable!(),
         }
     }
 }
 
+fn is_signed(ty: &vir_high::Type) -> bool {
+    matches!(
+        ty,
+        vir_high::Type::Int(
+            vir_high::ty::Int::I8
+                | vir_high::ty::Int::I16
+                | vir_high::ty::Int::I32
+                | vir_high::ty::Int::I64
+                | vir_high::ty::Int::I128
+                | vir_high::ty::Int::Isize
+                | vir_high::ty::Int::Unbounded
+        )
+    )
+}
+
 impl IntoPolymorphic<vir_poly::ContainerOp> for vir_high::expression::ContainerOp {
     fn lower(&self, encoder: &impl HighTypeEncoderInterfacePrivate) -> vir_poly::ContainerOp {
         vir_poly::ContainerOp {
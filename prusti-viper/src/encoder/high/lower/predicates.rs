@@ -81,8 +81,19 @@ impl IntoPredicates for vir_high::type_decl::Float {
         ty: &vir_high::Type,
         encoder: &impl HighTypeEncoderInterfacePrivate,
     ) -> Predicates {
+        // The bounds are present only when floats are assumed to be finite,
+        // and they also exclude NaN because it is not comparable.
         let field = create_value_field(ty.clone())?.lower(encoder);
-        let predicate = Predicate::new_primitive_value(ty.lower(encoder), field, None, None);
+        let predicate = Predicate::new_primitive_value(
+            ty.lower(encoder),
+            field,
+            self.lower_bound
+                .as_ref()
+                .map(|bound| (**bound).lower(encoder)),
+            self.upper_bound
+                .as_ref()
+                .map(|bound| (**bound).lower(encoder)),
+        );
         Ok(vec![predicate])
     }
 }
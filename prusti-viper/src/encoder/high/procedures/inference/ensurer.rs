@@ -124,6 +124,45 @@ pub(in super::super) fn try_ensure_enum_discriminant_by_unfolding(
     }
 }
 
+/// Checks whether `required_permission` can be obtained on all paths without
+/// reaching an unsatisfiable requirement. This is used to validate the places
+/// mentioned in user-provided hints before trying to ensure them.
+pub(in super::super) fn can_ensure_required_permission(
+    context: &mut impl Context,
+    state: &mut FoldUnfoldState,
+    required_permission: &Permission,
+) -> SpannedEncodingResult<bool> {
+    let (place, permission_kind) = match required_permission {
+        Permission::MemoryBlock(place) => (place, PermissionKind::MemoryBlock),
+        Permission::Owned(place) => (place, PermissionKind::Owned),
+        Permission::MutBorrowed(borrow) => unreachable!("requiring a borrow: {}", borrow),
+    };
+    let Some(predicate_state) = state.try_get_predicates_state(place)? else {
+        return Ok(false);
+    };
+    match predicate_state {
+        PredicateState::Unconditional(unconditional_predicate_state) => can_place_be_ensured_in(
+            context,
+            place,
+            permission_kind,
+            unconditional_predicate_state,
+        ),
+        PredicateState::Conditional(conditional_predicate_states) => {
+            for conditional_predicate_state in conditional_predicate_states.values() {
+                if !can_place_be_ensured_in(
+                    context,
+                    place,
+                    permission_kind,
+                    conditional_predicate_state,
+                )? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+    }
+}
+
 #[tracing::instrument(level = "debug", skip(context, state, actions))]
 pub(in super::super) fn ensure_required_permission(
     context: &mut impl Context,
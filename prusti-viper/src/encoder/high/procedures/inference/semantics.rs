@@ -93,6 +93,12 @@ impl CollectPermissionChanges for vir_typed::Statement {
             vir_typed::Statement::SetUnionVariant(statement) => {
                 statement.collect(encoder, consumed_permissions, produced_permissions)
             }
+            vir_typed::Statement::UnfoldHint(statement) => {
+                statement.collect(encoder, consumed_permissions, produced_permissions)
+            }
+            vir_typed::Statement::FoldHint(statement) => {
+                statement.collect(encoder, consumed_permissions, produced_permissions)
+            }
             vir_typed::Statement::NewLft(statement) => {
                 statement.collect(encoder, consumed_permissions, produced_permissions)
             }
@@ -617,6 +623,40 @@ impl CollectPermissionChanges for vir_typed::SetUnionVariant {
     }
 }
 
+impl CollectPermissionChanges for vir_typed::UnfoldHint {
+    fn collect<'v, 'tcx>(
+        &self,
+        encoder: &mut Encoder<'v, 'tcx>,
+        consumed_permissions: &mut Vec<Permission>,
+        produced_permissions: &mut Vec<Permission>,
+    ) -> SpannedEncodingResult<()> {
+        // Requiring all fields forces fold-unfold to unfold the place. The
+        // procedure encoder already checked that the place is a struct.
+        let type_decl = encoder
+            .encode_type_def_typed(&self.place.get_type().normalize_type())?
+            .unwrap_struct();
+        for field in type_decl.fields {
+            let field_place = self.place.clone().field(field, self.position);
+            consumed_permissions.push(Permission::Owned(field_place.clone()));
+            produced_permissions.push(Permission::Owned(field_place));
+        }
+        Ok(())
+    }
+}
+
+impl CollectPermissionChanges for vir_typed::FoldHint {
+    fn collect<'v, 'tcx>(
+        &self,
+        _encoder: &mut Encoder<'v, 'tcx>,
+        consumed_permissions: &mut Vec<Permission>,
+        produced_permissions: &mut Vec<Permission>,
+    ) -> SpannedEncodingResult<()> {
+        consumed_permissions.push(Permission::Owned(self.place.clone()));
+        produced_permissions.push(Permission::Owned(self.place.clone()));
+        Ok(())
+    }
+}
+
 impl CollectPermissionChanges for vir_typed::NewLft {
     fn collect<'v, 'tcx>(
         &self,
@@ -318,7 +318,7 @@ impl FoldUnfoldState {
             .unwrap_or_else(|| unreachable!("place: {place}")))
     }
 
-    pub(super) fn try_get_predicates_state(
+    pub(in super::super) fn try_get_predicates_state(
         &mut self,
         place: &vir_typed::Expression,
     ) -> SpannedEncodingResult<Option<&mut PredicateState>> {
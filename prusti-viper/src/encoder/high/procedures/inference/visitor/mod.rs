@@ -1,15 +1,15 @@
 use super::{
     ensurer::{
-        ensure_required_permission, ensure_required_permissions,
-        try_ensure_enum_discriminant_by_unfolding,
+        can_ensure_required_permission, ensure_required_permission, ensure_required_permissions,
+        try_ensure_enum_discriminant_by_unfolding, Context,
     },
     state::{FoldUnfoldState, PlaceWithDeadLifetimes, PredicateState, PredicateStateOnPath},
 };
 use crate::encoder::{
-    errors::SpannedEncodingResult,
+    errors::{SpannedEncodingError, SpannedEncodingResult},
     high::procedures::inference::{
         action::{Action, ConversionState, FoldingActionState, RestorationState, UnreachableState},
-        permission::PermissionKind,
+        permission::{Permission, PermissionKind},
         semantics::collect_permission_changes,
     },
     Encoder,
@@ -207,6 +207,11 @@ impl<'p, 'v, 'tcx> Visitor<'p, 'v, 'tcx> {
             self.process_assign_discriminant(target, discriminant, position, state)?;
             return Ok(());
         }
+        if let vir_typed::Statement::UnfoldHint(vir_typed::UnfoldHint { place, position })
+        | vir_typed::Statement::FoldHint(vir_typed::FoldHint { place, position }) = &statement
+        {
+            self.check_hint_place(place, *position, state)?;
+        }
         let (consumed_permissions, produced_permissions) =
             collect_permission_changes(self.encoder, &statement)?;
         debug!(
@@ -221,7 +226,9 @@ impl<'p, 'v, 'tcx> Visitor<'p, 'v, 'tcx> {
         state.remove_permissions(&consumed_permissions)?;
         state.insert_permissions(produced_permissions)?;
         match &statement {
-            vir_typed::Statement::ObtainMutRef(_) => {
+            vir_typed::Statement::ObtainMutRef(_)
+            | vir_typed::Statement::UnfoldHint(_)
+            | vir_typed::Statement::FoldHint(_) => {
                 // The requirements already performed the needed changes.
             }
             vir_typed::Statement::LeakAll(vir_typed::LeakAll {}) => {
@@ -421,6 +428,25 @@ impl<'p, 'v, 'tcx> Visitor<'p, 'v, 'tcx> {
         Ok(())
     }
 
+    /// Checks that the place mentioned in a user-provided fold or unfold hint
+    /// holds the `Owned` predicate.
+    fn check_hint_place(
+        &mut self,
+        place: &vir_typed::Expression,
+        position: vir_typed::Position,
+        state: &mut FoldUnfoldState,
+    ) -> SpannedEncodingResult<()> {
+        let permission = Permission::Owned(place.clone());
+        if !can_ensure_required_permission(self, state, &permission)? {
+            let span = self.get_span(position).unwrap();
+            return Err(SpannedEncodingError::incorrect(
+                format!("the place `{place}` does not hold the predicate required by the hint"),
+                span,
+            ));
+        }
+        Ok(())
+    }
+
     fn process_dead_lifetime(
         &mut self,
         statement: vir_typed::DeadLifetime,
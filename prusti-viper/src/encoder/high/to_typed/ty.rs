@@ -34,6 +34,17 @@ impl<'v, 'tcx> HighToTypedTypeLowerer for crate::encoder::Encoder<'v, 'tcx> {
         ))
     }
 
+    fn high_to_typed_type_type_closure(
+        &mut self,
+        ty: vir_high::ty::Closure,
+    ) -> Result<vir_typed::ty::Type, Self::Error> {
+        Ok(vir_typed::Type::struct_(
+            ty.name,
+            Vec::new(),
+            ty.lifetimes.high_to_typed_type(self)?,
+        ))
+    }
+
     fn high_to_typed_type_expression(
         &mut self,
         expression: vir_high::Expression,
@@ -62,6 +62,25 @@ impl<'v, 'tcx> HighToTypedTypeDeclLowerer for crate::encoder::Encoder<'v, 'tcx>
         ))
     }
 
+    fn high_to_typed_type_decl_type_decl_closure(
+        &mut self,
+        decl: vir_high::type_decl::Closure,
+    ) -> Result<vir_typed::TypeDecl, Self::Error> {
+        // The captured environment of a closure is modelled as a struct whose
+        // fields are the captured values and references.
+        let arguments = decl.arguments.high_to_typed_type(self)?;
+        Ok(vir_typed::TypeDecl::struct_(
+            decl.name,
+            decl.lifetimes.high_to_typed_type(self)?,
+            Vec::new(),
+            arguments
+                .into_iter()
+                .enumerate()
+                .map(|(index, ty)| vir_typed::FieldDecl::new(format!("closure_{index}"), index, ty))
+                .collect(),
+        ))
+    }
+
     fn high_to_typed_type_decl_variable_decl(
         &mut self,
         variable: vir_high::VariableDecl,
@@ -0,0 +1,407 @@
+use crate::encoder::{
+    errors::SpannedEncodingResult,
+    middle::core_proof::{
+        lowerer::{DomainsLowererInterface, Lowerer},
+        snapshots::SnapshotValuesInterface,
+    },
+};
+use rustc_hash::FxHashSet;
+use vir_crate::{
+    common::expression::{
+        BinaryOperationHelpers, ConditionalHelpers, ExpressionIterator, QuantifierHelpers,
+    },
+    low as vir_low, middle as vir_mid,
+};
+
+const DOMAIN_NAME: &str = "Bitwise";
+const POWER_OF_TWO: &str = "pow2";
+
+#[derive(Default)]
+pub(in super::super) struct BitwiseState {
+    /// Widths for which the conjunction function was already declared.
+    encoded_widths: FxHashSet<u32>,
+    /// The exponents below this one have their value of `pow2` declared.
+    declared_powers_of_two: u32,
+}
+
+fn power_of_two(exponent: u32) -> vir_low::Expression {
+    if exponent < 128 {
+        (1u128 << exponent).into()
+    } else {
+        assert_eq!(exponent, 128, "integers are at most 128 bits wide");
+        vir_low::Expression::constant_no_pos(
+            vir_low::expression::ConstantValue::BigInt(
+                "340282366920938463463374607431768211456".to_string(),
+            ),
+            vir_low::Type::Int,
+        )
+    }
+}
+
+trait Private {
+    /// The width in bits and the signedness of an integer type.
+    fn integer_width(&mut self, ty: &vir_mid::Type) -> (u32, bool);
+    /// Declares `pow2(exponent)` for all exponents below `width`.
+    fn ensure_powers_of_two(&mut self, width: u32) -> SpannedEncodingResult<()>;
+    /// Declares the conjunction of two unsigned integers of the given width.
+    fn ensure_bitwise_and(&mut self, width: u32) -> SpannedEncodingResult<()>;
+    fn bitwise_and_call(
+        &mut self,
+        width: u32,
+        left: vir_low::Expression,
+        right: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+    fn pow2_call(
+        &mut self,
+        exponent: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+    /// Converts a value of the given type to its unsigned representation.
+    fn to_unsigned(
+        &mut self,
+        width: u32,
+        signed: bool,
+        value: vir_low::Expression,
+    ) -> vir_low::Expression;
+    /// Converts an unsigned representation back to a value of the given type.
+    fn from_unsigned(
+        &mut self,
+        width: u32,
+        signed: bool,
+        value: vir_low::Expression,
+    ) -> vir_low::Expression;
+}
+
+impl<'p, 'v: 'p, 'tcx: 'v> Private for Lowerer<'p, 'v, 'tcx> {
+    fn integer_width(&mut self, ty: &vir_mid::Type) -> (u32, bool) {
+        use vir_mid::ty::Int;
+        let vir_mid::Type::Int(int) = ty else {
+            unreachable!("bitwise operation on a non-integer type: {}", ty);
+        };
+        match int {
+            Int::I8 => (8, true),
+            Int::I16 => (16, true),
+            Int::I32 => (32, true),
+            Int::I64 => (64, true),
+            Int::I128 => (128, true),
+            Int::U8 => (8, false),
+            Int::U16 => (16, false),
+            Int::U32 => (32, false),
+            Int::U64 => (64, false),
+            Int::U128 => (128, false),
+            Int::Isize | Int::Usize => {
+                let pointer_size = self.encoder.env().tcx().data_layout.pointer_size.bits();
+                (pointer_size.try_into().unwrap(), int == &Int::Isize)
+            }
+            Int::Char | Int::Unbounded => {
+                unreachable!("bitwise operation on an integer without width: {}", ty)
+            }
+        }
+    }
+
+    fn ensure_powers_of_two(&mut self, width: u32) -> SpannedEncodingResult<()>;
+    /// Declares the conjunction of two unsigned integers of the given width.
+    fn ensure_bitwise_and(&mut self, width: u32) -> SpannedEncodingResult<()>;
+    fn bitwise_and_call(
+        &mut self,
+        width: u32,
+        left: vir_low::Expression,
+        right: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+    fn pow2_call(
+        &mut self,
+        exponent: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+    /// Converts a value of the given type to its unsigned representation.
+    fn to_unsigned(
+        &mut self,
+        width: u32,
+        signed: bool,
+        value: vir_low::Expression,
+    ) -> vir_low::Expression;
+    /// Converts an unsigned representation back to a value of the given type.
+    fn from_unsigned(
+        &mut self,
+        width: u32,
+        signed: bool,
+        value: vir_low::Expression,
+    ) -> vir_low::Expression;
+}
+
+impl<'p, 'v: 'p, 'tcx: 'v> Private for Lowerer<'p, 'v, 'tcx> {
+    fn integer_width(&mut self, ty: &vir_mid::Type) -> (u32, bool) {
+        use vir_mid::ty::Int;
+        let vir_mid::Type::Int(int) = ty else {
+            unreachable!("bitwise operation on a non-integer type: {}", ty);
+        };
+        match int {
+            Int::I8 => (8, true),
+            Int::I16 => (16, true),
+            Int::I32 => (32, true),
+            Int::I64 => (64, true),
+            Int::I128 => (128, true),
+            Int::U8 => (8, false),
+            Int::U16 => (16, false),
+            Int::U32 => (32, false),
+            Int::U64 => (64, false),
+            Int::U128 => (128, false),
+            Int::Isize | Int::Usize => {
+                let pointer_size = self.encoder.env().tcx().data_layout.pointer_size.bits();
+                (pointer_size.try_into().unwrap(), int == &Int::Isize)
+            }
+            Int::Char | Int::Unbounded => {
+                unreachable!("bitwise operation on an integer without width: {}", ty)
+            }
+        }
+    }
+
+    fn power_of_two(&mut self, exponent: u32) -> vir_low::Expression {
+        if exponent < 128 {
+            (1u128 << exponent).into()
+        } else {
+            assert_eq!(exponent, 128, "integers are at most 128 bits wide");
+            vir_low::Expression::constant_no_pos(
+                vir_low::expression::ConstantValue::BigInt(
+                    "340282366920938463463374607431768211456".to_string(),
+                ),
+                vir_low::Type::Int,
+            )
+        }
+    }
+
+    fn ensure_powers_of_two(&mut self, width: u32) -> SpannedEncodingResult<()> {
+        if self.bitwise_state.declared_powers_of_two >= width {
+            return Ok(());
+        }
+        let mut facts = Vec::new();
+        for exponent in self.bitwise_state.declared_powers_of_two..width {
+            let call = self.pow2_call(exponent.into())?;
+            facts.push(vir_low::Expression::equals(call, power_of_two(exponent)));
+        }
+        let axiom = vir_low::DomainAxiomDecl {
+            comment: None,
+            name: format!(
+                "{POWER_OF_TWO}$values_{}_{}",
+                self.bitwise_state.declared_powers_of_two, width
+            ),
+            body: facts.into_iter().conjoin(),
+        };
+        self.declare_axiom(DOMAIN_NAME, axiom)?;
+        self.bitwise_state.declared_powers_of_two = width;
+        Ok(())
+    }
+
+    fn ensure_bitwise_and(&mut self, width: u32) -> SpannedEncodingResult<()> {
+        if !self.bitwise_state.encoded_widths.insert(width) {
+            return Ok(());
+        }
+        use vir_low::macros::*;
+        var_decls! { left: Int, right: Int };
+        let call = self.bitwise_and_call(width, left.clone().into(), right.clone().into())?;
+        let swapped_call =
+            self.bitwise_and_call(width, right.clone().into(), left.clone().into())?;
+        let modulus = power_of_two(width);
+        let all_ones = vir_low::Expression::subtract(modulus.clone(), 1.into());
+        let in_range = |value: &vir_low::VariableDecl| {
+            vir_low::Expression::and(
+                vir_low::Expression::less_equals(0.into(), value.clone().into()),
+                vir_low::Expression::less_than(value.clone().into(), modulus.clone()),
+            )
+        };
+        let parity = |value: vir_low::Expression| vir_low::Expression::module(value, 2.into());
+        let properties = vec![
+            vir_low::Expression::less_equals(0.into(), call.clone()),
+            vir_low::Expression::less_equals(call.clone(), left.clone().into()),
+            vir_low::Expression::less_equals(call.clone(), right.clone().into()),
+            vir_low::Expression::equals(call.clone(), swapped_call),
+            vir_low::Expression::equals(
+                parity(call.clone()),
+                vir_low::Expression::multiply(
+                    parity(left.clone().into()),
+                    parity(right.clone().into()),
+                ),
+            ),
+            vir_low::Expression::implies(
+                vir_low::Expression::equals(left.clone().into(), right.clone().into()),
+                vir_low::Expression::equals(call.clone(), left.clone().into()),
+            ),
+            vir_low::Expression::implies(
+                vir_low::Expression::equals(right.clone().into(), all_ones),
+                vir_low::Expression::equals(call.clone(), left.clone().into()),
+            ),
+        ];
+        let body = vir_low::Expression::forall(
+            vec![left.clone(), right.clone()],
+            vec![vir_low::Trigger::new(vec![call])],
+            vir_low::Expression::implies(
+                vir_low::Expression::and(in_range(&left), in_range(&right)),
+                properties.into_iter().conjoin(),
+            ),
+        );
+        let axiom = vir_low::DomainAxiomDecl {
+            comment: None,
+            name: format!("bitwise_and${width}$properties"),
+            body,
+        };
+        self.declare_axiom(DOMAIN_NAME, axiom)?;
+        Ok(())
+    }
+
+    fn bitwise_and_call(
+        &mut self,
+        width: u32,
+        left: vir_low::Expression,
+        right: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        self.create_domain_func_app(
+            DOMAIN_NAME,
+            format!("bitwise_and${width}"),
+            vec![left, right],
+            vir_low::Type::Int,
+            Default::default(),
+        )
+    }
+
+    fn pow2_call(
+        &mut self,
+        exponent: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        self.create_domain_func_app(
+            DOMAIN_NAME,
+            POWER_OF_TWO,
+            vec![exponent],
+            vir_low::Type::Int,
+            Default::default(),
+        )
+    }
+
+    fn to_unsigned(
+        &mut self,
+        width: u32,
+        signed: bool,
+        value: vir_low::Expression,
+    ) -> vir_low::Expression {
+        if signed {
+            vir_low::Expression::module(value, power_of_two(width))
+        } else {
+            value
+        }
+    }
+
+    fn from_unsigned(
+        &mut self,
+        width: u32,
+        signed: bool,
+        value: vir_low::Expression,
+    ) -> vir_low::Expression {
+        if signed {
+            let half = power_of_two(width - 1);
+            vir_low::Expression::conditional(
+                vir_low::Expression::less_than(value.clone(), half),
+                value.clone(),
+                vir_low::Expression::subtract(value, power_of_two(width)),
+            )
+        } else {
+            value
+        }
+    }
+}
+
+pub(in super::super) trait BitwiseInterface {
+    /// Encodes a bitwise operation on the values of two integers of type
+    /// `ty`. For shifts, `right` is the shift amount, which must be in the
+    /// bounds checked by `encode_shift_amount_in_bounds`.
+    fn encode_bitwise_operation(
+        &mut self,
+        op: vir_mid::BinaryOpKind,
+        ty: &vir_mid::Type,
+        left: vir_low::Expression,
+        right: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+    /// Whether `amount` is a valid amount for shifting a value of type `ty`:
+    /// it is non-negative and smaller than the width of `ty`.
+    fn encode_shift_amount_in_bounds(
+        &mut self,
+        ty: &vir_mid::Type,
+        amount: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+    /// The shift amount can have a different type than the shifted value.
+    /// Converts the snapshot of the amount into a snapshot of the type of the
+    /// shifted value, so that both operands have the same type.
+    fn shift_amount_snapshot(
+        &mut self,
+        ty: &vir_mid::Type,
+        amount_type: &vir_mid::Type,
+        amount: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+}
+
+impl<'p, 'v: 'p, 'tcx: 'v> BitwiseInterface for Lowerer<'p, 'v, 'tcx> {
+    fn encode_bitwise_operation(
+        &mut self,
+        op: vir_mid::BinaryOpKind,
+        ty: &vir_mid::Type,
+        left: vir_low::Expression,
+        right: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        let (width, signed) = self.integer_width(ty);
+        let result = match op {
+            vir_mid::BinaryOpKind::BitAnd
+            | vir_mid::BinaryOpKind::BitOr
+            | vir_mid::BinaryOpKind::BitXor => {
+                self.ensure_bitwise_and(width)?;
+                let left = self.to_unsigned(width, signed, left);
+                let right = self.to_unsigned(width, signed, right);
+                let conjunction = self.bitwise_and_call(width, left.clone(), right.clone())?;
+                let sum = vir_low::Expression::add(left, right);
+                let unsigned_result = match op {
+                    vir_mid::BinaryOpKind::BitAnd => conjunction,
+                    vir_mid::BinaryOpKind::BitOr => vir_low::Expression::subtract(sum, conjunction),
+                    _ => vir_low::Expression::subtract(
+                        sum,
+                        vir_low::Expression::multiply(2.into(), conjunction),
+                    ),
+                };
+                self.from_unsigned(width, signed, unsigned_result)
+            }
+            vir_mid::BinaryOpKind::Shl => {
+                self.ensure_powers_of_two(width)?;
+                let shifted = vir_low::Expression::multiply(left, self.pow2_call(right)?);
+                let unsigned_result = vir_low::Expression::module(shifted, power_of_two(width));
+                self.from_unsigned(width, signed, unsigned_result)
+            }
+            vir_mid::BinaryOpKind::Shr => {
+                self.ensure_powers_of_two(width)?;
+                vir_low::Expression::divide(left, self.pow2_call(right)?)
+            }
+            _ => unreachable!("not a bitwise operation: {}", op),
+        };
+        Ok(result)
+    }
+
+    fn encode_shift_amount_in_bounds(
+        &mut self,
+        ty: &vir_mid::Type,
+        amount: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        let (width, _) = self.integer_width(ty);
+        Ok(vir_low::Expression::and(
+            vir_low::Expression::less_equals(0.into(), amount.clone()),
+            vir_low::Expression::less_than(amount, width.into()),
+        ))
+    }
+
+    fn shift_amount_snapshot(
+        &mut self,
+        ty: &vir_mid::Type,
+        amount_type: &vir_mid::Type,
+        amount: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        if ty == amount_type {
+            return Ok(amount);
+        }
+        let value = self.obtain_constant_value(amount_type, amount, position)?;
+        self.construct_constant_snapshot(ty, value, position)
+    }
+}
@@ -0,0 +1,15 @@
+//! Encoder of bitwise operations on integers.
+//!
+//! Integer values are mathematical integers, so the bitwise operations are
+//! encoded on the unsigned (two's complement) representation of their
+//! operands. Only the conjunction is an axiomatized domain function; the
+//! other operations are defined in terms of it and of powers of two:
+//!
+//! * `a | b == a + b - (a & b)`,
+//! * `a ^ b == a + b - 2 * (a & b)`,
+//! * `a << b == (a * 2^b) % 2^width`,
+//! * `a >> b == a / 2^b` (rounding towards negative infinity).
+
+mod interface;
+
+pub(super) use self::interface::{BitwiseInterface, BitwiseState};
@@ -10,6 +10,7 @@ use crate::encoder::{
     high::types::HighTypeEncoderInterface,
     middle::core_proof::{
         addresses::AddressesInterface,
+        bitwise::BitwiseInterface,
         block_markers::BlockMarkersInterface,
         builtin_methods::builders::{
             BuiltinMethodBuilderMethods, CopyPlaceMethodBuilder, IntoMemoryBlockMethodBuilder,
@@ -37,10 +38,11 @@ use crate::encoder::{
     },
 };
 use itertools::Itertools;
+use prusti_common::config;
 use rustc_hash::FxHashSet;
 use vir_crate::{
     common::{
-        expression::{ExpressionIterator, UnaryOperationHelpers},
+        expression::{BinaryOperationHelpers, ExpressionIterator, UnaryOperationHelpers},
         identifier::WithIdentifier,
     },
     low::{self as vir_low, macros::method_name},
@@ -161,6 +163,13 @@ trait Private {
         result_value: &vir_low::VariableDecl,
         position: vir_low::Position,
     ) -> SpannedEncodingResult<()>;
+    /// Returns `None` if the type is unbounded.
+    fn encode_wrapped_operation_result(
+        &mut self,
+        ty: &vir_mid::Type,
+        operation_result: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<Option<vir_low::Expression>>;
     #[allow(clippy::too_many_arguments)]
     fn encode_assign_method_rvalue_reborrow(
         &mut self,
@@ -705,12 +714,36 @@ impl<'p, 'v: 'p, 'tcx: 'v> Private for Lowerer<'p, 'v, 'tcx> {
                         );
                     }
                 }
+                let left_type = value.left.expression.get_type();
+                let right_type = value.right.expression.get_type();
+                let operand_right = if matches!(
+                    value.kind,
+                    vir_mid::BinaryOpKind::Shl | vir_mid::BinaryOpKind::Shr
+                ) {
+                    // Shifts that are not CheckedBinaryOp must not overflow
+                    // either. Therefore, we put the bounds check of the shift
+                    // amount into the precondition.
+                    let amount = self.obtain_constant_value(
+                        right_type,
+                        operand_right.clone().into(),
+                        position,
+                    )?;
+                    pres.push(self.encode_shift_amount_in_bounds(left_type, amount)?);
+                    self.shift_amount_snapshot(
+                        left_type,
+                        right_type,
+                        operand_right.into(),
+                        position,
+                    )?
+                } else {
+                    operand_right.into()
+                };
                 self.construct_binary_op_snapshot(
                     value.kind,
-                    value.kind.get_result_type(value.left.expression.get_type()),
-                    value.left.expression.get_type(),
+                    value.kind.get_result_type(left_type),
+                    left_type,
                     operand_left.into(),
-                    operand_right.into(),
+                    operand_right,
                     position,
                 )?
             }
@@ -861,24 +894,81 @@ impl<'p, 'v: 'p, 'tcx: 'v> Private for Lowerer<'p, 'v, 'tcx> {
             self.encode_assign_operand(parameters, pres, posts, 1, &value.left, position, true)?;
         let operand_right =
             self.encode_assign_operand(parameters, pres, posts, 2, &value.right, position, true)?;
+        let is_shift = matches!(
+            value.kind,
+            vir_mid::BinaryOpKind::Shl | vir_mid::BinaryOpKind::Shr
+        );
+        let left_type = value.left.expression.get_type();
+        let right_type = value.right.expression.get_type();
+        let shift_amount_in_bounds = if is_shift && config::arithmetic_mode() != "unchecked" {
+            let amount =
+                self.obtain_constant_value(right_type, operand_right.clone().into(), position)?;
+            Some(self.encode_shift_amount_in_bounds(left_type, amount)?)
+        } else {
+            None
+        };
+        let operand_right = if is_shift {
+            self.shift_amount_snapshot(left_type, right_type, operand_right.into(), position)?
+        } else {
+            operand_right.into()
+        };
         let operation_result = self.construct_binary_op_snapshot(
             value.kind,
             operation_result_type,
-            value.left.expression.get_type(),
+            left_type,
             operand_left.into(),
-            operand_right.into(),
+            operand_right,
             position,
         )?;
         let validity = self
             .encode_snapshot_valid_call_for_type(operation_result.clone(), operation_result_type)?;
-        let flag_result = self.construct_constant_snapshot(
-            &vir_mid::Type::Bool,
-            vir_low::Expression::not(validity.clone()),
-            position,
-        )?;
-        let operation_result_value_condition = expr! {
-            [validity] ==> ([operation_result_value.clone()] == [operation_result])
+        let wrapped_operation_result = if config::arithmetic_mode() == "wrapping"
+            && matches!(
+                value.kind,
+                vir_mid::BinaryOpKind::Add
+                    | vir_mid::BinaryOpKind::Sub
+                    | vir_mid::BinaryOpKind::Mul
+            ) {
+            self.encode_wrapped_operation_result(
+                operation_result_type,
+                operation_result.clone(),
+                position,
+            )?
+        } else {
+            None
         };
+        let (overflow_flag, operation_result_value_condition) =
+            if let Some(shift_amount_in_bounds) = shift_amount_in_bounds {
+                // The result of a shift is always in the range of its type;
+                // the shift overflows if the shift amount is out of bounds.
+                (
+                    vir_low::Expression::not(shift_amount_in_bounds.clone()),
+                    expr! {
+                        [shift_amount_in_bounds] ==>
+                            ([operation_result_value.clone()] == [operation_result])
+                    },
+                )
+            } else if let Some(wrapped_operation_result) = wrapped_operation_result {
+                (
+                    false.into(),
+                    expr! { [operation_result_value.clone()] == [wrapped_operation_result] },
+                )
+            } else if config::arithmetic_mode() == "unchecked" {
+                // The operation is assumed to never overflow.
+                (
+                    false.into(),
+                    expr! { [operation_result_value.clone()] == [operation_result] },
+                )
+            } else {
+                (
+                    vir_low::Expression::not(validity.clone()),
+                    expr! {
+                        [validity] ==> ([operation_result_value.clone()] == [operation_result])
+                    },
+                )
+            };
+        let flag_result =
+            self.construct_constant_snapshot(&vir_mid::Type::Bool, overflow_flag, position)?;
         let flag_value_condition = expr! {
             [flag_value] == [flag_result]
         };
@@ -892,6 +982,35 @@ impl<'p, 'v: 'p, 'tcx: 'v> Private for Lowerer<'p, 'v, 'tcx> {
         });
         Ok(())
     }
+    fn encode_wrapped_operation_result(
+        &mut self,
+        ty: &vir_mid::Type,
+        operation_result: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<Option<vir_low::Expression>> {
+        let type_decl = self.encoder.get_type_decl_mid(ty)?.unwrap_int();
+        let (Some(lower), Some(upper)) = (type_decl.lower_bound, type_decl.upper_bound) else {
+            return Ok(None);
+        };
+        let lower = lower.to_builtin_method_snapshot(self)?;
+        let upper = upper.to_builtin_method_snapshot(self)?;
+        let value = self.obtain_constant_value(ty, operation_result, position)?;
+        // lower + (value - lower) % (upper - lower + 1)
+        let modulus = vir_low::Expression::add(
+            vir_low::Expression::subtract(upper, lower.clone()),
+            1.into(),
+        );
+        let wrapped = vir_low::Expression::add(
+            vir_low::Expression::module(
+                vir_low::Expression::subtract(value, lower.clone()),
+                modulus,
+            ),
+            lower,
+        );
+        Ok(Some(
+            self.construct_constant_snapshot(ty, wrapped, position)?,
+        ))
+    }
     #[allow(clippy::too_many_arguments)]
     fn encode_assign_method_rvalue_reborrow(
         &mut self,
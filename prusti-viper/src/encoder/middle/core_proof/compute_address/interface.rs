@@ -4,6 +4,7 @@ use crate::encoder::{
     middle::core_proof::{
         addresses::AddressesInterface, lowerer::Lowerer, places::PlacesInterface,
         references::ReferencesInterface, snapshots::IntoSnapshot,
+        type_layouts::TypeLayoutsInterface,
     },
 };
 use rustc_hash::FxHashSet;
@@ -135,6 +136,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> ComputeAddressInterface for Lowerer<'p, 'v, 'tcx> {
                         self.compute_address_state.axioms.push(axiom);
                     }
                     self.encode_compute_address(&decl.discriminant_type)?;
+                    if !decl.safety.is_enum() {
+                        self.encode_union_layout_axioms(ty, &decl)?;
+                    }
                     for variant in &decl.variants {
                         use vir_low::macros::*;
                         let compute_address = ty!(Address);
@@ -170,6 +174,32 @@ impl<'p, 'v: 'p, 'tcx: 'v> ComputeAddressInterface for Lowerer<'p, 'v, 'tcx> {
                             body,
                         };
                         self.compute_address_state.axioms.push(axiom);
+                        if !decl.safety.is_enum() {
+                            // All fields of a union share the union's address.
+                            let address =
+                                vir_low::VariableDecl::new("address", self.address_type()?);
+                            let variant_address = self.encode_enum_variant_address(
+                                ty,
+                                &variant.name.clone().into(),
+                                address.clone().into(),
+                                Default::default(),
+                            )?;
+                            let body = vir_low::Expression::forall(
+                                vec![address.clone()],
+                                vec![vir_low::Trigger::new(vec![variant_address.clone()])],
+                                expr! { [variant_address] == [address.into()] },
+                            );
+                            let axiom = vir_low::DomainAxiomDecl {
+                                comment: None,
+                                name: format!(
+                                    "{}${}$union_field_address_axiom",
+                                    ty.get_identifier(),
+                                    variant.name
+                                ),
+                                body,
+                            };
+                            self.compute_address_state.axioms.push(axiom);
+                        }
                         let variant_ty = ty.clone().variant(variant.name.clone().into());
                         self.encode_compute_address(&variant_ty)?;
                     }
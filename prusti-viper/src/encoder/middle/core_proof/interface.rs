@@ -1,3 +1,6 @@
+use super::{
+    lowerer::LoweringResult, lowering_cache::LoweringCache, statistics::ProgramStatistics,
+};
 use crate::encoder::{
     errors::SpannedEncodingResult, high::procedures::HighProcedureEncoderInterface,
     mir::specifications::SpecificationsInterface,
@@ -5,14 +8,22 @@ use crate::encoder::{
 use log::debug;
 use prusti_common::config;
 use prusti_rustc_interface::{hir::def_id::DefId, middle::ty};
+use rustc_hash::FxHashMap;
 use vir_crate::{
     common::{check_mode::CheckMode, identifier::WithIdentifier},
     low::{self as vir_low},
+    middle as vir_mid,
 };
 
 #[derive(Default)]
 pub(crate) struct MidCoreProofEncoderState {
     encoded_programs: Vec<vir_low::Program>,
+    /// The metrics of the lowered procedures, collected only if the `stats`
+    /// flag is enabled.
+    statistics: FxHashMap<(String, CheckMode), ProgramStatistics>,
+    /// The lowered procedures, used only if the `cache_lowering` flag is
+    /// enabled.
+    lowered_procedures: LoweringCache<(DefId, vir_mid::ProcedureDecl), LoweringResult>,
 }
 
 pub(crate) trait MidCoreProofEncoderInterface<'tcx> {
@@ -21,12 +32,22 @@ pub(crate) trait MidCoreProofEncoderInterface<'tcx> {
         proc_def_id: DefId,
         check_mode: CheckMode,
     ) -> SpannedEncodingResult<()>;
+    /// Lowers an already encoded procedure to a Viper program without queuing
+    /// it for verification.
+    fn lower_procedure_core_proof(
+        &mut self,
+        proc_def_id: DefId,
+        procedure: vir_mid::ProcedureDecl,
+    ) -> SpannedEncodingResult<vir_low::Program>;
     fn encode_core_proof_for_type(
         &mut self,
         ty: ty::Ty<'tcx>,
         check_mode: CheckMode,
     ) -> SpannedEncodingResult<()>;
     fn take_core_proof_programs(&mut self) -> Vec<vir_low::Program>;
+    /// The metrics of the lowered procedures, keyed by the program name and
+    /// the check mode.
+    fn take_core_proof_statistics(&mut self) -> FxHashMap<(String, CheckMode), ProgramStatistics>;
 }
 
 impl<'v, 'tcx: 'v> MidCoreProofEncoderInterface<'tcx> for super::super::super::Encoder<'v, 'tcx> {
@@ -44,13 +65,39 @@ impl<'v, 'tcx: 'v> MidCoreProofEncoderInterface<'tcx> for super::super::super::E
             return Ok(());
         }
         let procedure = self.encode_procedure_core_proof(proc_def_id, check_mode)?;
-        let super::lowerer::LoweringResult {
+        let program = self.lower_procedure_core_proof(proc_def_id, procedure)?;
+        self.mid_core_proof_encoder_state
+            .encoded_programs
+            .push(program);
+        Ok(())
+    }
+
+    fn lower_procedure_core_proof(
+        &mut self,
+        proc_def_id: DefId,
+        procedure: vir_mid::ProcedureDecl,
+    ) -> SpannedEncodingResult<vir_low::Program> {
+        let check_mode = procedure.check_mode;
+        let LoweringResult {
             procedures,
             domains,
             functions,
             predicates,
             methods,
-        } = super::lowerer::lower_procedure(self, proc_def_id, procedure)?;
+        } = if config::cache_lowering() {
+            // The cache is taken out of the state because lowering needs the
+            // encoder.
+            let mut cache =
+                std::mem::take(&mut self.mid_core_proof_encoder_state.lowered_procedures);
+            let result = cache.get_or_lower((proc_def_id, procedure), |(def_id, procedure)| {
+                debug!("Lowering {:?}", def_id);
+                super::lowerer::lower_procedure(self, def_id, procedure)
+            });
+            self.mid_core_proof_encoder_state.lowered_procedures = cache;
+            result?
+        } else {
+            super::lowerer::lower_procedure(self, proc_def_id, procedure)?
+        };
         let mut program = vir_low::Program {
             name: self.env().name.get_absolute_item_name(proc_def_id),
             check_mode,
@@ -60,13 +107,18 @@ impl<'v, 'tcx: 'v> MidCoreProofEncoderInterface<'tcx> for super::super::super::E
             functions,
             methods,
         };
-        if config::inline_caller_for() {
+        if config::inline_caller_for()
+            && super::transformations::is_pass_enabled("inline_caller_for")
+        {
             super::transformations::inline_functions::inline_caller_for(&mut program);
         }
-        self.mid_core_proof_encoder_state
-            .encoded_programs
-            .push(program);
-        Ok(())
+        if config::stats() {
+            self.mid_core_proof_encoder_state.statistics.insert(
+                (program.name.clone(), check_mode),
+                ProgramStatistics::collect(&program),
+            );
+        }
+        Ok(program)
     }
 
     fn encode_core_proof_for_type(
@@ -111,7 +163,9 @@ impl<'v, 'tcx: 'v> MidCoreProofEncoderInterface<'tcx> for super::super::super::E
             functions,
             methods,
         };
-        if config::inline_caller_for() {
+        if config::inline_caller_for()
+            && super::transformations::is_pass_enabled("inline_caller_for")
+        {
             super::transformations::inline_functions::inline_caller_for(&mut program);
         }
         self.mid_core_proof_encoder_state
@@ -123,4 +177,8 @@ impl<'v, 'tcx: 'v> MidCoreProofEncoderInterface<'tcx> for super::super::super::E
     fn take_core_proof_programs(&mut self) -> Vec<vir_low::Program> {
         std::mem::take(&mut self.mid_core_proof_encoder_state.encoded_programs)
     }
+
+    fn take_core_proof_statistics(&mut self) -> FxHashMap<(String, CheckMode), ProgramStatistics> {
+        std::mem::take(&mut self.mid_core_proof_encoder_state.statistics)
+    }
 }
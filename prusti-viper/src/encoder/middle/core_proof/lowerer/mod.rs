@@ -4,13 +4,16 @@ use self::{
 };
 use super::{
     adts::AdtsState,
+    bitwise::BitwiseState,
     builtin_methods::BuiltinMethodsState,
     compute_address::ComputeAddressState,
     into_low::IntoLow,
     lifetimes::LifetimesState,
     places::PlacesState,
+    pointers::PointersState,
     predicates::{PredicatesMemoryBlockInterface, PredicatesOwnedInterface, PredicatesState},
     snapshots::{SnapshotVariablesInterface, SnapshotsState},
+    type_layouts::TypeLayoutsState,
     types::TypesState,
 };
 use crate::encoder::{
@@ -38,6 +41,7 @@ pub(super) use self::{
     variables::VariablesLowererInterface,
 };
 
+#[derive(Clone)]
 pub(super) struct LoweringResult {
     pub(super) procedures: Vec<vir_low::ProcedureDecl>,
     pub(super) domains: Vec<vir_low::DomainDecl>,
@@ -53,7 +57,9 @@ pub(super) fn lower_procedure<'p, 'v: 'p, 'tcx: 'v>(
 ) -> SpannedEncodingResult<LoweringResult> {
     let lowerer = self::Lowerer::new(encoder);
     let mut result = lowerer.lower_procedure(def_id, procedure)?;
-    if let Some(path) = prusti_common::config::execute_only_failing_trace() {
+    if let Some(path) = prusti_common::config::execute_only_failing_trace()
+        && super::transformations::is_pass_enabled("remove_unvisited_blocks")
+    {
         let label_markers: FxHashMap<String, bool> =
             serde_json::from_reader(std::fs::File::open(path).unwrap()).unwrap();
         super::transformations::remove_unvisited_blocks::remove_unvisited_blocks(
@@ -99,9 +105,12 @@ pub(super) struct Lowerer<'p, 'v: 'p, 'tcx: 'v> {
     pub(super) compute_address_state: ComputeAddressState,
     pub(super) snapshots_state: SnapshotsState,
     pub(super) types_state: TypesState,
+    pub(super) type_layouts_state: TypeLayoutsState,
     pub(super) adts_state: AdtsState,
+    pub(super) bitwise_state: BitwiseState,
     pub(super) lifetimes_state: LifetimesState,
     pub(super) places_state: PlacesState,
+    pub(super) pointers_state: PointersState,
 }
 
 impl<'p, 'v: 'p, 'tcx: 'v> Lowerer<'p, 'v, 'tcx> {
@@ -120,9 +129,12 @@ impl<'p, 'v: 'p, 'tcx: 'v> Lowerer<'p, 'v, 'tcx> {
             compute_address_state: Default::default(),
             snapshots_state: Default::default(),
             types_state: Default::default(),
+            type_layouts_state: Default::default(),
             adts_state: Default::default(),
+            bitwise_state: Default::default(),
             lifetimes_state: Default::default(),
             places_state: Default::default(),
+            pointers_state: Default::default(),
         }
     }
 
@@ -197,7 +209,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> Lowerer<'p, 'v, 'tcx> {
             });
         }
         let mut removed_functions = FxHashSet::default();
-        if procedure.check_mode == CheckMode::Specifications {
+        if procedure.check_mode == CheckMode::Specifications
+            && super::transformations::is_pass_enabled("remove_predicates")
+        {
             removed_functions.insert(self.encode_memory_block_bytes_function_name()?);
         }
         let mut predicates = self.collect_owned_predicate_decls()?;
@@ -215,7 +229,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> Lowerer<'p, 'v, 'tcx> {
         };
         let mut methods = self.methods_state.destruct();
         let mut functions = self.functions_state.destruct();
-        if procedure.check_mode == CheckMode::Specifications {
+        if procedure.check_mode == CheckMode::Specifications
+            && super::transformations::is_pass_enabled("remove_predicates")
+        {
             super::transformations::remove_predicates::remove_predicates(
                 &mut lowered_procedure,
                 &mut methods,
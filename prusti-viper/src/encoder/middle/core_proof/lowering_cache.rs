@@ -0,0 +1,104 @@
+//! Reuse of the lowered forms of items that were already lowered.
+//!
+//! The entries are keyed by the item and its definition, so an unchanged item
+//! that is lowered again returns its previous lowering. The cache lives only in
+//! memory: lowering registers the positions of the errors in the encoder, which
+//! would be lost if an entry was loaded from disk.
+
+use rustc_hash::FxHashMap;
+use std::hash::Hash;
+
+pub(super) struct LoweringCache<K, T> {
+    entries: FxHashMap<K, T>,
+}
+
+impl<K, T> Default for LoweringCache<K, T> {
+    fn default() -> Self {
+        Self {
+            entries: FxHashMap::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, T: Clone> LoweringCache<K, T> {
+    /// Returns the previous lowering of `key` or lowers it with `lower`. Note
+    /// that the VIR items are compared without their positions, which is sound
+    /// only because the positions of the first lowering stay registered for
+    /// the whole compilation. Failed lowerings are not cached.
+    pub(super) fn get_or_lower<E>(
+        &mut self,
+        key: K,
+        lower: impl FnOnce(K) -> Result<T, E>,
+    ) -> Result<T, E> {
+        if let Some(value) = self.entries.get(&key) {
+            return Ok(value.clone());
+        }
+        let value = lower(key.clone())?;
+        self.entries.insert(key, value.clone());
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lower_counting(
+        cache: &mut LoweringCache<(&'static str, u32), String>,
+        key: (&'static str, u32),
+        lowerings: &mut u32,
+    ) -> String {
+        cache
+            .get_or_lower(key, |(name, version)| -> Result<_, ()> {
+                *lowerings += 1;
+                Ok(format!("{name}@{version}"))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_unchanged_item_is_hit() {
+        let mut cache = LoweringCache::default();
+        let mut lowerings = 0;
+        assert_eq!(
+            lower_counting(&mut cache, ("item", 1), &mut lowerings),
+            "item@1"
+        );
+        assert_eq!(
+            lower_counting(&mut cache, ("item", 1), &mut lowerings),
+            "item@1"
+        );
+        assert_eq!(lowerings, 1);
+    }
+
+    #[test]
+    fn test_changed_item_is_miss() {
+        let mut cache = LoweringCache::default();
+        let mut lowerings = 0;
+        lower_counting(&mut cache, ("item", 1), &mut lowerings);
+        assert_eq!(
+            lower_counting(&mut cache, ("item", 2), &mut lowerings),
+            "item@2"
+        );
+        assert_eq!(
+            lower_counting(&mut cache, ("other", 1), &mut lowerings),
+            "other@1"
+        );
+        assert_eq!(lowerings, 3);
+    }
+
+    #[test]
+    fn test_failed_lowering_is_not_cached() {
+        let mut cache: LoweringCache<(&'static str, u32), String> = LoweringCache::default();
+        assert_eq!(
+            cache.get_or_lower(("item", 1), |_| Err("failed")),
+            Err("failed")
+        );
+        let mut lowerings = 0;
+        assert_eq!(
+            lower_counting(&mut cache, ("item", 1), &mut lowerings),
+            "item@1"
+        );
+        assert_eq!(lowerings, 1);
+    }
+}
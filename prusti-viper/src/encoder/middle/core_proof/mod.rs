@@ -1,5 +1,6 @@
 mod addresses;
 mod adts;
+mod bitwise;
 mod block_markers;
 mod builtin_methods;
 mod compute_address;
@@ -10,13 +11,20 @@ mod interface;
 mod into_low;
 mod lifetimes;
 mod lowerer;
+mod lowering_cache;
 mod places;
+mod pointers;
 mod predicates;
 mod references;
 mod snapshots;
+mod statistics;
 mod transformations;
 mod type_layouts;
 mod types;
 mod utils;
 
-pub(crate) use self::interface::{MidCoreProofEncoderInterface, MidCoreProofEncoderState};
+pub use self::transformations::TRANSFORMATION_PASSES;
+pub(crate) use self::{
+    interface::{MidCoreProofEncoderInterface, MidCoreProofEncoderState},
+    statistics::ProgramStatistics,
+};
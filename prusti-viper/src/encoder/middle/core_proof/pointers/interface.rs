@@ -0,0 +1,176 @@
+use crate::encoder::{
+    errors::SpannedEncodingResult,
+    middle::core_proof::{
+        addresses::AddressesInterface,
+        lowerer::{FunctionsLowererInterface, Lowerer, PredicatesLowererInterface},
+        snapshots::{IntoSnapshot, SnapshotValidityInterface, SnapshotValuesInterface},
+    },
+};
+use rustc_hash::FxHashSet;
+use vir_crate::{
+    common::identifier::WithIdentifier,
+    low::{self as vir_low},
+    middle::{self as vir_mid},
+};
+
+#[derive(Default)]
+pub(in super::super) struct PointersState {
+    /// For which target types the `PointerValid` predicates were declared.
+    encoded_validity_predicates: FxHashSet<String>,
+    /// For which target types the target snapshot functions were declared.
+    encoded_target_functions: FxHashSet<String>,
+}
+
+trait Private {
+    fn encode_pointer_validity_predicate_name(
+        &mut self,
+        target_type: &vir_mid::Type,
+    ) -> SpannedEncodingResult<String>;
+    fn encode_pointer_validity_predicate(
+        &mut self,
+        target_type: &vir_mid::Type,
+    ) -> SpannedEncodingResult<String>;
+    fn encode_pointer_target_function(
+        &mut self,
+        target_type: &vir_mid::Type,
+    ) -> SpannedEncodingResult<String>;
+}
+
+impl<'p, 'v: 'p, 'tcx: 'v> Private for Lowerer<'p, 'v, 'tcx> {
+    fn encode_pointer_validity_predicate_name(
+        &mut self,
+        target_type: &vir_mid::Type,
+    ) -> SpannedEncodingResult<String> {
+        Ok(format!("PointerValid${}", target_type.get_identifier()))
+    }
+    fn encode_pointer_validity_predicate(
+        &mut self,
+        target_type: &vir_mid::Type,
+    ) -> SpannedEncodingResult<String> {
+        let predicate_name = self.encode_pointer_validity_predicate_name(target_type)?;
+        if !self
+            .pointers_state
+            .encoded_validity_predicates
+            .contains(&predicate_name)
+        {
+            self.pointers_state
+                .encoded_validity_predicates
+                .insert(predicate_name.clone());
+            let predicate = vir_low::PredicateDecl::new(
+                predicate_name.clone(),
+                vec![vir_low::VariableDecl::new("address", self.address_type()?)],
+                None,
+            );
+            self.declare_predicate(predicate)?;
+        }
+        Ok(predicate_name)
+    }
+    /// Declares a function that returns the snapshot of the value stored at
+    /// the given address. The function requires the `PointerValid` predicate,
+    /// so each of its applications checks that the pointer is valid.
+    fn encode_pointer_target_function(
+        &mut self,
+        target_type: &vir_mid::Type,
+    ) -> SpannedEncodingResult<String> {
+        let predicate_name = self.encode_pointer_validity_predicate(target_type)?;
+        let function_name = format!("{predicate_name}$target");
+        if !self
+            .pointers_state
+            .encoded_target_functions
+            .contains(&function_name)
+        {
+            self.pointers_state
+                .encoded_target_functions
+                .insert(function_name.clone());
+            use vir_low::macros::*;
+            let return_type = target_type.to_snapshot(self)?;
+            var_decls! { address: Address };
+            let result: vir_low::Expression = var! { __result: {return_type.clone()} }.into();
+            let validity = vir_low::Expression::predicate_access_predicate(
+                predicate_name,
+                vec![address.clone().into()],
+                vir_low::Expression::wildcard_permission(),
+                Default::default(),
+            );
+            let result_validity = self.encode_snapshot_valid_call_for_type(result, target_type)?;
+            let function = vir_low::FunctionDecl::new(
+                function_name.clone(),
+                vir_low::FunctionKind::PointerTarget,
+                vec![address],
+                return_type,
+                vec![validity],
+                vec![result_validity],
+                None,
+            );
+            self.declare_function(function)?;
+        }
+        Ok(function_name)
+    }
+}
+
+pub(in super::super) trait PointersInterface {
+    /// The address stored in the pointer with the given snapshot.
+    fn pointer_address(
+        &mut self,
+        pointer_type: &vir_mid::Type,
+        snapshot: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+    /// `acc(PointerValid(address(snapshot)), wildcard)`
+    fn encode_pointer_validity_acc(
+        &mut self,
+        pointer_type: &vir_mid::Type,
+        snapshot: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+    /// The snapshot of the value the pointer points to. The resulting
+    /// expression is well-defined only if the pointer is valid.
+    fn pointer_target_snapshot(
+        &mut self,
+        pointer_type: &vir_mid::Type,
+        snapshot: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+}
+
+impl<'p, 'v: 'p, 'tcx: 'v> PointersInterface for Lowerer<'p, 'v, 'tcx> {
+    fn pointer_address(
+        &mut self,
+        pointer_type: &vir_mid::Type,
+        snapshot: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        self.obtain_constant_value(pointer_type, snapshot, position)
+    }
+    fn encode_pointer_validity_acc(
+        &mut self,
+        pointer_type: &vir_mid::Type,
+        snapshot: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        let target_type = &*pointer_type.clone().unwrap_pointer().target_type;
+        let predicate_name = self.encode_pointer_validity_predicate(target_type)?;
+        let address = self.pointer_address(pointer_type, snapshot, position)?;
+        Ok(vir_low::Expression::predicate_access_predicate(
+            predicate_name,
+            vec![address],
+            vir_low::Expression::wildcard_permission(),
+            position,
+        ))
+    }
+    fn pointer_target_snapshot(
+        &mut self,
+        pointer_type: &vir_mid::Type,
+        snapshot: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        let target_type = &*pointer_type.clone().unwrap_pointer().target_type;
+        let function_name = self.encode_pointer_target_function(target_type)?;
+        let return_type = target_type.to_snapshot(self)?;
+        let address = self.pointer_address(pointer_type, snapshot, position)?;
+        Ok(
+            vir_low::Expression::function_call(function_name, vec![address], return_type)
+                .set_default_position(position),
+        )
+    }
+}
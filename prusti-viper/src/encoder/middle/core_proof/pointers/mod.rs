@@ -0,0 +1,11 @@
+//! Encoding of raw pointers.
+//!
+//! Reading through a raw pointer requires a `PointerValid` predicate instance
+//! for the pointed-to address. The instance can be obtained only from a
+//! specification such as `#[requires(ptr_valid(p))]`, which means that
+//! dereferencing a pointer without establishing its validity fails
+//! verification.
+
+mod interface;
+
+pub(super) use self::interface::{PointersInterface, PointersState};
@@ -1,21 +1,89 @@
 use super::super::super::lowerer::Lowerer;
 use crate::encoder::{
-    errors::SpannedEncodingResult,
+    errors::{SpannedEncodingError, SpannedEncodingResult},
     high::types::HighTypeEncoderInterface,
     middle::core_proof::{
         lifetimes::*,
         lowerer::DomainsLowererInterface,
+        pointers::PointersInterface,
         references::ReferencesInterface,
-        snapshots::{IntoSnapshot, SnapshotDomainsInterface, SnapshotValuesInterface},
+        snapshots::{
+            IntoSnapshot, SnapshotDomainsInterface, SnapshotLemmasInterface,
+            SnapshotValuesInterface,
+        },
+        type_layouts::TypeLayoutsInterface,
         types::TypesInterface,
     },
 };
+use prusti_rustc_interface::errors::MultiSpan;
 use vir_crate::{
     common::{identifier::WithIdentifier, position::Positioned},
     low::{self as vir_low},
-    middle::{self as vir_mid, operations::ty::Typed},
+    middle::{
+        self as vir_mid,
+        operations::ty::Typed,
+        visitors::{default_walk_expression, ExpressionWalker},
+    },
 };
 
+/// The position of an application of `ptr_valid` in `expression`, if there is
+/// one.
+fn find_pointer_validity(expression: &vir_mid::Expression) -> Option<vir_mid::Position> {
+    struct Finder {
+        position: Option<vir_mid::Position>,
+    }
+    impl ExpressionWalker for Finder {
+        fn walk_expression(&mut self, expression: &vir_mid::Expression) {
+            if self.position.is_some() {
+                return;
+            }
+            match expression {
+                vir_mid::Expression::BuiltinFuncApp(app)
+                    if app.function == vir_mid::BuiltinFunc::PointerValid =>
+                {
+                    self.position = Some(app.position);
+                }
+                _ => default_walk_expression(self, expression),
+            }
+        }
+    }
+    let mut finder = Finder { position: None };
+    finder.walk_expression(expression);
+    finder.position
+}
+
+fn pointer_validity_error(
+    lowerer: &Lowerer,
+    position: vir_mid::Position,
+    message: &str,
+) -> SpannedEncodingError {
+    let span = lowerer
+        .encoder
+        .error_manager()
+        .position_manager()
+        .get_span(position.into())
+        .cloned()
+        .unwrap_or_else(MultiSpan::new);
+    SpannedEncodingError::incorrect(message, span)
+}
+
+/// Reports an error if `operand` contains `ptr_valid`, which is encoded as an
+/// accessibility predicate and thus cannot occur below `!`, `||` or on the
+/// left of `==>`.
+fn reject_pointer_validity_in_operand(
+    lowerer: &Lowerer,
+    operand: &vir_mid::Expression,
+) -> SpannedEncodingResult<()> {
+    if let Some(position) = find_pointer_validity(operand) {
+        return Err(pointer_validity_error(
+            lowerer,
+            position,
+            "ptr_valid cannot be negated, used in a disjunction, or used as the condition of an implication",
+        ));
+    }
+    Ok(())
+}
+
 pub(super) trait IntoSnapshotLowerer<'p, 'v: 'p, 'tcx: 'v> {
     fn expression_vec_to_snapshot(
         &mut self,
@@ -187,11 +255,15 @@ pub(super) trait IntoSnapshotLowerer<'p, 'v: 'p, 'tcx: 'v> {
         expect_math_bool: bool,
     ) -> SpannedEncodingResult<vir_low::Expression> {
         let base_snapshot = self.expression_to_snapshot(lowerer, &deref.base, expect_math_bool)?;
-        let result = lowerer.reference_target_current_snapshot(
-            deref.base.get_type(),
-            base_snapshot,
-            Default::default(),
-        )?;
+        let result = if deref.base.get_type().is_pointer() {
+            lowerer.pointer_target_snapshot(deref.base.get_type(), base_snapshot, deref.position)?
+        } else {
+            lowerer.reference_target_current_snapshot(
+                deref.base.get_type(),
+                base_snapshot,
+                Default::default(),
+            )?
+        };
         self.ensure_bool_expression(lowerer, deref.get_type(), result, expect_math_bool)
     }
 
@@ -290,6 +362,9 @@ pub(super) trait IntoSnapshotLowerer<'p, 'v: 'p, 'tcx: 'v> {
         op: &vir_mid::UnaryOp,
         expect_math_bool: bool,
     ) -> SpannedEncodingResult<vir_low::Expression> {
+        if op.op_kind == vir_mid::UnaryOpKind::Not {
+            reject_pointer_validity_in_operand(lowerer, &op.argument)?;
+        }
         let argument_snapshot =
             self.expression_to_snapshot(lowerer, &op.argument, expect_math_bool)?;
         let ty = if expect_math_bool && op.get_type() == &vir_mid::Type::Bool {
@@ -358,6 +433,16 @@ pub(super) trait IntoSnapshotLowerer<'p, 'v: 'p, 'tcx: 'v> {
             }
         }
 
+        match op.op_kind {
+            vir_mid::BinaryOpKind::Or => {
+                reject_pointer_validity_in_operand(lowerer, &op.left)?;
+                reject_pointer_validity_in_operand(lowerer, &op.right)?;
+            }
+            vir_mid::BinaryOpKind::Implies => {
+                reject_pointer_validity_in_operand(lowerer, &op.left)?;
+            }
+            _ => {}
+        }
         let expect_math_bool_args = expect_math_bool
             && matches!(
                 op.op_kind,
@@ -407,6 +492,13 @@ pub(super) trait IntoSnapshotLowerer<'p, 'v: 'p, 'tcx: 'v> {
             vir_mid::BinaryOpKind::And => vir_low::BinaryOpKind::And,
             vir_mid::BinaryOpKind::Or => vir_low::BinaryOpKind::Or,
             vir_mid::BinaryOpKind::Implies => vir_low::BinaryOpKind::Implies,
+            vir_mid::BinaryOpKind::BitAnd
+            | vir_mid::BinaryOpKind::BitOr
+            | vir_mid::BinaryOpKind::BitXor
+            | vir_mid::BinaryOpKind::Shl
+            | vir_mid::BinaryOpKind::Shr => {
+                unreachable!("bitwise operations are encoded as domain functions")
+            }
             vir_mid::BinaryOpKind::LifetimeIntersection => unreachable!(),
         })
     }
@@ -493,15 +585,17 @@ pub(super) trait IntoSnapshotLowerer<'p, 'v: 'p, 'tcx: 'v> {
         };
 
         match app.function {
-            BuiltinFunc::Size => {
+            BuiltinFunc::Size | BuiltinFunc::Align => {
                 let return_type = self.type_to_snapshot(lowerer, &app.return_type)?;
-                lowerer.create_domain_func_app(
+                let function_app = lowerer.create_domain_func_app(
                     "Size",
                     app.get_identifier(),
                     args,
                     return_type,
                     app.position,
-                )
+                )?;
+                lowerer.encode_type_layout_axioms(&app.type_arguments[0])?;
+                Ok(function_app)
             }
             BuiltinFunc::PaddingSize => {
                 assert_eq!(args.len(), 0);
@@ -653,6 +747,51 @@ pub(super) trait IntoSnapshotLowerer<'p, 'v: 'p, 'tcx: 'v> {
                 let value = seq(ContainerOpKind::SeqLen)?;
                 lowerer.construct_constant_snapshot(app.get_type(), value, app.position)
             }
+            BuiltinFunc::PointerValid => {
+                assert_eq!(args.len(), 1);
+                if !expect_math_bool {
+                    return Err(pointer_validity_error(
+                        lowerer,
+                        app.position,
+                        "ptr_valid can be used only as a conjunct of a specification",
+                    ));
+                }
+                lowerer.encode_pointer_validity_acc(
+                    app.arguments[0].get_type(),
+                    args.pop().unwrap(),
+                    app.position,
+                )
+            }
+            BuiltinFunc::SnapshotEqualityReflexivity => {
+                assert_eq!(args.len(), 1);
+                let value = lowerer.encode_snapshot_equality_reflexivity_call(
+                    app.arguments[0].get_type(),
+                    args.pop().unwrap(),
+                    app.position,
+                )?;
+                if expect_math_bool {
+                    Ok(value)
+                } else {
+                    lowerer.construct_constant_snapshot(&vir_mid::Type::Bool, value, app.position)
+                }
+            }
+            BuiltinFunc::SnapshotEqualityCongruence => {
+                assert_eq!(args.len(), 2);
+                assert_eq!(app.arguments[0].get_type(), app.arguments[1].get_type());
+                let right = args.pop().unwrap();
+                let left = args.pop().unwrap();
+                let value = lowerer.encode_snapshot_equality_congruence_call(
+                    app.arguments[0].get_type(),
+                    left,
+                    right,
+                    app.position,
+                )?;
+                if expect_math_bool {
+                    Ok(value)
+                } else {
+                    lowerer.construct_constant_snapshot(&vir_mid::Type::Bool, value, app.position)
+                }
+            }
             BuiltinFunc::SnapshotEquality => {
                 assert_eq!(app.arguments[0].get_type(), app.arguments[1].get_type());
                 let value = vir_low::Expression::binary_op(
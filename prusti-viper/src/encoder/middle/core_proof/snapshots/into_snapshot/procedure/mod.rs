@@ -7,6 +7,7 @@ use crate::encoder::{
     errors::SpannedEncodingResult,
     middle::core_proof::{
         lowerer::{FunctionsLowererInterface, Lowerer},
+        pointers::PointersInterface,
         references::ReferencesInterface,
         snapshots::SnapshotVariablesInterface,
     },
@@ -89,6 +90,10 @@ impl<'p, 'v: 'p, 'tcx: 'v> IntoSnapshotLowerer<'p, 'v, 'tcx> for ProcedureSnapsh
                 base_snapshot,
                 Default::default(),
             )?
+        } else if deref.base.get_type().is_pointer() {
+            let base_snapshot =
+                self.expression_to_snapshot(lowerer, &deref.base, expect_math_bool)?;
+            lowerer.pointer_target_snapshot(deref.base.get_type(), base_snapshot, deref.position)?
         } else {
             let base_snapshot =
                 self.expression_to_snapshot(lowerer, &deref.base, expect_math_bool)?;
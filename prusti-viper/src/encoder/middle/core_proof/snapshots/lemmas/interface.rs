@@ -0,0 +1,207 @@
+use crate::encoder::{
+    errors::{SpannedEncodingError, SpannedEncodingResult},
+    high::types::HighTypeEncoderInterface,
+    middle::core_proof::{
+        lowerer::{FunctionsLowererInterface, Lowerer},
+        snapshots::{IntoSnapshot, SnapshotValidityInterface, SnapshotValuesInterface},
+    },
+};
+use prusti_rustc_interface::errors::MultiSpan;
+use vir_crate::{
+    common::{expression::ExpressionIterator, identifier::WithIdentifier},
+    low::{self as vir_low},
+    middle::{self as vir_mid},
+};
+
+trait Private {
+    /// Declares the function `snapshot_equality_reflexivity$T(value): Bool`
+    /// with the postcondition `value == value`.
+    fn encode_snapshot_equality_reflexivity_lemma(
+        &mut self,
+        ty: &vir_mid::Type,
+    ) -> SpannedEncodingResult<String>;
+    /// Declares the function `snapshot_equality_congruence$T(left, right):
+    /// Bool` that requires the fields of `left` and `right` to be equal and
+    /// ensures `left == right`.
+    fn encode_snapshot_equality_congruence_lemma(
+        &mut self,
+        ty: &vir_mid::Type,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<String>;
+    /// The equalities of the components of the two snapshots that imply the
+    /// equality of the snapshots themselves. Tuples are structs at this level,
+    /// so they are covered by the struct case.
+    fn snapshot_component_equalities(
+        &mut self,
+        ty: &vir_mid::Type,
+        left: vir_low::Expression,
+        right: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<Vec<vir_low::Expression>>;
+}
+
+impl<'p, 'v: 'p, 'tcx: 'v> Private for Lowerer<'p, 'v, 'tcx> {
+    fn encode_snapshot_equality_reflexivity_lemma(
+        &mut self,
+        ty: &vir_mid::Type,
+    ) -> SpannedEncodingResult<String> {
+        let function_name = format!("snapshot_equality_reflexivity${}", ty.get_identifier());
+        if !self.snapshots_state.encoded_reflexivity_lemmas.contains(ty) {
+            self.snapshots_state
+                .encoded_reflexivity_lemmas
+                .insert(ty.clone());
+            use vir_low::macros::*;
+            var_decls! { value: {ty.to_snapshot(self)?} };
+            let function = vir_low::FunctionDecl::new(
+                function_name.clone(),
+                vir_low::FunctionKind::SnapshotLemma,
+                vec![value.clone()],
+                vir_low::Type::Bool,
+                Vec::new(),
+                vec![expr! { value == value }],
+                Some(true.into()),
+            );
+            self.declare_function(function)?;
+        }
+        Ok(function_name)
+    }
+    fn encode_snapshot_equality_congruence_lemma(
+        &mut self,
+        ty: &vir_mid::Type,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<String> {
+        let function_name = format!("snapshot_equality_congruence${}", ty.get_identifier());
+        if !self.snapshots_state.encoded_congruence_lemmas.contains(ty) {
+            use vir_low::macros::*;
+            var_decls! {
+                left: {ty.to_snapshot(self)?},
+                right: {ty.to_snapshot(self)?}
+            };
+            let mut pres = vec![
+                self.encode_snapshot_valid_call_for_type(left.clone().into(), ty)?,
+                self.encode_snapshot_valid_call_for_type(right.clone().into(), ty)?,
+            ];
+            pres.extend(self.snapshot_component_equalities(
+                ty,
+                left.clone().into(),
+                right.clone().into(),
+                position,
+            )?);
+            self.snapshots_state
+                .encoded_congruence_lemmas
+                .insert(ty.clone());
+            // The body is `true`, so Viper checks that the postcondition
+            // follows from the snapshot domain axioms.
+            let function = vir_low::FunctionDecl::new(
+                function_name.clone(),
+                vir_low::FunctionKind::SnapshotLemma,
+                vec![left.clone(), right.clone()],
+                vir_low::Type::Bool,
+                vec![pres.into_iter().conjoin()],
+                vec![expr! { left == right }],
+                Some(true.into()),
+            );
+            self.declare_function(function)?;
+        }
+        Ok(function_name)
+    }
+    fn snapshot_component_equalities(
+        &mut self,
+        ty: &vir_mid::Type,
+        left: vir_low::Expression,
+        right: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<Vec<vir_low::Expression>> {
+        use vir_low::macros::*;
+        let type_decl = self.encoder.get_type_decl_mid(ty)?;
+        let equalities = match &type_decl {
+            vir_mid::TypeDecl::Bool | vir_mid::TypeDecl::Int(_) | vir_mid::TypeDecl::Pointer(_) => {
+                let left_value = self.obtain_constant_value(ty, left, Default::default())?;
+                let right_value = self.obtain_constant_value(ty, right, Default::default())?;
+                vec![expr! { [left_value] == [right_value] }]
+            }
+            vir_mid::TypeDecl::Struct(decl) => {
+                let mut equalities = Vec::new();
+                for field in &decl.fields {
+                    let left_field = self.obtain_struct_field_snapshot(
+                        ty,
+                        field,
+                        left.clone(),
+                        Default::default(),
+                    )?;
+                    let right_field = self.obtain_struct_field_snapshot(
+                        ty,
+                        field,
+                        right.clone(),
+                        Default::default(),
+                    )?;
+                    equalities.push(expr! { [left_field] == [right_field] });
+                }
+                equalities
+            }
+            _ => {
+                let span = self
+                    .encoder
+                    .error_manager()
+                    .position_manager()
+                    .get_span(position.into())
+                    .cloned()
+                    .unwrap_or_else(MultiSpan::new);
+                return Err(SpannedEncodingError::unsupported(
+                    format!("snapshot equality congruence for values of type {ty}"),
+                    span,
+                ));
+            }
+        };
+        Ok(equalities)
+    }
+}
+
+pub(in super::super::super) trait SnapshotLemmasInterface {
+    /// A call to the lemma that states that the snapshot is equal to itself.
+    fn encode_snapshot_equality_reflexivity_call(
+        &mut self,
+        ty: &vir_mid::Type,
+        value: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+    /// A call to the lemma that states that two snapshots with equal fields
+    /// are equal. The call is well-defined only if the fields are equal.
+    fn encode_snapshot_equality_congruence_call(
+        &mut self,
+        ty: &vir_mid::Type,
+        left: vir_low::Expression,
+        right: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+}
+
+impl<'p, 'v: 'p, 'tcx: 'v> SnapshotLemmasInterface for Lowerer<'p, 'v, 'tcx> {
+    fn encode_snapshot_equality_reflexivity_call(
+        &mut self,
+        ty: &vir_mid::Type,
+        value: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        let function_name = self.encode_snapshot_equality_reflexivity_lemma(ty)?;
+        Ok(
+            vir_low::Expression::function_call(function_name, vec![value], vir_low::Type::Bool)
+                .set_default_position(position),
+        )
+    }
+    fn encode_snapshot_equality_congruence_call(
+        &mut self,
+        ty: &vir_mid::Type,
+        left: vir_low::Expression,
+        right: vir_low::Expression,
+        position: vir_low::Position,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        let function_name = self.encode_snapshot_equality_congruence_lemma(ty, position)?;
+        Ok(vir_low::Expression::function_call(
+            function_name,
+            vec![left, right],
+            vir_low::Type::Bool,
+        )
+        .set_default_position(position))
+    }
+}
@@ -0,0 +1,6 @@
+//! Lemmas about snapshot equality that a user can invoke from specifications
+//! when the SMT solver fails to instantiate the snapshot domain axioms.
+
+mod interface;
+
+pub(in super::super) use self::interface::SnapshotLemmasInterface;
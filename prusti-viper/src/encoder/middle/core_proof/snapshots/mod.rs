@@ -3,6 +3,7 @@ mod builtin_functions;
 mod bytes;
 mod domains;
 mod into_snapshot;
+mod lemmas;
 mod state;
 mod validity;
 mod values;
@@ -17,6 +18,7 @@ pub(super) use self::{
         IntoBuiltinMethodSnapshot, IntoProcedureBoolExpression, IntoProcedureFinalSnapshot,
         IntoProcedureSnapshot, IntoPureBoolExpression, IntoPureSnapshot, IntoSnapshot,
     },
+    lemmas::SnapshotLemmasInterface,
     state::SnapshotsState,
     validity::{valid_call, valid_call2, SnapshotValidityInterface},
     values::SnapshotValuesInterface,
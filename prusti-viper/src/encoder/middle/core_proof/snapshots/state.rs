@@ -15,6 +15,12 @@ pub(in super::super) struct SnapshotsState {
     pub(super) encoded_to_bytes: FxHashSet<vir_mid::Type>,
     /// The list of types for which sequence_repeat_constructor was encoded.
     pub(super) encoded_sequence_repeat_constructor: FxHashSet<vir_mid::Type>,
+    /// The list of types for which the snapshot equality reflexivity lemma
+    /// was encoded.
+    pub(super) encoded_reflexivity_lemmas: FxHashSet<vir_mid::Type>,
+    /// The list of types for which the snapshot equality congruence lemma
+    /// was encoded.
+    pub(super) encoded_congruence_lemmas: FxHashSet<vir_mid::Type>,
     pub(super) all_variables: AllVariablesMap,
     pub(super) variables: BTreeMap<vir_mid::BasicBlockId, VariableVersionMap>,
     pub(super) variables_at_label: BTreeMap<String, VariableVersionMap>,
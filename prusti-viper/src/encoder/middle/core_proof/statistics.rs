@@ -0,0 +1,93 @@
+//! Metrics of the lowered programs that are reported with the `stats` flag.
+
+use vir_crate::low::{
+    self as vir_low, expression::visitors::ExpressionWalker, statement::visitors::StatementWalker,
+};
+
+/// The size of a lowered program.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ProgramStatistics {
+    /// The number of basic blocks of the procedures.
+    pub(crate) blocks: usize,
+    /// The number of predicate declarations.
+    pub(crate) predicates: usize,
+    /// The number of quantifiers in all expressions of the program, including
+    /// the axioms of the domains.
+    pub(crate) quantifiers: usize,
+}
+
+impl ProgramStatistics {
+    pub(super) fn collect(program: &vir_low::Program) -> Self {
+        let mut counter = QuantifierCounter::default();
+        for procedure in &program.procedures {
+            for block in &procedure.basic_blocks {
+                for statement in &block.statements {
+                    counter.walk_statement(statement);
+                }
+                if let vir_low::Successor::GotoSwitch(targets) = &block.successor {
+                    for (test, _) in targets {
+                        ExpressionWalker::walk_expression(&mut counter, test);
+                    }
+                }
+            }
+        }
+        for method in &program.methods {
+            for expression in method.pres.iter().chain(&method.posts) {
+                ExpressionWalker::walk_expression(&mut counter, expression);
+            }
+            for statement in method.body.iter().flatten() {
+                counter.walk_statement(statement);
+            }
+        }
+        for function in &program.functions {
+            for expression in function
+                .pres
+                .iter()
+                .chain(&function.posts)
+                .chain(&function.body)
+            {
+                ExpressionWalker::walk_expression(&mut counter, expression);
+            }
+        }
+        for predicate in &program.predicates {
+            if let Some(body) = &predicate.body {
+                ExpressionWalker::walk_expression(&mut counter, body);
+            }
+        }
+        for domain in &program.domains {
+            for axiom in &domain.axioms {
+                ExpressionWalker::walk_expression(&mut counter, &axiom.body);
+            }
+        }
+        Self {
+            blocks: program
+                .procedures
+                .iter()
+                .map(|procedure| procedure.basic_blocks.len())
+                .sum(),
+            predicates: program.predicates.len(),
+            quantifiers: counter.quantifiers,
+        }
+    }
+}
+
+#[derive(Default)]
+struct QuantifierCounter {
+    quantifiers: usize,
+}
+
+impl ExpressionWalker for QuantifierCounter {
+    fn walk_quantifier(&mut self, quantifier: &vir_low::expression::Quantifier) {
+        self.quantifiers += 1;
+        for trigger in &quantifier.triggers {
+            ExpressionWalker::walk_trigger(self, trigger);
+        }
+        ExpressionWalker::walk_expression(self, &quantifier.body);
+    }
+}
+
+impl StatementWalker for QuantifierCounter {
+    fn walk_expression(&mut self, expression: &vir_low::Expression) {
+        ExpressionWalker::walk_expression(self, expression);
+    }
+}
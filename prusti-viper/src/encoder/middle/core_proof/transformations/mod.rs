@@ -1,3 +1,22 @@
+use prusti_common::config;
+
 pub(super) mod inline_functions;
 pub(super) mod remove_predicates;
 pub(super) mod remove_unvisited_blocks;
+
+/// The names of the transformation passes. Each pass can be disabled with the
+/// `disable_pass` flag.
+pub const TRANSFORMATION_PASSES: &[&str] = &[
+    "inline_caller_for",
+    "remove_predicates",
+    "remove_unvisited_blocks",
+];
+
+/// Whether the pass was not disabled with the `disable_pass` flag.
+pub(super) fn is_pass_enabled(name: &str) -> bool {
+    debug_assert!(
+        TRANSFORMATION_PASSES.contains(&name),
+        "unknown pass: {name}"
+    );
+    !config::disable_pass().iter().any(|pass| pass == name)
+}
@@ -2,15 +2,88 @@ use crate::encoder::{
     errors::SpannedEncodingResult,
     high::{type_layouts::HighTypeLayoutsEncoderInterface, types::HighTypeEncoderInterface},
     middle::core_proof::{
-        lowerer::Lowerer,
-        snapshots::{IntoBuiltinMethodSnapshot, IntoProcedureSnapshot, IntoSnapshot},
+        lowerer::{DomainsLowererInterface, Lowerer},
+        snapshots::{
+            IntoBuiltinMethodSnapshot, IntoProcedureSnapshot, IntoPureSnapshot, IntoSnapshot,
+            SnapshotValuesInterface,
+        },
     },
 };
+use prusti_rustc_interface::middle::ty;
+use rustc_hash::FxHashSet;
 use vir_crate::{
+    common::{
+        expression::{BinaryOperationHelpers, ExpressionIterator},
+        identifier::WithIdentifier,
+    },
     low as vir_low,
     middle::{self as vir_mid, operations::const_generics::WithConstArguments},
 };
 
+#[derive(Default)]
+pub(in super::super) struct TypeLayoutsState {
+    /// Types for which the layout axioms were already declared.
+    encoded_types: FxHashSet<vir_mid::Type>,
+}
+
+trait Private {
+    fn size_and_align_values(
+        &mut self,
+        ty: &vir_mid::Type,
+    ) -> SpannedEncodingResult<(vir_low::Expression, vir_low::Expression)>;
+    /// The size and, if known, the alignment of primitive types. All primitive
+    /// sizes are fixed by the language except of `usize` and `isize`, which
+    /// depend on the pointer width of the compilation target. Alignments are
+    /// target-dependent except of the one-byte types.
+    fn primitive_layout(&mut self, ty: &vir_mid::Type) -> Option<(u64, Option<u64>)>;
+    /// Whether the type is known to be zero-sized, e.g. `PhantomData<T>` or a
+    /// struct without fields. Types whose layout depends on generic parameters
+    /// are not known to be zero-sized.
+    fn is_zero_sized(&mut self, ty: &vir_mid::Type) -> SpannedEncodingResult<bool>;
+}
+
+impl<'p, 'v: 'p, 'tcx: 'v> Private for Lowerer<'p, 'v, 'tcx> {
+    fn size_and_align_values(
+        &mut self,
+        ty: &vir_mid::Type,
+    ) -> SpannedEncodingResult<(vir_low::Expression, vir_low::Expression)> {
+        let size_type = self.size_type_mid()?;
+        let size = self.encode_type_size_expression2(ty, ty)?;
+        let size = self.obtain_constant_value(&size_type, size, Default::default())?;
+        let align = self.encode_type_align_expression2(ty, ty)?;
+        let align = self.obtain_constant_value(&size_type, align, Default::default())?;
+        Ok((size, align))
+    }
+
+    fn primitive_layout(&mut self, ty: &vir_mid::Type) -> Option<(u64, Option<u64>)> {
+        use vir_mid::ty::{Float, Int};
+        let layout = match ty {
+            vir_mid::Type::Bool | vir_mid::Type::Int(Int::I8 | Int::U8) => (1, Some(1)),
+            vir_mid::Type::Int(Int::I16 | Int::U16) => (2, None),
+            vir_mid::Type::Int(Int::I32 | Int::U32 | Int::Char)
+            | vir_mid::Type::Float(Float::F32) => (4, None),
+            vir_mid::Type::Int(Int::I64 | Int::U64) | vir_mid::Type::Float(Float::F64) => (8, None),
+            vir_mid::Type::Int(Int::I128 | Int::U128) => (16, None),
+            vir_mid::Type::Int(Int::Isize | Int::Usize) => {
+                let pointer_size = self.encoder.env().tcx().data_layout.pointer_size.bytes();
+                (pointer_size, None)
+            }
+            _ => return None,
+        };
+        Some(layout)
+    }
+
+    fn is_zero_sized(&mut self, ty: &vir_mid::Type) -> SpannedEncodingResult<bool> {
+        let mir_type = self.encoder.decode_type_mid(ty)?;
+        let layout = self
+            .encoder
+            .env()
+            .tcx()
+            .layout_of(ty::ParamEnv::reveal_all().and(mir_type));
+        Ok(layout.map_or(false, |layout| layout.is_zst()))
+    }
+}
+
 pub(in super::super) trait TypeLayoutsInterface {
     fn size_type_mid(&mut self) -> SpannedEncodingResult<vir_mid::Type>;
     fn size_type(&mut self) -> SpannedEncodingResult<vir_low::Type>;
@@ -23,10 +96,28 @@ pub(in super::super) trait TypeLayoutsInterface {
         ty: &vir_mid::Type,
         generics: &impl WithConstArguments,
     ) -> SpannedEncodingResult<vir_low::Expression>;
+    fn encode_type_align_expression2(
+        &mut self,
+        ty: &vir_mid::Type,
+        generics: &impl WithConstArguments,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
     fn encode_type_padding_size_expression(
         &mut self,
         ty: &vir_mid::Type,
     ) -> SpannedEncodingResult<vir_low::Expression>;
+    /// Declares the axioms that relate the size and alignment of the type to
+    /// its layout: the size is a multiple of the alignment, primitive types
+    /// have their fixed sizes, and the size of a struct is the sum of the
+    /// sizes of its fields plus padding.
+    fn encode_type_layout_axioms(&mut self, ty: &vir_mid::Type) -> SpannedEncodingResult<()>;
+    /// Declares that a union is large enough to hold each of its fields. All
+    /// fields of a union start at the union's address, so a union is at least
+    /// as large as its largest field.
+    fn encode_union_layout_axioms(
+        &mut self,
+        ty: &vir_mid::Type,
+        decl: &vir_mid::type_decl::Enum,
+    ) -> SpannedEncodingResult<()>;
 }
 
 impl<'p, 'v: 'p, 'tcx: 'v> TypeLayoutsInterface for Lowerer<'p, 'v, 'tcx> {
@@ -57,6 +148,20 @@ impl<'p, 'v: 'p, 'tcx: 'v> TypeLayoutsInterface for Lowerer<'p, 'v, 'tcx> {
         );
         size.to_builtin_method_snapshot(self)
     }
+    fn encode_type_align_expression2(
+        &mut self,
+        ty: &vir_mid::Type,
+        generics: &impl WithConstArguments,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        let size_type = self.size_type_mid()?;
+        let align = vir_mid::Expression::builtin_func_app_no_pos(
+            vir_mid::BuiltinFunc::Align,
+            vec![ty.clone()],
+            generics.get_const_arguments(),
+            size_type,
+        );
+        align.to_builtin_method_snapshot(self)
+    }
     fn encode_type_padding_size_expression(
         &mut self,
         ty: &vir_mid::Type,
@@ -67,4 +172,90 @@ impl<'p, 'v: 'p, 'tcx: 'v> TypeLayoutsInterface for Lowerer<'p, 'v, 'tcx> {
             .encode_type_padding_size_expression_mid(mir_type)?;
         size.to_builtin_method_snapshot(self)
     }
+    fn encode_type_layout_axioms(&mut self, ty: &vir_mid::Type) -> SpannedEncodingResult<()> {
+        if !ty.get_const_arguments().is_empty()
+            || ty.is_type_var()
+            || self.type_layouts_state.encoded_types.contains(ty)
+        {
+            return Ok(());
+        }
+        self.type_layouts_state.encoded_types.insert(ty.clone());
+        let (size, align) = self.size_and_align_values(ty)?;
+        let mut conjuncts = vec![
+            vir_low::Expression::less_than(0.into(), align.clone()),
+            vir_low::Expression::equals(
+                vir_low::Expression::module(size.clone(), align.clone()),
+                0.into(),
+            ),
+        ];
+        if self.is_zero_sized(ty)? {
+            conjuncts.push(vir_low::Expression::equals(size, 0.into()));
+        } else if let Some((fixed_size, fixed_align)) = self.primitive_layout(ty) {
+            conjuncts.push(vir_low::Expression::equals(size, fixed_size.into()));
+            if let Some(fixed_align) = fixed_align {
+                conjuncts.push(vir_low::Expression::equals(align, fixed_align.into()));
+            }
+        } else if let vir_mid::TypeDecl::Struct(decl) = self.encoder.get_type_decl_mid(ty)? {
+            let size_type = self.size_type_mid()?;
+            let mut fields_size = self.encode_type_padding_size_expression(ty)?;
+            fields_size =
+                self.obtain_constant_value(&size_type, fields_size, Default::default())?;
+            for field in &decl.fields {
+                // Zero-sized fields such as `PhantomData<T>` do not contribute
+                // to the size of the struct.
+                if self.is_zero_sized(&field.ty)? {
+                    continue;
+                }
+                let (field_size, _) = self.size_and_align_values(&field.ty)?;
+                fields_size = vir_low::Expression::add(fields_size, field_size);
+            }
+            conjuncts.push(vir_low::Expression::equals(size, fields_size));
+        }
+        let axiom = vir_low::DomainAxiomDecl {
+            comment: None,
+            name: format!("{}$layout_axiom", ty.get_identifier()),
+            body: conjuncts.into_iter().conjoin(),
+        };
+        self.declare_axiom("Size", axiom)?;
+        Ok(())
+    }
+    fn encode_union_layout_axioms(
+        &mut self,
+        ty: &vir_mid::Type,
+        decl: &vir_mid::type_decl::Enum,
+    ) -> SpannedEncodingResult<()> {
+        use vir_low::macros::*;
+        assert!(!decl.safety.is_enum(), "{ty} is not a union");
+        let size_type = self.size_type_mid()?;
+        let union_size = self.encode_type_size_expression2(ty, ty)?;
+        let union_size = self.obtain_constant_value(&size_type, union_size, Default::default())?;
+        let mut conjuncts = Vec::new();
+        for variant in &decl.variants {
+            let variant_type = ty.clone().variant(variant.name.clone().into());
+            let variant_size = self.encode_type_size_expression2(&variant_type, &variant_type)?;
+            let variant_size =
+                self.obtain_constant_value(&size_type, variant_size, Default::default())?;
+            conjuncts.push(expr! { [variant_size] <= [union_size.clone()] });
+        }
+        let mut parameters = Vec::new();
+        for parameter in &decl.const_parameters {
+            parameters.push(parameter.to_pure_snapshot(self)?);
+        }
+        let body = if parameters.is_empty() {
+            conjuncts.into_iter().conjoin()
+        } else {
+            vir_low::Expression::forall(
+                parameters,
+                vec![vir_low::Trigger::new(vec![union_size])],
+                conjuncts.into_iter().conjoin(),
+            )
+        };
+        let axiom = vir_low::DomainAxiomDecl {
+            comment: None,
+            name: format!("{}$union_layout_axiom", ty.get_identifier()),
+            body,
+        };
+        self.declare_axiom("Size", axiom)?;
+        Ok(())
+    }
 }
@@ -1,3 +1,3 @@
 mod interface;
 
-pub(super) use self::interface::TypeLayoutsInterface;
+pub(super) use self::interface::{TypeLayoutsInterface, TypeLayoutsState};
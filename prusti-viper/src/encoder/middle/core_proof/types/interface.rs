@@ -15,6 +15,7 @@ use crate::encoder::{
     high::types::HighTypeEncoderInterface,
     middle::core_proof::{
         addresses::AddressesInterface,
+        bitwise::BitwiseInterface,
         lowerer::{DomainsLowererInterface, Lowerer},
         snapshots::{
             IntoPureSnapshot, IntoSnapshot, SnapshotAdtsInterface, SnapshotDomainsInterface,
@@ -63,6 +64,15 @@ trait Private {
         parameters: Vec<vir_low::VariableDecl>,
         evaluation_result: vir_low::Expression,
     ) -> SpannedEncodingResult<()>;
+    /// Encodes the operation on the values of two constants of type
+    /// `argument_type`.
+    fn encode_constant_binary_op(
+        &mut self,
+        op: vir_mid::BinaryOpKind,
+        argument_type: &vir_mid::Type,
+        left: vir_low::Expression,
+        right: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
 }
 
 impl<'p, 'v: 'p, 'tcx: 'v> Private for Lowerer<'p, 'v, 'tcx> {
@@ -283,6 +293,28 @@ impl<'p, 'v: 'p, 'tcx: 'v> Private for Lowerer<'p, 'v, 'tcx> {
         }
         Ok(())
     }
+    fn encode_constant_binary_op(
+        &mut self,
+        op: vir_mid::BinaryOpKind,
+        argument_type: &vir_mid::Type,
+        left: vir_low::Expression,
+        right: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        match op {
+            vir_mid::BinaryOpKind::BitAnd
+            | vir_mid::BinaryOpKind::BitOr
+            | vir_mid::BinaryOpKind::BitXor
+            | vir_mid::BinaryOpKind::Shl
+            | vir_mid::BinaryOpKind::Shr => {
+                self.encode_bitwise_operation(op, argument_type, left, right)
+            }
+            _ => Ok(vir_low::Expression::binary_op_no_pos(
+                op.to_snapshot(self)?,
+                left,
+                right,
+            )),
+        }
+    }
 }
 
 pub(in super::super) trait TypesInterface {
@@ -355,7 +387,6 @@ impl<'p, 'v: 'p, 'tcx: 'v> TypesInterface for Lowerer<'p, 'v, 'tcx> {
                 vars! { argument: {snapshot_type.clone()} },
             )?;
             // Simplification axioms.
-            let op = op.to_snapshot(self)?;
             let simplification = match argument_type {
                 vir_mid::Type::Bool => {
                     assert_eq!(op, vir_low::UnaryOpKind::Not);
@@ -427,7 +458,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> TypesInterface for Lowerer<'p, 'v, 'tcx> {
             };
             if let Some(constant_type) = constant_type {
                 var_decls! { left: {constant_type.clone()}, right: {constant_type} };
-                let result = vir_low::Expression::binary_op_no_pos(op, expr! {left}, expr! {right});
+                let result =
+                    self.encode_constant_binary_op(op, argument_type, expr! {left}, expr! {right})?;
                 self.declare_simplification_axiom(
                     result_type,
                     &variant_name,
@@ -446,13 +478,19 @@ impl<'p, 'v: 'p, 'tcx: 'v> TypesInterface for Lowerer<'p, 'v, 'tcx> {
                     right.clone().into(),
                     Default::default(),
                 )?;
+                let evaluation_result = self.encode_constant_binary_op(
+                    op,
+                    argument_type,
+                    destructor_left,
+                    destructor_right,
+                )?;
                 self.declare_evaluation_axiom(
                     result_type,
                     &variant_name,
                     vec![left, right],
-                    vir_low::Expression::binary_op_no_pos(op, destructor_left, destructor_right),
+                    evaluation_result,
                 )?;
-            } else if op == vir_low::BinaryOpKind::EqCmp {
+            } else if op == vir_mid::BinaryOpKind::EqCmp {
                 // FIXME: For now, we treat Rust's == as bit equality.
                 var_decls! { left: {snapshot_type.clone()}, right: {snapshot_type} };
                 let domain_name = self.encode_snapshot_domain_name(&vir_mid::Type::Bool)?;
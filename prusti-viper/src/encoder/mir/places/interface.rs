@@ -4,7 +4,10 @@ use crate::{
             EncodingResult, ErrorCtxt, SpannedEncodingError, SpannedEncodingResult, WithSpan,
         },
         high::pure_functions::HighPureFunctionEncoderInterface,
-        mir::{constants::ConstantsEncoderInterface, types::MirTypeEncoderInterface},
+        mir::{
+            constants::ConstantsEncoderInterface,
+            types::{encode_box_field, MirTypeEncoderInterface},
+        },
     },
     error_internal, error_unsupported,
 };
@@ -175,7 +178,18 @@ impl<'v, 'tcx: 'v> PlacesEncoderInterface<'tcx> for super::super::super::Encoder
                 .encode_place_type_high(mir_type)
                 .with_span(declaration_span)?;
             expr = match element {
-                mir::ProjectionElem::Deref => vir_high::Expression::deref_no_pos(expr, ty),
+                mir::ProjectionElem::Deref => {
+                    let parent_mir_type = mir::PlaceRef {
+                        local: place.local,
+                        projection: &place.projection[..i],
+                    }
+                    .ty(mir, self.env().tcx());
+                    if parent_mir_type.ty.is_box() {
+                        expr.field_no_pos(encode_box_field(ty))
+                    } else {
+                        vir_high::Expression::deref_no_pos(expr, ty)
+                    }
+                }
                 mir::ProjectionElem::Field(field, _) => {
                     let parent_mir_type = {
                         let prev_place_ref = mir::PlaceRef {
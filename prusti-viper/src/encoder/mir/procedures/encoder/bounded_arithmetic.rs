@@ -0,0 +1,357 @@
+//! A conservative analysis that finds checked arithmetic operations that
+//! provably do not overflow because they are guarded by a comparison.
+//!
+//! The analysis recognises the counted loop pattern:
+//!
+//! ```ignore
+//! while i < n {
+//!     ...
+//!     i += 1;
+//! }
+//! ```
+//!
+//! If `i < n` held when the guard was last evaluated and `i` was not modified
+//! since then, `i + 1` is at most `n`, which fits the type of `i`. Similarly,
+//! `i - 1` cannot overflow when guarded by `i > n`.
+
+use prusti_rustc_interface::{
+    data_structures::graph::WithSuccessors,
+    middle::{mir, ty},
+};
+use rustc_hash::FxHashSet;
+
+/// Which bound a guard establishes for a variable.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Bound {
+    /// The variable is strictly smaller than some value of the same type.
+    Upper,
+    /// The variable is strictly larger than some value of the same type.
+    Lower,
+}
+
+/// Returns the blocks whose `Assert` terminator checks for an overflow of an
+/// operation that provably does not overflow.
+pub(super) fn find_non_overflowing_checks<'tcx>(
+    tcx: ty::TyCtxt<'tcx>,
+    body: &mir::Body<'tcx>,
+) -> FxHashSet<mir::BasicBlock> {
+    let mut blocks = FxHashSet::default();
+    let borrowed = mutably_borrowed_locals(body);
+    for (bb, data) in body.basic_blocks.iter_enumerated() {
+        let Some(mir::Terminator {
+            kind:
+                mir::TerminatorKind::Assert {
+                    cond,
+                    expected: false,
+                    msg: box mir::AssertKind::Overflow(..),
+                    ..
+                },
+            ..
+        }) = &data.terminator
+        else {
+            continue;
+        };
+        let Some(flag_place) = cond.place() else {
+            continue;
+        };
+        let Some((statement_index, op, operand)) = find_checked_operation(tcx, data, flag_place)
+        else {
+            continue;
+        };
+        let Some(variable) = resolve_local(data, statement_index, operand) else {
+            continue;
+        };
+        if borrowed.contains(&variable) || !body.local_decls[variable].ty.is_integral() {
+            continue;
+        }
+        let required_bound = match op {
+            mir::BinOp::Add => Bound::Upper,
+            mir::BinOp::Sub => Bound::Lower,
+            _ => unreachable!(),
+        };
+        let location = mir::Location {
+            block: bb,
+            statement_index,
+        };
+        if is_guarded(body, location, variable, required_bound) {
+            blocks.insert(bb);
+        }
+    }
+    blocks
+}
+
+/// Finds the statement computing the overflow flag checked by the terminator
+/// of the block. Only additions of one to a local and subtractions of one
+/// from a local are considered. Returns the statement index, the operation,
+/// and the operand that is not the constant.
+fn find_checked_operation<'a, 'tcx>(
+    tcx: ty::TyCtxt<'tcx>,
+    data: &'a mir::BasicBlockData<'tcx>,
+    flag_place: mir::Place<'tcx>,
+) -> Option<(usize, mir::BinOp, &'a mir::Operand<'tcx>)> {
+    let [mir::ProjectionElem::Field(field, _)] = flag_place.projection[..] else {
+        return None;
+    };
+    if field.index() != 1 {
+        return None;
+    }
+    let (statement_index, statement) = data
+        .statements
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, statement)| assigns_local(statement, flag_place.local))?;
+    let mir::StatementKind::Assign(box (
+        target,
+        mir::Rvalue::CheckedBinaryOp(op, box (left, right)),
+    )) = &statement.kind
+    else {
+        return None;
+    };
+    if target.as_local() != Some(flag_place.local) {
+        return None;
+    }
+    match op {
+        mir::BinOp::Add if is_one(tcx, right) => Some((statement_index, *op, left)),
+        mir::BinOp::Add if is_one(tcx, left) => Some((statement_index, *op, right)),
+        mir::BinOp::Sub if is_one(tcx, right) => Some((statement_index, *op, left)),
+        _ => None,
+    }
+}
+
+/// Whether the operand is the integer constant one.
+fn is_one<'tcx>(tcx: ty::TyCtxt<'tcx>, operand: &mir::Operand<'tcx>) -> bool {
+    let mir::Operand::Constant(constant) = operand else {
+        return false;
+    };
+    let ty = constant.ty();
+    if !ty.is_integral() {
+        return false;
+    }
+    constant
+        .literal
+        .try_eval_bits(tcx, ty::ParamEnv::reveal_all(), ty)
+        == Some(1)
+}
+
+/// Resolves the operand used at the given statement to the local whose value
+/// it has, following copies and moves between locals within the block.
+fn resolve_local(
+    data: &mir::BasicBlockData<'_>,
+    statement_index: usize,
+    operand: &mir::Operand<'_>,
+) -> Option<mir::Local> {
+    let mut local = operand.place()?.as_local()?;
+    let mut index = statement_index;
+    while let Some((copy_index, statement)) = data.statements[..index]
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, statement)| assigns_local(statement, local))
+    {
+        let mir::StatementKind::Assign(box (_, mir::Rvalue::Use(source))) = &statement.kind else {
+            return None;
+        };
+        let source = source.place()?.as_local()?;
+        // The copied value must still be the value of the source when the
+        // operand is used.
+        if data.statements[copy_index + 1..statement_index]
+            .iter()
+            .any(|statement| assigns_local(statement, source))
+        {
+            return None;
+        }
+        local = source;
+        index = copy_index;
+    }
+    Some(local)
+}
+
+/// Whether the guard that was evaluated last before reaching the location
+/// establishes the required bound for `variable` and `variable` was not
+/// modified since then.
+fn is_guarded(
+    body: &mir::Body<'_>,
+    location: mir::Location,
+    variable: mir::Local,
+    required_bound: Bound,
+) -> bool {
+    let dominators = body.basic_blocks.dominators();
+    let predecessors = body.basic_blocks.predecessors();
+    let mut candidate = location.block;
+    // Walk up the dominator tree looking for a block entered only when a
+    // guard on `variable` held.
+    while let Some(guard_block) = dominators.immediate_dominator(candidate) {
+        if predecessors[candidate].as_slice() == [guard_block]
+            && guard_bound(body, guard_block, candidate, variable) == Some(required_bound)
+            && is_unmodified_since_guard(body, guard_block, candidate, location, variable)
+        {
+            return true;
+        }
+        candidate = guard_block;
+    }
+    false
+}
+
+/// If the terminator of `guard_block` is a switch on a comparison involving
+/// `variable` and `taken` is entered only when the comparison was true,
+/// returns the bound the comparison establishes for `variable`.
+fn guard_bound(
+    body: &mir::Body<'_>,
+    guard_block: mir::BasicBlock,
+    taken: mir::BasicBlock,
+    variable: mir::Local,
+) -> Option<Bound> {
+    let data = &body[guard_block];
+    let mir::TerminatorKind::SwitchInt { discr, targets } = &data.terminator().kind else {
+        return None;
+    };
+    let values: Vec<_> = targets.iter().collect();
+    let [(0, false_target)] = values[..] else {
+        return None;
+    };
+    if targets.otherwise() != taken || false_target == taken {
+        return None;
+    }
+    let condition = discr.place()?.as_local()?;
+    let (statement_index, statement) = data
+        .statements
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, statement)| assigns_local(statement, condition))?;
+    let mir::StatementKind::Assign(box (_, mir::Rvalue::BinaryOp(op, box (left, right)))) =
+        &statement.kind
+    else {
+        return None;
+    };
+    let unmodified_until_switch = |local| {
+        !data.statements[statement_index + 1..]
+            .iter()
+            .any(|statement| assigns_local(statement, local))
+    };
+    let left = resolve_local(data, statement_index, left).filter(|&l| unmodified_until_switch(l));
+    let right = resolve_local(data, statement_index, right).filter(|&l| unmodified_until_switch(l));
+    match op {
+        mir::BinOp::Lt if left == Some(variable) => Some(Bound::Upper),
+        mir::BinOp::Lt if right == Some(variable) => Some(Bound::Lower),
+        mir::BinOp::Gt if left == Some(variable) => Some(Bound::Lower),
+        mir::BinOp::Gt if right == Some(variable) => Some(Bound::Upper),
+        _ => None,
+    }
+}
+
+/// Whether `variable` is not modified on any path from entering `taken`
+/// (which has `guard_block` as its only predecessor) to `location` that does
+/// not go through `guard_block` again. Also checks that `location` cannot be
+/// reached from `guard_block` through its other successor.
+fn is_unmodified_since_guard(
+    body: &mir::Body<'_>,
+    guard_block: mir::BasicBlock,
+    taken: mir::BasicBlock,
+    location: mir::Location,
+    variable: mir::Local,
+) -> bool {
+    let target = location.block;
+    let forward = reachable_avoiding(guard_block, taken, |bb| {
+        body.basic_blocks.successors(bb).collect()
+    });
+    let predecessors = body.basic_blocks.predecessors();
+    let backward = reachable_avoiding(guard_block, target, |bb| {
+        predecessors[bb].iter().copied().collect()
+    });
+    for successor in body.basic_blocks.successors(guard_block) {
+        if successor != taken
+            && reachable_avoiding(guard_block, successor, |bb| {
+                body.basic_blocks.successors(bb).collect()
+            })
+            .contains(&target)
+        {
+            return false;
+        }
+    }
+    // Whether `target` can be executed again without evaluating the guard.
+    let target_in_cycle = body
+        .basic_blocks
+        .successors(target)
+        .any(|successor| backward.contains(&successor) && forward.contains(&successor));
+    for bb in forward.intersection(&backward) {
+        let data = &body[*bb];
+        let statements = if *bb == target && !target_in_cycle {
+            &data.statements[..location.statement_index]
+        } else {
+            if terminator_assigns_local(data.terminator(), variable) {
+                return false;
+            }
+            &data.statements[..]
+        };
+        if statements
+            .iter()
+            .any(|statement| assigns_local(statement, variable))
+        {
+            return false;
+        }
+    }
+    true
+}
+
+/// Collects the blocks reachable from `start` by following `next` without
+/// going through `avoid`.
+fn reachable_avoiding(
+    avoid: mir::BasicBlock,
+    start: mir::BasicBlock,
+    next: impl Fn(mir::BasicBlock) -> Vec<mir::BasicBlock>,
+) -> FxHashSet<mir::BasicBlock> {
+    let mut visited = FxHashSet::default();
+    let mut work_queue = vec![start];
+    while let Some(bb) = work_queue.pop() {
+        if bb == avoid || !visited.insert(bb) {
+            continue;
+        }
+        work_queue.extend(next(bb));
+    }
+    visited
+}
+
+/// Whether the statement may change the value of the local.
+fn assigns_local(statement: &mir::Statement<'_>, local: mir::Local) -> bool {
+    match &statement.kind {
+        mir::StatementKind::Assign(box (place, _))
+        | mir::StatementKind::SetDiscriminant {
+            place: box place, ..
+        }
+        | mir::StatementKind::Deinit(box place) => place.local == local,
+        mir::StatementKind::StorageLive(storage_local)
+        | mir::StatementKind::StorageDead(storage_local) => *storage_local == local,
+        _ => false,
+    }
+}
+
+/// Whether the terminator may change the value of the local.
+fn terminator_assigns_local(terminator: &mir::Terminator<'_>, local: mir::Local) -> bool {
+    match &terminator.kind {
+        mir::TerminatorKind::Call { destination, .. } => destination.local == local,
+        mir::TerminatorKind::InlineAsm { .. } | mir::TerminatorKind::Yield { .. } => true,
+        _ => false,
+    }
+}
+
+/// Locals that are mutably borrowed or whose address is taken somewhere in
+/// the body. Their value may change without an assignment to them.
+fn mutably_borrowed_locals(body: &mir::Body<'_>) -> FxHashSet<mir::Local> {
+    let mut locals = FxHashSet::default();
+    for data in body.basic_blocks.iter() {
+        for statement in &data.statements {
+            if let mir::StatementKind::Assign(box (_, rvalue)) = &statement.kind {
+                match rvalue {
+                    mir::Rvalue::Ref(_, mir::BorrowKind::Mut { .. }, place)
+                    | mir::Rvalue::AddressOf(_, place) => {
+                        locals.insert(place.local);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    locals
+}
@@ -239,6 +239,11 @@ impl<'p, 'v, 'tcx> BuiltinFuncAppEncoder<'p, 'v, 'tcx> for super::ProcedureEncod
             "prusti_contracts::Ghost::<T>::new" => {
                 make_manual_assign(self, block_builder, &mut |_, args, _| args[0].clone())?
             }
+            "std::boxed::Box::<T>::new" | "alloc::boxed::Box::<T>::new" => {
+                make_manual_assign(self, block_builder, &mut |_, args, target_ty| {
+                    vir_high::Expression::constructor_no_pos(target_ty, args)
+                })?
+            }
             "prusti_contracts::snapshot_equality" => {
                 unreachable!();
             }
@@ -58,6 +58,7 @@ use vir_crate::{
     },
 };
 
+mod bounded_arithmetic;
 mod builtin_function_encoder;
 mod elaborate_drops;
 mod ghost;
@@ -86,6 +87,11 @@ pub(super) fn encode_procedure<'v, 'tcx: 'v>(
         SpecificationBlocks::build(encoder.env().query, mir, &procedure, true);
     let initialization = compute_definitely_initialized(def_id, mir, encoder.env().tcx());
     let allocation = compute_definitely_allocated(def_id, mir);
+    let non_overflowing_checks = if config::elide_guarded_overflow_checks() {
+        self::bounded_arithmetic::find_non_overflowing_checks(tcx, mir)
+    } else {
+        Default::default()
+    };
     let lifetime_count = lifetimes.lifetime_count();
     let lifetime_token_permission = None;
     let old_lifetime_ctr: usize = 0;
@@ -109,7 +115,10 @@ pub(super) fn encode_procedure<'v, 'tcx: 'v>(
         specification_blocks,
         specification_block_encoding: Default::default(),
         loop_invariant_encoding: Default::default(),
-        check_panics: config::check_panics() && check_mode != CheckMode::CoreProof,
+        check_panics: (config::check_panics()
+            || encoder.env().query.has_prusti_attribute(def_id, "no_panic"))
+            && check_mode != CheckMode::CoreProof,
+        non_overflowing_checks,
         locals_without_explicit_allocation,
         used_locals: Default::default(),
         fresh_id_generator: 0,
@@ -145,6 +154,9 @@ struct ProcedureEncoder<'p, 'v: 'p, 'tcx: 'v> {
     /// The loop invariant to be inserted at the end of the given basic block.
     loop_invariant_encoding: BTreeMap<mir::BasicBlock, vir_high::Statement>,
     check_panics: bool,
+    /// Blocks whose overflow check is provably satisfied and is therefore
+    /// assumed instead of asserted.
+    non_overflowing_checks: FxHashSet<mir::BasicBlock>,
     /// Locals that are not explicitly allocated or deallocated with
     /// `StorageLive`/`StorageDead`. Such locals are assumed to be alive through
     /// the entire body of the function.
@@ -374,6 +386,26 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok(postconditions)
     }
 
+    /// Splits the tupled-up arguments of a closure call into one expression
+    /// per argument.
+    fn untuple_closure_arguments(
+        &self,
+        tupled_arguments: vir_high::Expression,
+    ) -> Vec<vir_high::Expression> {
+        let vir_high::Type::Tuple(tuple) = tupled_arguments.get_type() else {
+            unreachable!("closure arguments are not tupled: {tupled_arguments}");
+        };
+        tuple
+            .arguments
+            .iter()
+            .enumerate()
+            .map(|(index, ty)| {
+                let field = vir_high::FieldDecl::new(format!("tuple_{index}"), index, ty.clone());
+                vir_high::Expression::field_no_pos(tupled_arguments.clone(), field)
+            })
+            .collect()
+    }
+
     fn encode_functional_specifications(
         &mut self,
     ) -> SpannedEncodingResult<(Vec<vir_high::Statement>, Vec<vir_high::Statement>)> {
@@ -836,9 +868,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         operands: &IndexSlice<FieldIdx, mir::Operand<'tcx>>,
     ) -> SpannedEncodingResult<()> {
         let ty = match aggregate_kind {
-            mir::AggregateKind::Array(_) | mir::AggregateKind::Tuple => {
-                encoded_target.get_type().clone()
-            }
+            mir::AggregateKind::Array(_)
+            | mir::AggregateKind::Tuple
+            | mir::AggregateKind::Closure(_, _) => encoded_target.get_type().clone(),
             mir::AggregateKind::Adt(adt_did, variant_index, _substs, _, active_field_index) => {
                 let mut ty = encoded_target.get_type().clone();
                 let tcx = self.encoder.env().tcx();
@@ -865,7 +897,6 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 }
                 ty
             }
-            mir::AggregateKind::Closure(_, _) => unimplemented!(),
             mir::AggregateKind::Generator(_, _, _) => unimplemented!(),
         };
         let base_lifetimes = ty.get_lifetimes();
@@ -1030,6 +1061,17 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     vir_high::Statement::move_place_no_pos(encoded_target.clone(), encoded_source),
                 )?);
             }
+            mir::Operand::Copy(source) if self.is_raw_pointer_deref(*source) => {
+                // A read through a raw pointer does not move any permissions:
+                // the value is the snapshot of the pointee, which can be
+                // obtained only if the pointer is known to be valid.
+                let encoded_source = self.encode_place(*source, Some(span))?;
+                block_builder.add_statement(self.set_statement_error(
+                    location,
+                    ErrorCtxt::RawPointerDeref,
+                    vir_high::Statement::write_place_no_pos(encoded_target.clone(), encoded_source),
+                )?);
+            }
             mir::Operand::Copy(source) => {
                 let encoded_source = self.encode_place(*source, Some(span))?;
                 assert!(
@@ -1135,15 +1177,18 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             mir::BinOp::Mul => vir_high::BinaryOpKind::Mul,
             mir::BinOp::Div => vir_high::BinaryOpKind::Div,
             mir::BinOp::Rem => vir_high::BinaryOpKind::Mod,
-            // mir::BinOp::BitXor => vir_high::BinaryOpKind::BitXor,
+            mir::BinOp::BitXor if result_type == &vir_high::Type::Bool => {
+                vir_high::BinaryOpKind::NeCmp
+            }
+            mir::BinOp::BitXor => vir_high::BinaryOpKind::BitXor,
             mir::BinOp::BitAnd if result_type == &vir_high::Type::Bool => {
                 vir_high::BinaryOpKind::And
             }
-            // mir::BinOp::BitAnd => vir_high::BinaryOpKind::BitAnd,
+            mir::BinOp::BitAnd => vir_high::BinaryOpKind::BitAnd,
             mir::BinOp::BitOr if result_type == &vir_high::Type::Bool => vir_high::BinaryOpKind::Or,
-            // mir::BinOp::BitOr => vir_high::BinaryOpKind::BitOr,
-            // mir::BinOp::Shl => vir_high::BinaryOpKind::Shl,
-            // mir::BinOp::Shr => vir_high::BinaryOpKind::Shr,
+            mir::BinOp::BitOr => vir_high::BinaryOpKind::BitOr,
+            mir::BinOp::Shl => vir_high::BinaryOpKind::Shl,
+            mir::BinOp::Shr => vir_high::BinaryOpKind::Shr,
             mir::BinOp::Eq => vir_high::BinaryOpKind::EqCmp,
             mir::BinOp::Lt => vir_high::BinaryOpKind::LtCmp,
             mir::BinOp::Le => vir_high::BinaryOpKind::LeCmp,
@@ -1255,6 +1300,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 unwind,
             } => self.encode_terminator_assert(
                 block_builder,
+                location,
                 span,
                 cond,
                 *expected,
@@ -1593,6 +1639,14 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 self.def_id,
             )?);
         }
+        if self.encoder.env().query.is_closure(called_def_id) {
+            // Closure calls are wrapped around std::ops::Fn::call(), which receives
+            // two arguments: The closure instance, and the tupled-up arguments. The
+            // contract of the closure refers to the arguments one by one.
+            assert_eq!(arguments.len(), 2);
+            let tupled_arguments = arguments.pop().unwrap();
+            arguments.extend(self.untuple_closure_arguments(tupled_arguments));
+        }
         self.encode_exhale_lifetime_tokens(
             block_builder,
             &lifetimes_to_exhale_inhale,
@@ -1628,13 +1682,6 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             }
         }
 
-        if self.encoder.env().query.is_closure(called_def_id) {
-            // Closure calls are wrapped around std::ops::Fn::call(), which receives
-            // two arguments: The closure instance, and the tupled-up arguments
-            assert_eq!(args.len(), 2);
-            unimplemented!();
-        }
-
         if let Some(target_block) = target {
             let position = self.register_error(location, ErrorCtxt::ProcedureCall);
             let encoded_target_place = self
@@ -1812,6 +1859,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
     fn encode_terminator_assert(
         &mut self,
         block_builder: &mut BasicBlockBuilder,
+        location: mir::Location,
         span: Span,
         cond: &mir::Operand<'tcx>,
         expected: bool,
@@ -1841,7 +1889,15 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
 
         let target_label = self.encode_basic_block_label(target);
         block_builder.add_comment(format!("Rust assertion: {assert_msg}"));
-        if self.check_panics {
+        if self.non_overflowing_checks.contains(&location.block) {
+            block_builder.add_comment("The overflow check provably succeeds.".to_string());
+            block_builder.add_statement(self.encoder.set_statement_error_ctxt(
+                vir_high::Statement::assume_no_pos(guard.clone()),
+                span,
+                ErrorCtxt::UnexpectedAssumeOverflowCheck,
+                self.def_id,
+            )?);
+        } else if self.check_panics {
             block_builder.add_statement(self.encoder.set_statement_error_ctxt(
                 vir_high::Statement::assert_no_pos(guard.clone()),
                 span,
@@ -2050,6 +2106,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
 
                 if self.check_mode != CheckMode::CoreProof {
                     encoded_statements.push(stmt);
+                    self.encoder.register_encoded_assumption(cl_def_id);
                 }
 
                 return Ok(true);
@@ -2100,33 +2157,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                         self.encoder.env().name.get_absolute_item_name(*def_id);
                     match full_called_function_name.as_ref() {
                         "prusti_contracts::prusti_set_union_active_field" => {
-                            assert_eq!(args.len(), 1);
-                            let argument_place = if let mir::Operand::Move(place) = args[0] {
-                                place
-                            } else {
-                                unreachable!()
-                            };
-                            // Find the place whose address was stored in the argument by
-                            // iterating backwards through statements.
-                            let mut statement_index = block.statements.len() - 1;
-                            let union_variant_place = loop {
-                                if let Some(statement) = block.statements.get(statement_index) {
-                                    if let mir::StatementKind::Assign(box (
-                                        target_place,
-                                        mir::Rvalue::AddressOf(_, union_variant_place),
-                                    )) = &statement.kind
-                                    {
-                                        if *target_place == argument_place {
-                                            break union_variant_place;
-                                        }
-                                    }
-                                    statement_index -= 1;
-                                } else {
-                                    unreachable!();
-                                }
-                            };
+                            let union_variant_place = self.find_address_of_argument(block, args);
                             let encoded_variant_place =
-                                self.encode_place(*union_variant_place, None)?;
+                                self.encode_place(union_variant_place, None)?;
                             let statement = self.encoder.set_statement_error_ctxt(
                                 vir_high::Statement::set_union_variant_no_pos(
                                     encoded_variant_place,
@@ -2139,6 +2172,28 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                             encoded_statements.push(statement);
                             Ok(true)
                         }
+                        "prusti_contracts::prusti_unfold_owned"
+                        | "prusti_contracts::prusti_fold_owned" => {
+                            let place = self.find_address_of_argument(block, args);
+                            self.check_hint_place(place, span)?;
+                            let encoded_place = self.encode_place(place, None)?;
+                            let statement = if full_called_function_name
+                                == "prusti_contracts::prusti_unfold_owned"
+                            {
+                                vir_high::Statement::unfold_hint_no_pos(encoded_place)
+                            } else {
+                                vir_high::Statement::fold_hint_no_pos(encoded_place)
+                            };
+                            let statement = self.encoder.set_statement_error_ctxt(
+                                statement,
+                                span,
+                                ErrorCtxt::FoldUnfoldHint,
+                                self.def_id,
+                            )?;
+                            statement.check_no_default_position();
+                            encoded_statements.push(statement);
+                            Ok(true)
+                        }
                         _ => unreachable!(),
                     }
                 } else {
@@ -2148,4 +2203,67 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             _ => unreachable!("block: {:?}", bb),
         }
     }
+
+    /// Find the place whose address was stored in the only argument of a
+    /// specification function call by iterating backwards through statements.
+    fn find_address_of_argument(
+        &self,
+        block: &mir::BasicBlockData<'tcx>,
+        args: &[mir::Operand<'tcx>],
+    ) -> mir::Place<'tcx> {
+        assert_eq!(args.len(), 1);
+        let argument_place = if let mir::Operand::Move(place) = args[0] {
+            place
+        } else {
+            unreachable!()
+        };
+        let mut statement_index = block.statements.len() - 1;
+        loop {
+            if let Some(statement) = block.statements.get(statement_index) {
+                if let mir::StatementKind::Assign(box (
+                    target_place,
+                    mir::Rvalue::AddressOf(_, place),
+                )) = &statement.kind
+                {
+                    if *target_place == argument_place {
+                        return *place;
+                    }
+                }
+                statement_index -= 1;
+            } else {
+                unreachable!();
+            }
+        }
+    }
+
+    /// Whether the place is a dereference of a local raw pointer.
+    fn is_raw_pointer_deref(&self, place: mir::Place<'tcx>) -> bool {
+        place.projection.len() == 1
+            && place.projection[0] == mir::ProjectionElem::Deref
+            && self.mir.local_decls[place.local].ty.is_unsafe_ptr()
+    }
+
+    /// Check that the place mentioned in a fold or unfold hint can have an
+    /// `Owned` predicate: it must be a struct that is not reached through a
+    /// raw pointer.
+    fn check_hint_place(&self, place: mir::Place<'tcx>, span: Span) -> SpannedEncodingResult<()> {
+        let tcx = self.encoder.env().tcx();
+        for (base, projection) in place.iter_projections() {
+            if projection == mir::ProjectionElem::Deref && base.ty(self.mir, tcx).ty.is_unsafe_ptr()
+            {
+                return Err(SpannedEncodingError::unsupported(
+                    "fold and unfold hints are not supported for places behind raw pointers",
+                    span,
+                ));
+            }
+        }
+        let ty = place.ty(self.mir, tcx).ty;
+        if !ty.ty_adt_def().map_or(false, |adt_def| adt_def.is_struct()) {
+            return Err(SpannedEncodingError::incorrect(
+                format!("fold and unfold hints are supported only for structs, not for `{ty}`"),
+                span,
+            ));
+        }
+        Ok(())
+    }
 }
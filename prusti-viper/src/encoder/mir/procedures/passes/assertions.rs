@@ -47,6 +47,8 @@ pub(in super::super) fn propagate_assertions_back<'v, 'tcx: 'v>(
                         | vir_high::Statement::GhostAssign(_)
                         | vir_high::Statement::LeakAll(_)
                         | vir_high::Statement::SetUnionVariant(_)
+                        | vir_high::Statement::UnfoldHint(_)
+                        | vir_high::Statement::FoldHint(_)
                         | vir_high::Statement::NewLft(_)
                         | vir_high::Statement::EndLft(_)
                         | vir_high::Statement::DeadLifetime(_)
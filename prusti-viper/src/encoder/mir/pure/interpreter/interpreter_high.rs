@@ -22,6 +22,7 @@ use crate::encoder::{
             SpecificationEncoderInterface,
         },
         specifications::SpecificationsInterface,
+        type_layouts::MirTypeLayoutsEncoderInterface,
         types::MirTypeEncoderInterface,
     },
     mir_encoder::{MirEncoder, PRECONDITION_LABEL},
@@ -677,6 +678,36 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
                 );
                 subst_with(encoded_rhs)
             }
+            "prusti_contracts::snapshot_equality_reflexivity" => {
+                builtin((SnapshotEqualityReflexivity, Type::Bool))
+            }
+            "prusti_contracts::snapshot_equality_congruence" => {
+                assert_eq!(type_arguments.len(), 1);
+                if !matches!(
+                    type_arguments[0],
+                    Type::Bool | Type::Int(_) | Type::Pointer(_) | Type::Struct(_) | Type::Tuple(_)
+                ) {
+                    return Err(SpannedEncodingError::unsupported(
+                        format!(
+                            "snapshot equality congruence lemmas are not supported for `{}`",
+                            type_arguments[0]
+                        ),
+                        span,
+                    ));
+                }
+                builtin((SnapshotEqualityCongruence, Type::Bool))
+            }
+            "prusti_contracts::ptr_valid" => {
+                let position = encoded_args[0].position();
+                let encoded_rhs = vir_high::Expression::builtin_func_app(
+                    vir_high::BuiltinFunc::PointerValid,
+                    Vec::new(),
+                    encoded_args.into(),
+                    vir_high::Type::Bool,
+                    position,
+                );
+                subst_with(encoded_rhs)
+            }
             "prusti_contracts::before_expiry" => {
                 // self.encode_call_before_expiry()?
                 unimplemented!();
@@ -699,6 +730,20 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
                 );
                 subst_with(encoded_rhs)
             }
+            "std::mem::size_of" | "core::mem::size_of" => {
+                assert!(encoded_args.is_empty());
+                let encoded_rhs = self
+                    .encoder
+                    .encode_type_size_expression(substs.type_at(0))?;
+                subst_with(encoded_rhs)
+            }
+            "std::mem::align_of" | "core::mem::align_of" => {
+                assert!(encoded_args.is_empty());
+                let encoded_rhs = self
+                    .encoder
+                    .encode_type_align_expression(substs.type_at(0))?;
+                subst_with(encoded_rhs)
+            }
             "core::slice::<impl [T]>::len" => {
                 assert_eq!(encoded_args.len(), 1);
                 self.encode_call_len(
@@ -242,6 +242,9 @@ pub(super) fn encode_quantifier<'tcx>(
         parent_def_id,
         body_substs,
     )?;
+    if encoded_trigger_sets.is_empty() {
+        encoded_trigger_sets = infer_trigger_sets(&encoded_qvars, &encoded_body);
+    }
 
     // replace qvars with a nicer name based on quantifier depth to ensure that
     // quantifiers remain stable for caching
@@ -323,6 +326,102 @@ fn find_quantifier_depth(expr: &vir_crate::polymorphic::Expr) -> usize {
     checker.max_depth
 }
 
+/// Infers the trigger sets of a quantifier without user-provided triggers
+/// from the sequence lookups `a[i]` in its body. Each lookup that mentions all
+/// quantified variables becomes a trigger set on its own; otherwise, all
+/// lookups together form a single trigger set if they mention all quantified
+/// variables. Lookups whose arguments cannot appear in a trigger (for example,
+/// `a[i + 1]`) are ignored. If no trigger set can be inferred, the choice of
+/// triggers is left to the verifier.
+fn infer_trigger_sets(
+    qvars: &[vir_crate::polymorphic::LocalVar],
+    body: &vir_crate::polymorphic::Expr,
+) -> Vec<vir_crate::polymorphic::Trigger> {
+    use vir_crate::polymorphic::ExprWalker;
+    struct LookupCollector<'a> {
+        qvars: &'a [vir_crate::polymorphic::Expr],
+        lookups: Vec<vir_crate::polymorphic::Expr>,
+    }
+    impl<'a> ExprWalker for LookupCollector<'a> {
+        fn walk_func_app(&mut self, expr: &vir_crate::polymorphic::FuncApp) {
+            // The name of `BuiltinFunctionKind::{ArrayLookupPure, SliceLookupPure}`.
+            if expr.function_name == "lookup_pure"
+                && expr.arguments.iter().all(is_trigger_term)
+                && self
+                    .qvars
+                    .iter()
+                    .any(|qvar| expr.arguments.iter().any(|argument| argument.find(qvar)))
+            {
+                let lookup = vir_crate::polymorphic::Expr::FuncApp(expr.clone());
+                if !self.lookups.contains(&lookup) {
+                    self.lookups.push(lookup);
+                }
+            }
+            for argument in &expr.arguments {
+                self.walk(argument);
+            }
+        }
+        // Lookups that mention variables bound inside the body cannot be
+        // triggers of the quantifier, and lookups in an old state would be
+        // triggers on the current state.
+        fn walk_forall(&mut self, _expr: &vir_crate::polymorphic::ForAll) {}
+        fn walk_exists(&mut self, _expr: &vir_crate::polymorphic::Exists) {}
+        fn walk_let_expr(&mut self, expr: &vir_crate::polymorphic::LetExpr) {
+            self.walk(&expr.def);
+        }
+        fn walk_labelled_old(&mut self, _expr: &vir_crate::polymorphic::LabelledOld) {}
+    }
+    fn is_trigger_term(expr: &vir_crate::polymorphic::Expr) -> bool {
+        match expr {
+            vir_crate::polymorphic::Expr::Local(..) | vir_crate::polymorphic::Expr::Const(..) => {
+                true
+            }
+            vir_crate::polymorphic::Expr::Field(vir_crate::polymorphic::FieldExpr {
+                base, ..
+            })
+            | vir_crate::polymorphic::Expr::SnapApp(vir_crate::polymorphic::SnapApp {
+                base, ..
+            }) => is_trigger_term(base),
+            vir_crate::polymorphic::Expr::FuncApp(vir_crate::polymorphic::FuncApp {
+                arguments,
+                ..
+            })
+            | vir_crate::polymorphic::Expr::DomainFuncApp(
+                vir_crate::polymorphic::DomainFuncApp { arguments, .. },
+            ) => arguments.iter().all(is_trigger_term),
+            _ => false,
+        }
+    }
+
+    let qvars = qvars
+        .iter()
+        .map(|var| var.clone().into())
+        .collect::<Vec<vir_crate::polymorphic::Expr>>();
+    let mut collector = LookupCollector {
+        qvars: &qvars,
+        lookups: vec![],
+    };
+    collector.walk(body);
+    let mentions_all_qvars = |terms: &[vir_crate::polymorphic::Expr]| {
+        qvars
+            .iter()
+            .all(|qvar| terms.iter().any(|term| term.find(qvar)))
+    };
+    let single_lookup_sets = collector
+        .lookups
+        .iter()
+        .filter(|lookup| mentions_all_qvars(std::slice::from_ref(lookup)))
+        .map(|lookup| vir_crate::polymorphic::Trigger::new(vec![lookup.clone()]))
+        .collect::<Vec<_>>();
+    if !single_lookup_sets.is_empty() {
+        single_lookup_sets
+    } else if !collector.lookups.is_empty() && mentions_all_qvars(&collector.lookups) {
+        vec![vir_crate::polymorphic::Trigger::new(collector.lookups)]
+    } else {
+        vec![]
+    }
+}
+
 fn check_trigger(trigger: &vir_crate::polymorphic::Expr) -> EncodingResult<()> {
     use vir_crate::polymorphic::FallibleExprFolder;
     struct TriggerChecker {}
@@ -7,6 +7,10 @@ pub(crate) trait MirTypeLayoutsEncoderInterface<'tcx> {
         &self,
         ty: ty::Ty<'tcx>,
     ) -> SpannedEncodingResult<vir_high::Expression>;
+    fn encode_type_align_expression(
+        &self,
+        ty: ty::Ty<'tcx>,
+    ) -> SpannedEncodingResult<vir_high::Expression>;
     fn encode_type_padding_size_expression(
         &self,
         ty: ty::Ty<'tcx>,
@@ -30,6 +34,22 @@ impl<'v, 'tcx: 'v> MirTypeLayoutsEncoderInterface<'tcx> for super::super::super:
         Ok(function_call)
     }
 
+    fn encode_type_align_expression(
+        &self,
+        ty: ty::Ty<'tcx>,
+    ) -> SpannedEncodingResult<vir_high::Expression> {
+        let encoded_ty = self.encode_type_high(ty)?.erase_lifetimes();
+        let usize = vir_high::Type::Int(vir_high::ty::Int::Usize);
+        let const_arguments = encoded_ty.get_const_arguments();
+        let function_call = vir_high::Expression::builtin_func_app_no_pos(
+            vir_high::BuiltinFunc::Align,
+            vec![encoded_ty],
+            const_arguments,
+            usize,
+        );
+        Ok(function_call)
+    }
+
     fn encode_type_padding_size_expression(
         &self,
         ty: ty::Ty<'tcx>,
@@ -4,7 +4,10 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use super::{helpers::compute_discriminant_values, interface::MirTypeEncoderInterface};
+use super::{
+    helpers::{compute_discriminant_values, encode_box_field},
+    interface::MirTypeEncoderInterface,
+};
 use crate::encoder::{
     errors::{EncodingResult, SpannedEncodingError, SpannedEncodingResult, WithSpan},
     mir::{
@@ -219,9 +222,9 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
 
             ty::TyKind::Closure(def_id, _substs) => vir::Type::closure(
                 encode_closure_name(self.encoder, *def_id),
-                // FIXME: We are currently ignoring type arguments and lifetimes.
+                // FIXME: We are currently ignoring type arguments.
                 // self.encode_substs(substs),
-                // lifetimes,
+                lifetimes,
             ),
 
             ty::TyKind::FnDef(def_id, _substs) => vir::Type::function_def(
@@ -367,7 +370,7 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
             ty::TyKind::Float(_) => {
                 let mut lower_bound = None;
                 let mut upper_bound = None;
-                if config::check_overflows() {
+                if !config::ieee_floats() {
                     if let Some((lower, upper)) = self.get_float_bounds() {
                         lower_bound = Some(Box::new(lower));
                         upper_bound = Some(Box::new(upper));
@@ -478,7 +481,8 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                     .filter_map(|ty| self.encoder.encode_type_high(ty).ok())
                     .collect();
                 let name = encode_closure_name(self.encoder, *def_id);
-                vir::TypeDecl::closure(name, arguments)
+                let lifetimes = self.encoder.get_lifetimes_from_type_high(self.ty)?;
+                vir::TypeDecl::closure(name, lifetimes, arguments)
             }
             ty::TyKind::Array(elem_ty, _size) => {
                 let lifetimes = self.encoder.get_lifetimes_from_type_high(self.ty)?;
@@ -735,7 +739,7 @@ pub(super) fn encode_adt_def<'v, 'tcx>(
         debug!("ADT {:?} is a box", adt_def);
         assert!(variant_index.is_none());
         let boxed_ty = encoder.encode_type_high(substs.type_at(0))?;
-        let field = vir::FieldDecl::new("val_ref", 0usize, boxed_ty);
+        let field = encode_box_field(boxed_ty);
         Ok(vir::TypeDecl::struct_(
             encode_box_name(),
             lifetimes,
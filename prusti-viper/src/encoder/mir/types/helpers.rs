@@ -7,6 +7,12 @@ use vir_crate::{
     polymorphic::{self as vir},
 };
 
+/// The field of a box that stores the boxed value. A box owns its content, so
+/// the content is encoded as if it was stored inline.
+pub(crate) fn encode_box_field(boxed_type: vir_high::Type) -> vir_high::FieldDecl {
+    vir_high::FieldDecl::new("val_ref", 0usize, boxed_type)
+}
+
 /// Compute the values that a discriminant can take.
 pub(crate) fn compute_discriminant_values<'tcx>(
     adt_def: ty::AdtDef<'tcx>,
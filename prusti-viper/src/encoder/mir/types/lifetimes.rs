@@ -51,8 +51,13 @@ pub(super) fn extract_lifetimes_from_type<'tcx>(
         | ty::TyKind::Str
         | ty::TyKind::Error(_)
         | ty::TyKind::Never => {}
+        ty::TyKind::Closure(_, args) => {
+            // Only the captured references are part of the closure value.
+            for upvar_ty in args.as_closure().upvar_tys() {
+                extract_lifetimes_from_type(type_encoder, upvar_ty, lifetimes)?;
+            }
+        }
         ty::TyKind::Adt(_, args)
-        | ty::TyKind::Closure(_, args)
         | ty::TyKind::Alias(_, ty::AliasTy { args, .. })
         | ty::TyKind::FnDef(_, args) => {
             extract_lifetimes_from_substs(type_encoder, args, lifetimes)?
@@ -7,7 +7,7 @@ mod interface;
 mod lifetimes;
 
 pub(crate) use self::{
-    helpers::compute_discriminant_bounds,
+    helpers::{compute_discriminant_bounds, encode_box_field},
     interface::{MirTypeEncoderInterface, MirTypeEncoderState},
 };
 
@@ -4,7 +4,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-pub use self::encoder::Encoder;
+pub use self::{encoder::Encoder, middle::core_proof::TRANSFORMATION_PASSES};
 
 mod builtin_encoder;
 #[allow(clippy::module_inception)]
@@ -17,7 +17,7 @@ mod loop_encoder;
 mod mir_encoder;
 mod mir_successor;
 mod name_interner;
-mod places;
+pub mod places;
 mod procedure_encoder;
 mod stub_function_encoder;
 mod stub_procedure_encoder;
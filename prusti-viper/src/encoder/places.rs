@@ -94,6 +94,69 @@ impl<'tcx> LocalVariableManager<'tcx> {
     pub fn iter(&self) -> impl iter::Iterator<Item = Local> + 'tcx {
         self.variables.indices()
     }
+
+    /// Returns `false` only if the two places are known to denote disjoint
+    /// memory. Places rooted in different locals are disjoint unless one of
+    /// them goes through a dereference; places with the same root are
+    /// disjoint if they diverge at different fields of a struct-like type or
+    /// at different constant indices counted from the same end.
+    pub fn may_alias(&self, a: &Place<'tcx>, b: &Place<'tcx>) -> bool {
+        let (root_a, projection_a) = a.root_and_projection();
+        let (root_b, projection_b) = b.root_and_projection();
+        if root_a != root_b {
+            let has_deref = |projection: &[mir::PlaceElem<'tcx>]| {
+                projection
+                    .iter()
+                    .any(|elem| matches!(elem, mir::ProjectionElem::Deref))
+            };
+            return has_deref(projection_a) || has_deref(projection_b);
+        }
+        // The type of the common prefix, if we managed to track it.
+        let mut ty = Some(self.get_type(root_a));
+        for (elem_a, elem_b) in projection_a.iter().zip(projection_b) {
+            match (elem_a, elem_b) {
+                (mir::ProjectionElem::Deref, mir::ProjectionElem::Deref) => {
+                    ty = ty.and_then(|ty| ty.builtin_deref(true)).map(|ty| ty.ty);
+                }
+                (
+                    mir::ProjectionElem::Field(field_a, field_ty),
+                    mir::ProjectionElem::Field(field_b, _),
+                ) => {
+                    if field_a != field_b {
+                        // The fields of a union overlap.
+                        return !matches!(ty, Some(ty) if !ty.is_union());
+                    }
+                    ty = Some(*field_ty);
+                }
+                (
+                    mir::ProjectionElem::Downcast(_, variant_a),
+                    mir::ProjectionElem::Downcast(_, variant_b),
+                ) if variant_a == variant_b => {}
+                (
+                    mir::ProjectionElem::ConstantIndex {
+                        offset: offset_a,
+                        from_end: from_end_a,
+                        ..
+                    },
+                    mir::ProjectionElem::ConstantIndex {
+                        offset: offset_b,
+                        from_end: from_end_b,
+                        ..
+                    },
+                ) if from_end_a == from_end_b => {
+                    if offset_a != offset_b {
+                        return false;
+                    }
+                    ty = ty.and_then(|ty| ty.builtin_index());
+                }
+                // Equal `Index` projections may still index with different
+                // values.
+                _ => return true,
+            }
+        }
+        // One place is a prefix of the other.
+        true
+    }
 }
 
 /// This place is a generalisation of mir::Place.
@@ -120,6 +183,16 @@ impl<'a, 'tcx: 'a> From<&'a mir::Place<'tcx>> for Place<'tcx> {
 }
 
 impl<'tcx> Place<'tcx> {
+    fn root_and_projection(&self) -> (Local, &[mir::PlaceElem<'tcx>]) {
+        match self {
+            Place::NormalPlace(place) => (place.local.into(), &place.projection[..]),
+            Place::SubstitutedPlace {
+                substituted_root,
+                place,
+            } => (*substituted_root, &place.projection[..]),
+        }
+    }
+
     pub fn is_root(&self, local: Local) -> bool {
         // fn check_if_root(place: mir::Place, local: Local) -> bool {
         //     match place {
@@ -178,7 +178,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             loop_encoder: LoopEncoder::new(procedure, tcx),
             auxiliary_local_vars: FxHashMap::default(),
             mir_encoder,
-            check_panics: config::check_panics(),
+            check_panics: config::check_panics()
+                || encoder
+                    .env()
+                    .query
+                    .has_prusti_attribute(proc_def_id, "no_panic"),
             check_foldunfold_state: config::check_foldunfold_state(),
             polonius_info: None,
             procedure_contract: None,
@@ -257,6 +261,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 let assume_stmt = vir::Stmt::Inhale(vir::Inhale { expr: assume_expr });
 
                 encoded_statements.push(assume_stmt);
+                self.encoder.register_encoded_assumption(cl_def_id);
 
                 return Ok(true);
             }
@@ -809,6 +814,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         match &bb.successor {
             Successor::Undefined => (),
             Successor::Return => (),
+            Successor::IntentionalUnreachable(_) => (),
             Successor::Goto(cbi) => preconds.extend(self.block_preconditions(cbi)),
             Successor::GotoSwitch(succs, def) => {
                 preconds.extend(
@@ -832,6 +838,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         match bb.successor.clone() {
             Successor::Undefined => (),
             Successor::Return => (),
+            Successor::IntentionalUnreachable(_) => (),
             Successor::Goto(cbi) => self.block_assert_to_assume(&cbi),
             Successor::GotoSwitch(succs, def) => {
                 for (_, succ) in succs {
@@ -7063,6 +7070,15 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 self.mir.span,
             ));
         }
+        if config::check_unassigned_returns() {
+            for name in self.cfg_method.unassigned_returns() {
+                PrustiError::warning(
+                    format!("the formal return `{name}` might not be assigned on some path"),
+                    self.mir.span.into(),
+                )
+                .emit(&self.encoder.env().diagnostic);
+            }
+        }
         Ok(())
     }
 
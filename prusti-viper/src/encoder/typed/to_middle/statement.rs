@@ -79,6 +79,20 @@ impl<'v, 'tcx> TypedToMiddleStatementLowerer for crate::encoder::Encoder<'v, 'tc
         unreachable!("loop invariant statement cannot be lowered")
     }
 
+    fn typed_to_middle_statement_statement_unfold_hint(
+        &self,
+        _: vir_typed::UnfoldHint,
+    ) -> Result<vir_mid::Statement, Self::Error> {
+        unreachable!("unfold hint statement cannot be lowered")
+    }
+
+    fn typed_to_middle_statement_statement_fold_hint(
+        &self,
+        _: vir_typed::FoldHint,
+    ) -> Result<vir_mid::Statement, Self::Error> {
+        unreachable!("fold hint statement cannot be lowered")
+    }
+
     fn typed_to_middle_statement_assert(
         &self,
         statement: vir_typed::Assert,
@@ -23,5 +23,7 @@
 #![allow(clippy::needless_pass_by_ref_mut)] // see https://github.com/rust-lang/rust-clippy/issues/11179
 
 pub mod encoder;
+#[cfg(feature = "program_transformer")]
+pub mod program_transformer;
 mod utils;
 pub mod verifier;
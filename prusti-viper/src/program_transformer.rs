@@ -0,0 +1,32 @@
+//! An extension point for external tools that need to post-process the Viper
+//! programs, for example to instrument them for profiling, before they are
+//! sent to the backend.
+//!
+//! The module is only compiled with the `program_transformer` feature, so that
+//! the verification path is unchanged when no transformer can be registered.
+
+use once_cell::sync::OnceCell;
+use prusti_common::vir::program::Program;
+
+pub type ProgramTransformer = Box<dyn Fn(Program) -> Program + Send + Sync>;
+
+static PROGRAM_TRANSFORMER: OnceCell<ProgramTransformer> = OnceCell::new();
+
+/// Register the transformer that is applied to every program right before it
+/// is verified. Only one transformer can be registered; if one already was,
+/// the given transformer is returned back as the error.
+pub fn register_program_transformer<F>(transformer: F) -> Result<(), ProgramTransformer>
+where
+    F: Fn(Program) -> Program + Send + Sync + 'static,
+{
+    PROGRAM_TRANSFORMER.set(Box::new(transformer))
+}
+
+/// Apply the registered transformer to `program`. Without a registered
+/// transformer, the program is returned unchanged.
+pub(crate) fn transform_program(program: Program) -> Program {
+    match PROGRAM_TRANSFORMER.get() {
+        Some(transformer) => transformer(program),
+        None => program,
+    }
+}
@@ -17,7 +17,9 @@ use prusti_common::{
     Stopwatch,
 };
 use prusti_interface::{
-    data::{VerificationResult, VerificationTask},
+    data::{
+        MethodVerificationResult, VerificationDiagnostic, VerificationResult, VerificationTask,
+    },
     environment::Environment,
     specs::typed,
     PrustiError,
@@ -27,8 +29,12 @@ use prusti_server::{
     process_verification_request, spawn_server_thread, tokio::runtime::Builder, PrustiClient,
     VerificationRequest, ViperBackendConfig,
 };
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
 use viper::{self, PersistentCache, Viper};
-use vir_crate::common::check_mode::CheckMode;
+use vir_crate::{common::check_mode::CheckMode, polymorphic as vir_poly};
 
 /// A verifier is an object for verifying a single crate, potentially
 /// many times.
@@ -38,6 +44,23 @@ where
 {
     env: &'v Environment<'tcx>,
     encoder: Encoder<'v, 'tcx>,
+    /// The number of Viper programs that did not verify successfully in the
+    /// last call to `verify`.
+    failed_programs: usize,
+    /// The number of Viper programs that were encoded but not verified in the
+    /// last call to `verify`, because verification stopped at the first error.
+    skipped_programs: usize,
+    /// The number of encoding errors reported in the last call to `verify`. A
+    /// procedure with an encoding error is not verified.
+    encoding_errors: usize,
+    /// The number of trusted procedures that were skipped in the last call to
+    /// `verify`.
+    skipped_trusted_procedures: usize,
+    /// The number of `prusti_assume!` statements that were encoded in the last
+    /// call to `verify`.
+    encoded_assumptions: usize,
+    /// The result of verifying each Viper program in the last call to `verify`.
+    method_results: Vec<MethodVerificationResult>,
 }
 
 impl<'v, 'tcx> Verifier<'v, 'tcx> {
@@ -45,9 +68,50 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
         Verifier {
             env,
             encoder: Encoder::new(env, def_spec),
+            failed_programs: 0,
+            skipped_programs: 0,
+            encoding_errors: 0,
+            skipped_trusted_procedures: 0,
+            encoded_assumptions: 0,
+            method_results: Vec::new(),
         }
     }
 
+    /// The number of Viper programs that failed to verify in the last call to
+    /// `verify`. There is one program per verified method.
+    pub fn failed_programs(&self) -> usize {
+        self.failed_programs
+    }
+
+    /// The number of Viper programs that were not verified in the last call to
+    /// `verify` because `stop_on_first_error` stopped the verification.
+    pub fn skipped_programs(&self) -> usize {
+        self.skipped_programs
+    }
+
+    /// The number of encoding errors reported in the last call to `verify`.
+    pub fn encoding_errors(&self) -> usize {
+        self.encoding_errors
+    }
+
+    /// The number of trusted procedures whose bodies were not verified in the
+    /// last call to `verify`.
+    pub fn skipped_trusted_procedures(&self) -> usize {
+        self.skipped_trusted_procedures
+    }
+
+    /// The number of unchecked `prusti_assume!` statements that the
+    /// verification in the last call to `verify` relied on.
+    pub fn encoded_assumptions(&self) -> usize {
+        self.encoded_assumptions
+    }
+
+    /// The result of verifying each Viper program in the last call to
+    /// `verify`, ordered by the name of the program.
+    pub fn method_results(&self) -> &[MethodVerificationResult] {
+        &self.method_results
+    }
+
     #[tracing::instrument(name = "prusti_viper::verify", level = "info", skip(self))]
     pub fn verify(&mut self, task: &VerificationTask<'tcx>) -> VerificationResult {
         let mut stopwatch = Stopwatch::start("prusti-viper", "encoding to Viper");
@@ -75,32 +139,90 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
         self.encoder.process_encoding_queue();
 
         let encoding_errors_count = self.encoder.count_encoding_errors();
+        self.encoding_errors = encoding_errors_count;
+        self.skipped_trusted_procedures = self.encoder.count_skipped_trusted_procedures();
+        self.encoded_assumptions = self.encoder.count_encoded_assumptions();
 
-        let polymorphic_programs = self.encoder.get_viper_programs();
+        let mut polymorphic_programs = self.encoder.get_viper_programs();
+        for program in &mut polymorphic_programs {
+            for method in &mut program.methods {
+                method.insert_invariant_precondition_checks(&program.functions);
+            }
+        }
 
-        let mut programs: Vec<Program> = if config::simplify_encoding() {
+        if config::simplify_encoding() {
             stopwatch.start_next("optimizing Viper program");
             let source_file_name = self.encoder.env().name.source_file_name();
-            polymorphic_programs
+            polymorphic_programs = polymorphic_programs
                 .into_iter()
-                .map(|program| Program::Legacy(optimize_program(program, &source_file_name).into()))
-                .collect()
-        } else {
-            polymorphic_programs
-                .into_iter()
-                .map(|program| Program::Legacy(program.into()))
-                .collect()
-        };
+                .map(|program| optimize_program(program, &source_file_name))
+                .collect();
+        }
+        if config::print_cfg() {
+            // Printed to stderr so that it does not interleave with the JSON
+            // results printed to stdout.
+            for method in polymorphic_programs
+                .iter()
+                .flat_map(|program| &program.methods)
+            {
+                eprintln!("{method}");
+            }
+        }
+        let mut programs: Vec<Program> = polymorphic_programs
+            .into_iter()
+            .map(|program| Program::Legacy(program.into()))
+            .collect();
         programs.extend(self.encoder.get_core_proof_programs());
+        #[cfg(feature = "program_transformer")]
+        let programs: Vec<Program> = programs
+            .into_iter()
+            .map(crate::program_transformer::transform_program)
+            .collect();
+        let mut statistics = self.encoder.get_core_proof_statistics();
+        let program_keys: Vec<_> = programs
+            .iter()
+            .map(|program| (program.get_name().to_string(), program.get_check_mode()))
+            .collect();
 
         stopwatch.start_next("verifying Viper program");
-        let verification_results = verify_programs(self.env, programs);
+        let source_path = self.env.name.source_path();
+        let rust_program_name = source_path.file_name().unwrap().to_str().unwrap();
+        let (verification_results, solve_times): (Vec<_>, Vec<_>) =
+            verify_programs(rust_program_name, programs)
+                .into_iter()
+                .map(|(program_name, result, solve_time)| ((program_name, result), solve_time))
+                .unzip();
         stopwatch.finish();
+        self.skipped_programs = program_keys.len() - verification_results.len();
+
+        // The outcome and the errors of each verified program
+        let mut method_results: BTreeMap<String, MethodVerificationResult> = verification_results
+            .iter()
+            .map(|(method_name, result)| {
+                let outcome = match result {
+                    viper::VerificationResult::Success => VerificationResult::Success,
+                    viper::VerificationResult::Timeout => VerificationResult::Timeout,
+                    _ => VerificationResult::Failure,
+                };
+                let method_result = MethodVerificationResult {
+                    method_name: method_name.clone(),
+                    outcome,
+                    diagnostics: vec![],
+                };
+                (method_name.clone(), method_result)
+            })
+            .collect();
+
+        self.failed_programs = verification_results
+            .iter()
+            .filter(|(_, result)| !result.is_success())
+            .count();
 
         // Group verification results
         let mut verification_errors: Vec<_> = vec![];
         let mut consistency_errors: Vec<_> = vec![];
         let mut java_exceptions: Vec<_> = vec![];
+        let mut timeouts: Vec<_> = vec![];
         for (method_name, result) in verification_results.into_iter() {
             match result {
                 viper::VerificationResult::Success => {}
@@ -117,6 +239,9 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
                 viper::VerificationResult::JavaException(exception) => {
                     java_exceptions.push((method_name, exception));
                 }
+                viper::VerificationResult::Timeout => {
+                    timeouts.push(method_name);
+                }
             }
         }
 
@@ -125,27 +250,54 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
         let mut result = VerificationResult::Success;
 
         for (method, error) in consistency_errors.into_iter() {
-            PrustiError::internal(
+            let prusti_error = PrustiError::internal(
                 format!("consistency error in {method}: {error}"),
                 DUMMY_SP.into(),
-            )
-            .emit(&self.env.diagnostic);
+            );
+            self.record_diagnostic(&mut method_results, &method, &prusti_error);
+            prusti_error.emit(&self.env.diagnostic);
             result = VerificationResult::Failure;
         }
 
         for (method, exception) in java_exceptions.into_iter() {
             error!("Java exception: {}", exception.get_stack_trace());
-            PrustiError::internal(format!("in {method}: {exception}"), DUMMY_SP.into())
-                .emit(&self.env.diagnostic);
+            let prusti_error =
+                PrustiError::internal(format!("in {method}: {exception}"), DUMMY_SP.into());
+            self.record_diagnostic(&mut method_results, &method, &prusti_error);
+            prusti_error.emit(&self.env.diagnostic);
             result = VerificationResult::Failure;
         }
 
+        for method in timeouts.iter() {
+            let prusti_error = PrustiError::verification(
+                format!("verification timed out for method {method}"),
+                DUMMY_SP.into(),
+            );
+            self.record_diagnostic(&mut method_results, method, &prusti_error);
+            prusti_error.emit(&self.env.diagnostic);
+        }
+
         // Report verification errors
         let mut prusti_errors: Vec<_> = vec![];
         for (method, verification_error) in verification_errors.into_iter() {
             debug!("Verification error in {}: {:?}", method, verification_error);
             let mut prusti_error = error_manager.translate_verification_error(&verification_error);
 
+            // point out the panic sites of functions that must not panic
+            if error_manager.is_panic_error(&verification_error) {
+                if let Some(def_id) = error_manager.get_def_id(&verification_error) {
+                    if self.env.query.has_prusti_attribute(def_id, "no_panic") {
+                        prusti_error = prusti_error.add_note(
+                            format!(
+                                "this panic site violates the `#[no_panic]` attribute of `{}`",
+                                self.env.name.get_item_name(def_id)
+                            ),
+                            Some(self.env.query.get_def_span(def_id)),
+                        );
+                    }
+                }
+            }
+
             // annotate with counterexample, if requested
             if config::counterexample() {
                 if config::unsafe_core_proof() {
@@ -187,15 +339,16 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
                 }
             }
 
-            prusti_errors.push(prusti_error);
+            prusti_errors.push((method, prusti_error));
         }
-        prusti_errors.sort();
+        prusti_errors.sort_by(|(_, first), (_, second)| first.cmp(second));
 
-        for prusti_error in prusti_errors {
+        for (method, prusti_error) in prusti_errors {
             debug!("Prusti error: {:?}", prusti_error);
             if prusti_error.is_disabled() {
                 prusti_error.cancel();
             } else {
+                self.record_diagnostic(&mut method_results, &method, &prusti_error);
                 prusti_error.emit(&self.env.diagnostic);
             }
             result = VerificationResult::Failure;
@@ -205,23 +358,97 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
             result = VerificationResult::Failure;
         }
 
+        if !timeouts.is_empty() && result == VerificationResult::Success {
+            result = VerificationResult::Timeout;
+        }
+
+        self.method_results = method_results.into_values().collect();
+
+        if config::stats() {
+            // The table is printed to stderr so that it does not interleave
+            // with the JSON results printed to stdout.
+            eprintln!("method\tcheck_mode\tblocks\tpredicates\tquantifiers\tsolve_time_ms");
+            for ((method_name, check_mode), solve_time) in program_keys.into_iter().zip(solve_times)
+            {
+                // The encoding metrics are only collected for the core proof.
+                let (blocks, predicates, quantifiers) =
+                    match statistics.remove(&(method_name.clone(), check_mode)) {
+                        Some(program_statistics) => (
+                            program_statistics.blocks.to_string(),
+                            program_statistics.predicates.to_string(),
+                            program_statistics.quantifiers.to_string(),
+                        ),
+                        None => Default::default(),
+                    };
+                eprintln!(
+                    "{method_name}\t{check_mode}\t{blocks}\t{predicates}\t{quantifiers}\t{}",
+                    solve_time.as_millis()
+                );
+            }
+        }
+
         result
     }
+
+    fn record_diagnostic(
+        &self,
+        method_results: &mut BTreeMap<String, MethodVerificationResult>,
+        method: &str,
+        prusti_error: &PrustiError,
+    ) {
+        if !prusti_error.is_error() {
+            return;
+        }
+        let span = prusti_error
+            .span()
+            .primary_span()
+            .filter(|span| !span.is_dummy())
+            .map(|span| self.env.query.codemap().span_to_diagnostic_string(span));
+        if let Some(method_result) = method_results.get_mut(method) {
+            method_result.diagnostics.push(VerificationDiagnostic {
+                message: prusti_error.message().to_string(),
+                span,
+            });
+        }
+    }
+}
+
+/// Verify a trivial method that asserts `true` with the configured backend.
+/// This checks that the Viper and SMT solver installation is usable without
+/// compiling any Rust code.
+pub fn verify_selftest_program() -> viper::VerificationResult {
+    let mut method = vir_poly::CfgMethod::new("selftest".to_string(), vec![], vec![], vec![]);
+    let start = method.add_block(
+        "start",
+        vec![vir_poly::Stmt::Assert(vir_poly::Assert {
+            expr: true.into(),
+            position: vir_poly::Position::default(),
+        })],
+    );
+    method.set_successor(start, vir_poly::Successor::Return);
+    let program = vir_poly::Program {
+        name: "selftest".to_string(),
+        domains: vec![],
+        backend_types: vec![],
+        fields: vec![],
+        builtin_methods: vec![],
+        methods: vec![method],
+        functions: vec![],
+        viper_predicates: vec![],
+    };
+    let (_, result, _) = verify_programs("prusti", vec![Program::Legacy(program.into())])
+        .pop()
+        .unwrap();
+    result
 }
 
 /// Verify a list of programs.
-/// Returns a list of (program_name, verification_result) tuples.
+/// Returns a list of (program_name, verification_result, solve_time) tuples,
+/// where `solve_time` is the duration of the backend call.
 fn verify_programs(
-    env: &Environment,
+    rust_program_name: &str,
     programs: Vec<Program>,
-) -> Vec<(String, viper::VerificationResult)> {
-    let source_path = env.name.source_path();
-    let rust_program_name = source_path
-        .file_name()
-        .unwrap()
-        .to_str()
-        .unwrap()
-        .to_owned();
+) -> Vec<(String, viper::VerificationResult, Duration)> {
     let verification_requests = programs.into_iter().map(|mut program| {
         let program_name = program.get_name().to_string();
         let check_mode = program.get_check_mode();
@@ -258,15 +485,16 @@ fn verify_programs(
             .enable_all()
             .build()
             .expect("failed to construct Tokio runtime");
-        verification_requests
-            .map(|(program_name, request)| {
-                let remote_result = runtime.block_on(client.verify(request));
-                let result = remote_result.unwrap_or_else(|error| {
-                    panic!("Verification request of program {program_name} failed: {error:?}")
-                });
-                (program_name, result)
-            })
-            .collect()
+        let verification_results = verification_requests.map(|(program_name, request)| {
+            let start = Instant::now();
+            let remote_result = runtime.block_on(client.verify(request));
+            let solve_time = start.elapsed();
+            let result = remote_result.unwrap_or_else(|error| {
+                panic!("Verification request of program {program_name} failed: {error:?}")
+            });
+            (program_name, result, solve_time)
+        });
+        collect_verification_results(verification_results)
     } else {
         let mut stopwatch = Stopwatch::start("prusti-viper", "JVM startup");
         stopwatch.start_next("attach current thread to the JVM");
@@ -275,11 +503,33 @@ fn verify_programs(
         let viper_thread = Lazy::new(|| viper.attach_current_thread());
         stopwatch.finish();
         let mut cache = PersistentCache::load_cache(config::cache_path());
-        verification_requests
-            .map(|(program_name, request)| {
-                let result = process_verification_request(&viper_thread, request, &mut cache);
-                (program_name, result)
-            })
-            .collect()
+        if config::stats() {
+            // Do not attribute the JVM startup to the first verified program.
+            Lazy::force(&viper_thread);
+        }
+        let verification_results = verification_requests.map(|(program_name, request)| {
+            let start = Instant::now();
+            let result = process_verification_request(&viper_thread, request, &mut cache);
+            (program_name, result, start.elapsed())
+        });
+        collect_verification_results(verification_results)
+    }
+}
+
+/// Collect the results of the lazily verified programs. With
+/// `stop_on_first_error`, the programs after the first failing one are not
+/// verified.
+fn collect_verification_results(
+    verification_results: impl Iterator<Item = (String, viper::VerificationResult, Duration)>,
+) -> Vec<(String, viper::VerificationResult, Duration)> {
+    let mut collected = vec![];
+    for (program_name, result, solve_time) in verification_results {
+        let failed = !result.is_success();
+        collected.push((program_name, result, solve_time));
+        if failed && config::stop_on_first_error() {
+            info!("Skipping the remaining programs after the first failure");
+            break;
+        }
     }
+    collected
 }
@@ -1,6 +1,7 @@
 use crate::verifier::verify;
 use prusti_common::config;
 use prusti_interface::{
+    data::{MethodVerificationResult, VerificationResult},
     environment::{mir_storage, Environment},
     specs::{self, cross_crate::CrossCrateSpecs, is_spec_fn},
 };
@@ -16,11 +17,39 @@ use prusti_rustc_interface::{
         query::{ExternProviders, Providers},
         ty::TyCtxt,
     },
+    parse,
     session::Session,
 };
+use std::path::PathBuf;
 
 #[derive(Default)]
-pub struct PrustiCompilerCalls;
+pub struct PrustiCompilerCalls {
+    /// A file whose items are added to the root module of the crate, see
+    /// `config::specs`.
+    specs_file: Option<PathBuf>,
+    verification_timed_out: bool,
+    method_results: Vec<MethodVerificationResult>,
+}
+
+impl PrustiCompilerCalls {
+    pub fn new(specs_file: Option<PathBuf>) -> Self {
+        Self {
+            specs_file,
+            ..Default::default()
+        }
+    }
+
+    /// Whether verification failed only because some methods timed out.
+    pub fn verification_timed_out(&self) -> bool {
+        self.verification_timed_out
+    }
+
+    /// Takes the result of verifying each method, empty if nothing was
+    /// verified.
+    pub fn take_method_results(&mut self) -> Vec<MethodVerificationResult> {
+        std::mem::take(&mut self.method_results)
+    }
+}
 
 // Running `get_body_with_borrowck_facts` can be very slow, therefore we avoid it when not
 // necessary; for crates which won't be verified or spec_fns it suffices to load just the fn body
@@ -82,6 +111,29 @@ impl prusti_rustc_interface::driver::Callbacks for PrustiCompilerCalls {
             },
         );
     }
+    /// Adds the items of the external specifications file to the crate, so
+    /// that they are expanded together with the inline specifications.
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn after_crate_root_parsing<'tcx>(
+        &mut self,
+        compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        if let Some(specs_file) = &self.specs_file {
+            let session = compiler.session();
+            match parse::parse_crate_from_file(specs_file, &session.parse_sess) {
+                Ok(specs) => {
+                    let mut krate = queries.parse().unwrap();
+                    krate.get_mut().items.extend(specs.items);
+                }
+                Err(mut error) => {
+                    error.emit();
+                }
+            }
+            session.abort_if_errors();
+        }
+        Compilation::Continue
+    }
     #[tracing::instrument(level = "debug", skip_all)]
     fn after_expansion<'tcx>(
         &mut self,
@@ -131,6 +183,10 @@ impl prusti_rustc_interface::driver::Callbacks for PrustiCompilerCalls {
         Compilation::Continue
     }
 
+    /// Collects the specifications and verifies the crate once rustc has
+    /// finished its analysis. Compilation is stopped afterwards (unless
+    /// `full_compilation` is enabled and `check_only` is not), so that no
+    /// object code is emitted.
     #[tracing::instrument(level = "debug", skip_all)]
     fn after_analysis<'tcx>(
         &mut self,
@@ -157,12 +213,14 @@ impl prusti_rustc_interface::driver::Callbacks for PrustiCompilerCalls {
             }
             CrossCrateSpecs::import_export_cross_crate(&mut env, &mut def_spec);
             if !config::no_verify() {
-                verify(env, def_spec);
+                let (verification_result, method_results) = verify(env, def_spec);
+                self.verification_timed_out = verification_result == VerificationResult::Timeout;
+                self.method_results = method_results;
             }
         });
 
         compiler.session().abort_if_errors();
-        if config::full_compilation() {
+        if config::full_compilation() && !config::check_only() {
             Compilation::Continue
         } else {
             Compilation::Stop
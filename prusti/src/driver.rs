@@ -17,17 +17,25 @@ use arg_value::arg_value;
 use callbacks::PrustiCompilerCalls;
 use log::info;
 use prusti_common::{config, report::user, Stopwatch};
+use prusti_interface::data::MethodVerificationResult;
 use prusti_rustc_interface::{
     driver, errors,
     session::{self, EarlyErrorHandler},
 };
-use std::env;
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
 use tracing_subscriber::{filter::EnvFilter, prelude::*};
+use viper::VerificationBackend;
 
 /// Link to report Prusti bugs
 const BUG_REPORT_URL: &str = "https://github.com/viperproject/prusti-dev/issues/new";
 
+/// Exit code used when verification failed only because of timeouts
+const VERIFICATION_TIMEOUT_EXIT_CODE: i32 = 3;
+
 fn get_prusti_version_info() -> String {
     format!(
         "{}, commit {} {}, built on {}",
@@ -38,6 +46,101 @@ fn get_prusti_version_info() -> String {
     )
 }
 
+/// Report an error in the driver configuration and abort.
+fn early_error(message: String) -> ! {
+    EarlyErrorHandler::new(session::config::ErrorOutputType::HumanReadable(
+        errors::emitter::HumanReadableErrorType::Default(errors::emitter::ColorConfig::Auto),
+    ))
+    .early_error(message)
+}
+
+/// Report a problem in the driver configuration without aborting.
+fn early_warning(message: String) {
+    EarlyErrorHandler::new(session::config::ErrorOutputType::HumanReadable(
+        errors::emitter::HumanReadableErrorType::Default(errors::emitter::ColorConfig::Auto),
+    ))
+    .early_warn(message)
+}
+
+/// Warn about disabled transformation passes that do not exist and log which
+/// passes will run.
+fn check_disabled_passes() {
+    let known_passes = prusti_viper::encoder::TRANSFORMATION_PASSES;
+    let disabled_passes = config::disable_pass();
+    for pass in &disabled_passes {
+        if !known_passes.contains(&pass.as_str()) {
+            early_warning(format!(
+                "Ignoring unknown transformation pass `{pass}`. Known passes: {}",
+                known_passes.join(", ")
+            ));
+        }
+    }
+    let (disabled, enabled): (Vec<&str>, Vec<&str>) = known_passes
+        .iter()
+        .copied()
+        .partition(|pass| disabled_passes.iter().any(|disabled| disabled == pass));
+    info!("Enabled transformation passes: {}", enabled.join(", "));
+    info!("Disabled transformation passes: {}", disabled.join(", "));
+}
+
+/// Verify a built-in trivial program and report whether the verification
+/// backend works. Returns the exit code of the driver.
+fn run_selftest(backend: VerificationBackend) -> i32 {
+    user::message(format!(
+        "Running the Prusti self-test with the {backend} backend..."
+    ));
+    match prusti_viper::verifier::verify_selftest_program() {
+        viper::VerificationResult::Success => {
+            user::message("Self-test succeeded: the verification backend is working.");
+            0
+        }
+        result => {
+            user::message(format!("Self-test failed: {result:?}"));
+            1
+        }
+    }
+}
+
+/// Print the verification result of each method to stdout as a JSON array.
+/// The array is empty if nothing was verified.
+fn print_json_results(method_results: &[MethodVerificationResult]) {
+    let json_results: Vec<_> = method_results
+        .iter()
+        .map(|method_result| {
+            let errors: Vec<_> = method_result
+                .diagnostics
+                .iter()
+                .map(|diagnostic| {
+                    serde_json::json!({
+                        "message": diagnostic.message,
+                        "span": diagnostic.span,
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "method": method_result.method_name,
+                "success": errors.is_empty(),
+                "errors": errors,
+            })
+        })
+        .collect();
+    println!("{}", serde_json::Value::Array(json_results));
+}
+
+/// Whether rustc would compile an input or answer a query with the given
+/// arguments, that is, whether it would not fail with a "no input" error. The
+/// first argument is the path of the driver.
+fn has_rustc_input(rustc_args: &[String]) -> bool {
+    rustc_args.iter().skip(1).any(|arg| {
+        arg == "-"
+            || arg.ends_with(".rs")
+            || arg == "-vV"
+            || arg == "-V"
+            || arg == "--version"
+            || arg.starts_with("--print")
+    })
+}
+
 /// Initialize Prusti and the Rust compiler loggers.
 fn init_loggers() -> Option<FlushGuard> {
     // TODO: The `config::log() != ""` here is very bad; it makes us ignore the `log_tracing` flag
@@ -50,17 +153,23 @@ fn init_loggers() -> Option<FlushGuard> {
             .file(log_dir.join("trace.json"))
             .include_args(true)
             .build();
-        tracing_subscriber::registry()
+        // Ignore the error if a logger is already installed, e.g. because the
+        // driver is run more than once in the same process.
+        let _ = tracing_subscriber::registry()
             .with(filter)
             .with(chrome_layer)
-            .init();
+            .try_init();
         Some(guard)
     } else {
-        env_logger::init_from_env(
+        // `PRUSTI_LOG` takes module filters as `RUST_LOG` does, e.g.
+        // `PRUSTI_LOG=prusti_viper::encoder::middle::core_proof=trace`. It does
+        // not affect the loggers of rustc, which are configured separately.
+        let _ = env_logger::Builder::from_env(
             env_logger::Env::new()
                 .filter_or("PRUSTI_LOG", config::log())
                 .write_style_or("PRUSTI_LOG_STYLE", config::log_style()),
-        );
+        )
+        .try_init();
         None
     };
 
@@ -71,6 +180,103 @@ fn init_loggers() -> Option<FlushGuard> {
     guard
 }
 
+/// The outcome of running the compiler with Prusti's callbacks.
+struct CompilerRun {
+    /// The exit code reported by rustc.
+    rustc_exit_code: i32,
+    /// Whether verification failed only because some methods timed out.
+    verification_timed_out: bool,
+    /// The result of verifying each method, empty if nothing was verified.
+    method_results: Vec<MethodVerificationResult>,
+}
+
+impl CompilerRun {
+    /// The exit code of the driver.
+    fn exit_code(&self) -> i32 {
+        if self.rustc_exit_code != 0 && self.verification_timed_out {
+            VERIFICATION_TIMEOUT_EXIT_CODE
+        } else {
+            self.rustc_exit_code
+        }
+    }
+}
+
+/// Runs the compiler with Prusti's callbacks on the given arguments, verifying
+/// the crate unless verification is disabled.
+fn run_compiler(
+    mut rustc_args: Vec<String>,
+    backend: VerificationBackend,
+    specs_file: Option<PathBuf>,
+) -> CompilerRun {
+    let mut callbacks = PrustiCompilerCalls::new(specs_file);
+    let callbacks_ref = &mut callbacks;
+    let rustc_exit_code = driver::catch_with_exit_code(move || {
+        if !config::summary() {
+            user::message(format!(
+                "{}\n{}\n{}\n",
+                r"  __          __        __  ___             ",
+                r" |__)  _\/_  |__) |  | /__`  |   ____\/_  | ",
+                r" |      /\   |  \ \__/ .__/  |       /\   | ",
+            ));
+            user::message(format!("Prusti version: {}", get_prusti_version_info()));
+            user::message(format!("Verification backend: {backend}"));
+        }
+        info!("Prusti version: {}", get_prusti_version_info());
+        info!("Verification backend: {backend}");
+
+        if rustc_args.get(1).map(|s| s.as_ref()) == Some("-vV") {
+            // When cargo queries the verbose rustc version,
+            // also print the Prusti version to stdout.
+            // This ensures that the cargo build cache is
+            // invalidated when the Prusti version changes.
+            println!("Prusti version: {}", get_prusti_version_info());
+        }
+
+        rustc_args.push("-Zalways-encode-mir".to_owned());
+        rustc_args.push("-Zcrate-attr=feature(stmt_expr_attributes)".to_owned());
+        rustc_args.push("-Zcrate-attr=feature(register_tool)".to_owned());
+        rustc_args.push("-Zcrate-attr=register_tool(prusti)".to_owned());
+
+        if config::check_overflows() {
+            // Some crates might have a `overflow-checks = false` in their `Cargo.toml` to
+            // disable integer overflow checks, but we want to override that.
+            rustc_args.push("-Coverflow-checks=on".to_owned());
+        }
+
+        if config::dump_debug_info() {
+            rustc_args.push(format!(
+                "-Zdump-mir-dir={}",
+                config::log_dir()
+                    .join("mir")
+                    .to_str()
+                    .expect("failed to configure dump-mir-dir")
+            ));
+            rustc_args.push("-Zdump-mir=all".to_owned());
+            rustc_args.push("-Zdump-mir-graphviz".to_owned());
+            if !config::ignore_regions() {
+                rustc_args.push("-Zidentify-regions=yes".to_owned());
+            }
+        }
+        if config::dump_nll_facts() {
+            rustc_args.push("-Znll-facts=yes".to_string());
+            rustc_args.push(format!(
+                "-Znll-facts-dir={}",
+                config::log_dir()
+                    .join("nll-facts")
+                    .to_str()
+                    .expect("failed to configure nll-facts-dir")
+            ));
+        }
+
+        driver::RunCompiler::new(&rustc_args, callbacks_ref).run()
+    });
+    CompilerRun {
+        rustc_exit_code,
+        verification_timed_out: callbacks.verification_timed_out(),
+        method_results: callbacks.take_method_results(),
+    }
+}
+
 fn main() {
     driver::install_ice_hook(BUG_REPORT_URL, |handler| {
         let version_info = get_prusti_version_info();
@@ -97,11 +303,65 @@ fn main() {
     // This must be done after the build script check, otherwise Tokio's global tracing will fail.
     let _log_flush_guard = init_loggers();
 
+    // Reject unknown backends and arithmetic modes before compiling anything,
+    // so that a typo does not surface as a panic in the middle of the
+    // verification.
+    let backend = match config::viper_backend().parse::<VerificationBackend>() {
+        Ok(backend) => backend,
+        Err(error) => early_error(error.to_string()),
+    };
+    if let Err(error) = config::check_arithmetic_mode() {
+        early_error(error);
+    }
+
+    if let Some(z3_path) = config::z3_path() {
+        if !std::path::Path::new(&z3_path).is_file() {
+            early_error(format!("The Z3 path `{z3_path}` does not point to a file"));
+        }
+    }
+    if let Some(option) = config::smt_options().into_iter().find(|option| {
+        option
+            .split_once('=')
+            .map_or(true, |(key, _)| key.is_empty())
+    }) {
+        early_error(format!(
+            "Invalid SMT option `{option}`. SMT options must have the form `key=value`"
+        ));
+    }
+
+    check_disabled_passes();
+
+    if config::selftest() {
+        if !has_rustc_input(&original_rustc_args) {
+            std::process::exit(run_selftest(backend));
+        }
+        early_warning(
+            "The `selftest` flag is ignored because an input was given to the compiler".to_string(),
+        );
+    }
+
     // This environment variable will not be set when building dependencies.
     let is_primary_package = env::var("CARGO_PRIMARY_PACKAGE").is_ok();
     // Is this crate a dependency when user doesn't want to verify dependencies
     let is_no_verify_dep_crate = !is_primary_package && config::no_verify_deps();
 
+    // The external specifications are written for the crate being verified, so
+    // they are not added to the dependencies that cargo builds.
+    let is_cargo_dependency = env::var("CARGO_CRATE_NAME").is_ok() && !is_primary_package;
+    let specs_file = config::specs()
+        .filter(|_| !is_cargo_dependency)
+        .map(|specs| {
+            let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+            let specs_file = Path::new(&manifest_dir).join(specs);
+            if !specs_file.is_file() {
+                early_error(format!(
+                    "The specifications file `{}` does not exist",
+                    specs_file.display()
+                ));
+            }
+            specs_file
+        });
+
     // Would `cargo check` not report errors for this crate? That is, are lints disabled
     // (i.e. is this a non-local crate)
     let are_lints_disabled =
@@ -113,6 +373,21 @@ fn main() {
         config::set_no_verify(true);
     }
 
+    // The dump file is truncated only by the runs that verify a crate, so that
+    // the dependencies that cargo builds do not wipe the programs appended by
+    // other runs.
+    if let Some(dump_path) = config::dump_viper().filter(|_| !config::no_verify()) {
+        let dump_path = std::path::Path::new(&dump_path);
+        if !dump_path.is_dir() {
+            if let Err(error) = std::fs::File::create(dump_path) {
+                early_error(format!(
+                    "Failed to create the Viper dump file `{}`: {error}",
+                    dump_path.display()
+                ));
+            }
+        }
+    }
+
     // Disable incremental compilation because it causes mir_borrowck not to be called.
     let mut rustc_args = Vec::new();
     let mut is_codegen = false;
@@ -131,64 +406,19 @@ fn main() {
         }
     }
 
-    let exit_code = driver::catch_with_exit_code(move || {
-        user::message(format!(
-            "{}\n{}\n{}\n",
-            r"  __          __        __  ___             ",
-            r" |__)  _\/_  |__) |  | /__`  |   ____\/_  | ",
-            r" |      /\   |  \ \__/ .__/  |       /\   | ",
-        ));
-        user::message(format!("Prusti version: {}", get_prusti_version_info()));
-        info!("Prusti version: {}", get_prusti_version_info());
-
-        if rustc_args.get(1).map(|s| s.as_ref()) == Some("-vV") {
-            // When cargo queries the verbose rustc version,
-            // also print the Prusti version to stdout.
-            // This ensures that the cargo build cache is
-            // invalidated when the Prusti version changes.
-            println!("Prusti version: {}", get_prusti_version_info());
-        }
-
-        rustc_args.push("-Zalways-encode-mir".to_owned());
-        rustc_args.push("-Zcrate-attr=feature(stmt_expr_attributes)".to_owned());
-        rustc_args.push("-Zcrate-attr=feature(register_tool)".to_owned());
-        rustc_args.push("-Zcrate-attr=register_tool(prusti)".to_owned());
-
-        if config::check_overflows() {
-            // Some crates might have a `overflow-checks = false` in their `Cargo.toml` to
-            // disable integer overflow checks, but we want to override that.
-            rustc_args.push("-Coverflow-checks=on".to_owned());
-        }
-
-        if config::dump_debug_info() {
-            rustc_args.push(format!(
-                "-Zdump-mir-dir={}",
-                config::log_dir()
-                    .join("mir")
-                    .to_str()
-                    .expect("failed to configure dump-mir-dir")
-            ));
-            rustc_args.push("-Zdump-mir=all".to_owned());
-            rustc_args.push("-Zdump-mir-graphviz".to_owned());
-            if !config::ignore_regions() {
-                rustc_args.push("-Zidentify-regions=yes".to_owned());
-            }
-        }
-        if config::dump_nll_facts() {
-            rustc_args.push("-Znll-facts=yes".to_string());
-            rustc_args.push(format!(
-                "-Znll-facts-dir={}",
-                config::log_dir()
-                    .join("nll-facts")
-                    .to_str()
-                    .expect("failed to configure nll-facts-dir")
-            ));
-        }
-
-        let mut callbacks = PrustiCompilerCalls;
-
-        driver::RunCompiler::new(&rustc_args, &mut callbacks).run()
-    });
+    let compiler_run = run_compiler(rustc_args, backend, specs_file);
+    for method_result in &compiler_run.method_results {
+        info!(
+            "Verification result of {}: {:?} ({} errors)",
+            method_result.method_name,
+            method_result.outcome,
+            method_result.diagnostics.len()
+        );
+    }
+    if config::json_results() {
+        print_json_results(&compiler_run.method_results);
+    }
+    let exit_code = compiler_run.exit_code();
 
     // Check if verifying a program in our test suite is taking too long
     let duration = stopwatch.finish();
@@ -3,31 +3,65 @@
 use log::{debug, warn};
 use prusti_common::{config, report::user};
 use prusti_interface::{
-    data::{VerificationResult, VerificationTask},
+    data::{MethodVerificationResult, VerificationResult, VerificationTask},
     environment::Environment,
     specs::typed,
 };
 use prusti_viper::verifier::Verifier;
+use std::{path::Path, time::Instant};
 
+/// Verifies the annotated procedures of the crate. Returns the overall result
+/// together with the result of verifying each method.
 #[tracing::instrument(name = "prusti::verify", level = "debug", skip(env))]
-pub fn verify(env: Environment<'_>, def_spec: typed::DefSpecificationMap) {
+pub fn verify(
+    env: Environment<'_>,
+    def_spec: typed::DefSpecificationMap,
+) -> (VerificationResult, Vec<MethodVerificationResult>) {
+    let start_time = Instant::now();
     if env.diagnostic.has_errors() {
         warn!("The compiler reported an error, so the program will not be verified.");
+        (VerificationResult::Failure, Vec::new())
     } else {
         debug!("Prepare verification task...");
         // TODO: can we replace `get_annotated_procedures` with information
         // that is already in `def_spec`?
-        let (annotated_procedures, types) = env.get_annotated_procedures_and_types();
+        let (mut annotated_procedures, types) = env.get_annotated_procedures_and_types();
+        let verify_only_items = config::verify_only_items();
+        if !verify_only_items.is_empty() {
+            annotated_procedures.retain(|procedure| {
+                let name = env.name.get_absolute_item_name(*procedure);
+                verify_only_items
+                    .iter()
+                    .any(|item| name == *item || name.ends_with(&format!("::{item}")))
+            });
+        }
+        let changed_files = config::changed();
+        let mut skipped_unchanged = 0;
+        if !changed_files.is_empty() {
+            let source_map = env.query.codemap();
+            let procedures_count = annotated_procedures.len();
+            annotated_procedures.retain(|procedure| {
+                let span = env.query.get_def_span(procedure);
+                let file_name = source_map.lookup_source_file(span.lo()).name.clone();
+                let file_name = file_name.prefer_local().to_string();
+                changed_files
+                    .iter()
+                    .any(|changed| Path::new(&file_name).ends_with(changed))
+            });
+            skipped_unchanged = procedures_count - annotated_procedures.len();
+        }
         let verification_task = VerificationTask {
             procedures: annotated_procedures,
             types,
         };
         debug!("Verification task: {:?}", &verification_task);
 
-        user::message(format!(
-            "Verification of {} items...",
-            verification_task.procedures.len()
-        ));
+        if !config::summary() {
+            user::message(format!(
+                "Verification of {} items...",
+                verification_task.procedures.len()
+            ));
+        }
 
         if config::print_collected_verification_items() {
             println!(
@@ -43,6 +77,10 @@ pub fn verify(env: Environment<'_>, def_spec: typed::DefSpecificationMap) {
             }
         }
 
+        let mut skipped_programs = 0;
+        let mut skipped_trusted = 0;
+        let mut assumptions = 0;
+        let mut method_results = Vec::new();
         let verification_result =
             if verification_task.procedures.is_empty() && verification_task.types.is_empty() {
                 VerificationResult::Success
@@ -53,11 +91,46 @@ pub fn verify(env: Environment<'_>, def_spec: typed::DefSpecificationMap) {
                 let mut verifier = Verifier::new(&env, def_spec);
                 let verification_result = verifier.verify(&verification_task);
                 debug!("Verifier returned {:?}", verification_result);
+                skipped_programs = verifier.skipped_programs() + verifier.encoding_errors();
+                skipped_trusted = verifier.skipped_trusted_procedures();
+                assumptions = verifier.encoded_assumptions();
+                method_results = verifier.method_results().to_vec();
 
                 verification_result
             };
 
+        if config::summary() {
+            let verified_methods = method_results
+                .iter()
+                .filter(|method_result| method_result.outcome == VerificationResult::Success)
+                .count();
+            let failed_methods = method_results.len() - verified_methods;
+            // Unlike the other messages, the summary is printed also in the
+            // quiet mode, because it is the only output that was asked for.
+            eprintln!(
+                "{verified_methods} methods verified, {failed_methods} failed, \
+                 {skipped_programs} skipped, {skipped_trusted} trusted, total time {:.2}s",
+                start_time.elapsed().as_secs_f64()
+            );
+        } else {
+            if skipped_unchanged > 0 {
+                user::message(format!(
+                    "Skipped verification of {skipped_unchanged} items in unchanged files"
+                ));
+            }
+            if skipped_trusted > 0 {
+                user::message(format!(
+                    "Skipped verification of {skipped_trusted} trusted items"
+                ));
+            }
+            if assumptions > 0 {
+                user::message(format!(
+                    "Verification relies on {assumptions} unchecked assumptions"
+                ));
+            }
+        }
         match verification_result {
+            VerificationResult::Success | VerificationResult::Timeout if config::summary() => {}
             VerificationResult::Success => {
                 if env.diagnostic.has_errors() {
                     user::message(
@@ -72,7 +145,9 @@ pub fn verify(env: Environment<'_>, def_spec: typed::DefSpecificationMap) {
                 }
             }
             VerificationResult::Failure => {
-                user::message("Verification failed");
+                if !config::summary() {
+                    user::message("Verification failed");
+                }
                 assert!(
                     env.diagnostic.has_errors()
                         || config::internal_errors_as_warnings()
@@ -80,6 +155,10 @@ pub fn verify(env: Environment<'_>, def_spec: typed::DefSpecificationMap) {
                             && config::allow_unreachable_unsupported_code())
                 );
             }
+            VerificationResult::Timeout => {
+                user::message("Verification timed out");
+            }
         };
+        (verification_result, method_results)
     }
 }
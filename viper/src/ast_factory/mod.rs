@@ -10,12 +10,16 @@ mod position;
 mod program;
 mod statement;
 mod structs;
+mod viper_program_builder;
 
 use crate::jni_utils::JniUtils;
 use jni::{objects::JObject, JNIEnv};
 use viper_sys::wrappers::viper::silver::ast;
 
-pub use self::{ast_type::*, expression::*, position::*, program::*, statement::*, structs::*};
+pub use self::{
+    ast_type::*, expression::*, position::*, program::*, statement::*, structs::*,
+    viper_program_builder::*,
+};
 
 #[derive(Clone, Copy)]
 pub struct AstFactory<'a> {
@@ -265,6 +265,17 @@ impl<'a> AstFactory<'a> {
         )
     }
 
+    pub fn label_with_pos(&self, name: &str, invs: &[Expr], pos: Position) -> Stmt<'a> {
+        build_ast_node_with_pos!(
+            self,
+            Stmt,
+            ast::Label,
+            self.jni.new_string(name),
+            self.jni.new_seq(&map_to_jobjects!(invs)),
+            pos.to_jobject()
+        )
+    }
+
     pub fn goto(&self, target: &str) -> Stmt<'a> {
         build_ast_node!(self, Stmt, ast::Goto, self.jni.new_string(target))
     }
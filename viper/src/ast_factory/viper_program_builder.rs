@@ -0,0 +1,84 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::ast_factory::{
+    structs::{
+        Domain, DomainFunc, Field, Function, Method, NamedDomainAxiom, Predicate, Program, Type,
+    },
+    AstFactory,
+};
+use rustc_hash::FxHashSet;
+
+/// Accumulates the declarations of a Viper program, so that a complete
+/// program can be assembled from the pieces produced by different encoders.
+/// Domains are identified by their name: a domain whose name was already
+/// declared is ignored.
+pub struct ViperProgramBuilder<'a> {
+    ast: AstFactory<'a>,
+    domain_names: FxHashSet<String>,
+    domains: Vec<Domain<'a>>,
+    fields: Vec<Field<'a>>,
+    functions: Vec<Function<'a>>,
+    predicates: Vec<Predicate<'a>>,
+    methods: Vec<Method<'a>>,
+}
+
+impl<'a> ViperProgramBuilder<'a> {
+    pub fn new(ast: AstFactory<'a>) -> Self {
+        ViperProgramBuilder {
+            ast,
+            domain_names: FxHashSet::default(),
+            domains: vec![],
+            fields: vec![],
+            functions: vec![],
+            predicates: vec![],
+            methods: vec![],
+        }
+    }
+
+    /// Declares a domain, unless a domain with the same name was already
+    /// declared. Returns whether the domain was added.
+    pub fn add_domain(
+        &mut self,
+        name: &str,
+        functions: &[DomainFunc],
+        axioms: &[NamedDomainAxiom],
+        type_vars: &[Type],
+    ) -> bool {
+        if !self.domain_names.insert(name.to_string()) {
+            return false;
+        }
+        self.domains
+            .push(self.ast.domain(name, functions, axioms, type_vars));
+        true
+    }
+
+    pub fn add_field(&mut self, field: Field<'a>) {
+        self.fields.push(field);
+    }
+
+    pub fn add_function(&mut self, function: Function<'a>) {
+        self.functions.push(function);
+    }
+
+    pub fn add_predicate(&mut self, predicate: Predicate<'a>) {
+        self.predicates.push(predicate);
+    }
+
+    pub fn add_method(&mut self, method: Method<'a>) {
+        self.methods.push(method);
+    }
+
+    /// Builds the program with all the declarations added so far, in the
+    /// order in which they were added.
+    pub fn build(&self) -> Program<'a> {
+        self.ast.program(
+            &self.domains,
+            &self.fields,
+            &self.functions,
+            &self.predicates,
+            &self.methods,
+        )
+    }
+}
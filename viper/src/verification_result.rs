@@ -17,6 +17,8 @@ pub enum VerificationResult {
     ConsistencyErrors(Vec<String>),
     /// The verification raised a Java exception.
     JavaException(JavaException),
+    /// The verifier exceeded its timeout.
+    Timeout,
 }
 
 impl VerificationResult {
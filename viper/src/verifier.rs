@@ -173,6 +173,13 @@ impl<'a> Verifier<'a> {
                         .is_instance_of(viper_error, "viper/silver/verifier/VerificationError");
 
                     if !is_verification_error {
+                        if self
+                            .jni
+                            .is_instance_of(viper_error, "viper/silver/verifier/TimeoutOccurred")
+                        {
+                            return VerificationResult::Timeout;
+                        }
+
                         let is_aborted_exceptionally = self
                             .jni
                             .is_instance_of(viper_error, "viper/silver/verifier/AbortedExceptionally");
@@ -0,0 +1,72 @@
+use viper::*;
+
+lazy_static::lazy_static! {
+    static ref VIPER: Viper = Viper::new_for_tests();
+}
+
+#[test]
+fn success_with_built_program() {
+    env_logger::init();
+
+    let verification_context: VerificationContext = VIPER.attach_current_thread();
+    let ast = verification_context.new_ast_factory();
+
+    let mut builder = ViperProgramBuilder::new(ast);
+
+    let zero_function = ast.domain_func("zero", &[], ast.int_type(), false, "Zero");
+    let zero_axiom = ast.named_domain_axiom(
+        "zero_is_zero",
+        ast.eq_cmp(ast.domain_func_app(zero_function, &[], &[]), ast.int_lit(0)),
+    );
+    assert!(builder.add_domain("Zero", &[zero_function], &[zero_axiom], &[]));
+    // A second declaration of the same domain is ignored.
+    assert!(!builder.add_domain("Zero", &[zero_function], &[zero_axiom], &[]));
+
+    builder.add_field(ast.field("value", ast.int_type()));
+    builder.add_function(ast.function(
+        "one",
+        &[],
+        ast.int_type(),
+        &[],
+        &[],
+        ast.no_position(),
+        Some(ast.int_lit(1)),
+    ));
+    builder.add_predicate(ast.predicate(
+        "Value",
+        &[ast.local_var_decl("box", ast.ref_type())],
+        Some(ast.field_access_predicate(
+            ast.field_access(
+                ast.local_var("box", ast.ref_type()),
+                ast.field("value", ast.int_type()),
+            ),
+            ast.full_perm(),
+        )),
+    ));
+    builder.add_method(ast.method(
+        "check",
+        &[],
+        &[],
+        &[],
+        &[],
+        Some(ast.seqn(
+            &[ast.assert(
+                ast.lt_cmp(
+                    ast.domain_func_app(zero_function, &[], &[]),
+                    ast.func_app("one", &[], ast.int_type(), ast.no_position()),
+                ),
+                ast.no_position(),
+            )],
+            &[],
+        )),
+    ));
+
+    let program = builder.build();
+
+    let mut verifier =
+        verification_context.new_verifier_with_default_smt(viper::VerificationBackend::Silicon);
+
+    let verification_result = verifier.verify(program);
+
+    assert!(verification_result.is_success());
+}
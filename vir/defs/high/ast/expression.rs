@@ -145,6 +145,11 @@ pub enum BinaryOpKind {
     And,
     Or,
     Implies,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
     LifetimeIntersection,
 }
 
@@ -232,6 +237,7 @@ pub struct FuncApp {
 pub enum BuiltinFunc {
     SnapshotEquality,
     Size,
+    Align,
     PaddingSize,
     Discriminant,
     LifetimeIncluded,
@@ -249,6 +255,12 @@ pub enum BuiltinFunc {
     NewInt,
     Index,
     Len,
+    /// Whether the raw pointer points to a valid, readable location.
+    PointerValid,
+    /// A call to the lemma stating that a snapshot is equal to itself.
+    SnapshotEqualityReflexivity,
+    /// A call to the lemma stating that snapshots with equal fields are equal.
+    SnapshotEqualityCongruence,
 }
 
 #[display(fmt = "__builtin__{}({})", function, "display::cjoin(arguments)")]
@@ -33,6 +33,8 @@ pub enum Statement {
     GhostAssign(GhostAssign),
     LeakAll(LeakAll),
     SetUnionVariant(SetUnionVariant),
+    UnfoldHint(UnfoldHint),
+    FoldHint(FoldHint),
     NewLft(NewLft),
     EndLft(EndLft),
     DeadLifetime(DeadLifetime),
@@ -252,6 +254,22 @@ pub struct SetUnionVariant {
     pub position: Position,
 }
 
+#[display(fmt = "unfold-hint Owned({})", place)]
+/// A user-provided hint that tells fold-unfold to unfold `Owned(place)` at
+/// this program point.
+pub struct UnfoldHint {
+    pub place: Expression,
+    pub position: Position,
+}
+
+#[display(fmt = "fold-hint Owned({})", place)]
+/// A user-provided hint that tells fold-unfold to fold `Owned(place)` at this
+/// program point.
+pub struct FoldHint {
+    pub place: Expression,
+    pub position: Position,
+}
+
 #[display(fmt = "{} = newlft()", target)]
 pub struct NewLft {
     pub target: VariableDecl,
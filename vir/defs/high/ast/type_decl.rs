@@ -147,6 +147,7 @@ pub struct Pointer {
 #[display(fmt = "{}", name)]
 pub struct Closure {
     pub name: String,
+    pub lifetimes: Vec<LifetimeConst>,
     /// The tuple of captured arguments.
     pub arguments: Vec<Type>,
 }
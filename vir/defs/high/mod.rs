@@ -20,10 +20,10 @@ pub use self::{
         rvalue::{Operand, OperandKind, Rvalue},
         statement::{
             Assert, Assign, Assume, BorShorten, CloseFracRef, CloseMutRef, Comment, Consume,
-            CopyPlace, DeadInclusion, DeadLifetime, EndLft, Exhale, GhostAssign, GhostHavoc, Havoc,
-            Inhale, LeakAll, LifetimeReturn, LifetimeTake, LoopInvariant, MovePlace, NewLft,
-            ObtainMutRef, OldLabel, OpenFracRef, OpenMutRef, SetUnionVariant, Statement,
-            WriteAddress, WritePlace,
+            CopyPlace, DeadInclusion, DeadLifetime, EndLft, Exhale, FoldHint, GhostAssign,
+            GhostHavoc, Havoc, Inhale, LeakAll, LifetimeReturn, LifetimeTake, LoopInvariant,
+            MovePlace, NewLft, ObtainMutRef, OldLabel, OpenFracRef, OpenMutRef, SetUnionVariant,
+            Statement, UnfoldHint, WriteAddress, WritePlace,
         },
         ty::{self, Type},
         type_decl::{self, DiscriminantRange, DiscriminantValue, TypeDecl},
@@ -38,6 +38,11 @@ impl BinaryOpKind {
             | BinaryOpKind::Mul
             | BinaryOpKind::Div
             | BinaryOpKind::Mod
+            | BinaryOpKind::BitAnd
+            | BinaryOpKind::BitOr
+            | BinaryOpKind::BitXor
+            | BinaryOpKind::Shl
+            | BinaryOpKind::Shr
             | BinaryOpKind::LifetimeIntersection => argument_type,
         }
     }
@@ -37,6 +37,7 @@ impl WithLifetimes for ty::Type {
             | ty::Type::Map(ty::Map { lifetimes, .. })
             | ty::Type::Array(ty::Array { lifetimes, .. })
             | ty::Type::Slice(ty::Slice { lifetimes, .. })
+            | ty::Type::Closure(ty::Closure { lifetimes, .. })
             | ty::Type::Trusted(ty::Trusted { lifetimes, .. }) => lifetimes.clone(),
             _ => vec![],
         }
@@ -22,6 +22,8 @@ impl Positioned for Statement {
             Self::Consume(statement) => statement.position(),
             Self::LeakAll(statement) => statement.position(),
             Self::SetUnionVariant(statement) => statement.position(),
+            Self::UnfoldHint(statement) => statement.position(),
+            Self::FoldHint(statement) => statement.position(),
             Self::NewLft(statement) => statement.position(),
             Self::EndLft(statement) => statement.position(),
             Self::DeadLifetime(statement) => statement.position(),
@@ -146,6 +148,18 @@ impl Positioned for SetUnionVariant {
     }
 }
 
+impl Positioned for UnfoldHint {
+    fn position(&self) -> Position {
+        self.position
+    }
+}
+
+impl Positioned for FoldHint {
+    fn position(&self) -> Position {
+        self.position
+    }
+}
+
 impl Positioned for NewLft {
     fn position(&self) -> Position {
         self.position
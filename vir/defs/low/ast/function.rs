@@ -4,6 +4,8 @@ use crate::common::display;
 pub enum FunctionKind {
     MemoryBlockBytes,
     CallerFor,
+    PointerTarget,
+    SnapshotLemma,
 }
 
 #[display(
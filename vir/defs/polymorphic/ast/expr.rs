@@ -1332,6 +1332,54 @@ impl Expr {
         .fold(self)
     }
 
+    /// Rename the free occurrences of the local variable `old` to `new`. The
+    /// occurrences bound by a quantifier or a `let` expression are kept.
+    #[must_use]
+    pub fn rename_local_var(self, old: &str, new: &str) -> Self {
+        struct LocalRenamer<'a> {
+            old: &'a str,
+            new: &'a str,
+        }
+        impl<'a> ExprFolder for LocalRenamer<'a> {
+            fn fold(&mut self, e: Expr) -> Expr {
+                let is_shadowed = match &e {
+                    Expr::ForAll(ForAll { variables, .. })
+                    | Expr::Exists(Exists { variables, .. }) => {
+                        variables.iter().any(|var| var.name == self.old)
+                    }
+                    _ => false,
+                };
+                if is_shadowed {
+                    return e;
+                }
+                match e {
+                    Expr::LetExpr(LetExpr {
+                        variable,
+                        def,
+                        body,
+                        position,
+                    }) if variable.name == self.old => Expr::LetExpr(LetExpr {
+                        variable,
+                        def: self.fold_boxed(def),
+                        body,
+                        position,
+                    }),
+                    e => default_fold_expr(self, e),
+                }
+            }
+
+            fn fold_local(&mut self, Local { variable, position }: Local) -> Expr {
+                let variable = if variable.name == self.old {
+                    LocalVar::new(self.new, variable.typ)
+                } else {
+                    variable
+                };
+                Expr::Local(Local { variable, position })
+            }
+        }
+        LocalRenamer { old, new }.fold(self)
+    }
+
     #[must_use]
     pub fn replace_multiple_places(self, replacements: &[(Expr, Expr)]) -> Self {
         // TODO: disabled for snapshot patching
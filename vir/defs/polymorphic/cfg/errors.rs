@@ -0,0 +1,41 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+/// A malformed `CfgMethod` detected by one of its consistency checks.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CfgError {
+    #[error("the block label `{0}` is not a valid identifier")]
+    InvalidLabel(String),
+    #[error("the block label `{0}` is already used")]
+    DuplicateLabel(String),
+    #[error("the block label `{0}` is reserved for the end of the method")]
+    ReservedLabel(String),
+    #[error("the variable name `{0}` is already used")]
+    DuplicateVariable(String),
+    #[error("the variable `{0}` is already declared with a different type")]
+    ConflictingVariableType(String),
+    #[error("`{0}` is not a local variable or formal return of the method")]
+    UnknownVariable(String),
+    #[error("the switch at the end of block `{0}` has no guarded targets")]
+    EmptySwitch(String),
+    #[error("the blocks {} are not reachable from the entry block", .0.join(", "))]
+    UnreachableBlocks(Vec<String>),
+    #[error("the blocks {} are reachable but have no successor", .0.join(", "))]
+    MissingSuccessors(Vec<String>),
+    #[error("the loop heads {} have no invariants", .0.join(", "))]
+    LoopsWithoutInvariants(Vec<String>),
+    #[error(
+        "the blocks {} unconditionally jump to themselves without invariants",
+        .0.join(", ")
+    )]
+    SelfLoopsWithoutInvariants(Vec<String>),
+    #[error("the block `{0}` does not have a `Return` successor")]
+    NotAReturnBlock(String),
+    #[error("the postcondition refers to `{0}`, which is not a formal return of the method")]
+    UnknownReturnVariable(String),
+}
+
+pub type CfgResult<T> = Result<T, CfgError>;
@@ -0,0 +1,102 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::polymorphic::{
+    ast::LocalVar,
+    cfg::method::{CfgMethod, Successor},
+};
+
+/// A summary of a `CfgMethod` that abstracts from the statements and
+/// expressions, used to snapshot the shape of an encoding in golden tests.
+///
+/// There is no section for formal arguments: a `CfgMethod` has none, its Viper
+/// method is emitted without arguments and the arguments of the encoded Rust
+/// function are local variables.
+#[derive(serde::Serialize)]
+struct MethodShape<'a> {
+    name: &'a str,
+    formal_returns: Vec<VarShape<'a>>,
+    local_vars: Vec<VarShape<'a>>,
+    blocks: Vec<BlockShape<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct VarShape<'a> {
+    name: &'a str,
+    typ: String,
+}
+
+#[derive(serde::Serialize)]
+struct BlockShape<'a> {
+    label: &'a str,
+    invariant_count: usize,
+    successor: SuccessorShape,
+}
+
+#[derive(serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum SuccessorShape {
+    Undefined,
+    Return,
+    IntentionalUnreachable,
+    Goto { target: usize },
+    GotoSwitch { targets: Vec<usize>, default: usize },
+}
+
+impl<'a> From<&'a LocalVar> for VarShape<'a> {
+    fn from(var: &'a LocalVar) -> Self {
+        VarShape {
+            name: &var.name,
+            typ: var.typ.to_string(),
+        }
+    }
+}
+
+impl From<&Successor> for SuccessorShape {
+    fn from(successor: &Successor) -> Self {
+        match successor {
+            Successor::Undefined => SuccessorShape::Undefined,
+            Successor::Return => SuccessorShape::Return,
+            Successor::IntentionalUnreachable(_) => SuccessorShape::IntentionalUnreachable,
+            Successor::Goto(target) => SuccessorShape::Goto {
+                target: target.index(),
+            },
+            Successor::GotoSwitch(guarded_targets, default_target) => SuccessorShape::GotoSwitch {
+                targets: guarded_targets
+                    .iter()
+                    .map(|(_, target)| target.index())
+                    .collect(),
+                default: default_target.index(),
+            },
+        }
+    }
+}
+
+impl CfgMethod {
+    /// Serializes the shape of the method to JSON: its name, formal returns,
+    /// local variables (which include the arguments of the encoded function),
+    /// and for each block its label, the number of its invariants, and its
+    /// successor. The output depends only on the method,
+    /// so it can be compared against golden files.
+    pub fn to_json(&self) -> String {
+        let shape = MethodShape {
+            name: &self.method_name,
+            formal_returns: self.formal_returns.iter().map(VarShape::from).collect(),
+            local_vars: self.local_vars.iter().map(VarShape::from).collect(),
+            blocks: self
+                .basic_blocks
+                .iter()
+                .zip(&self.basic_blocks_labels)
+                .map(|(block, label)| BlockShape {
+                    label,
+                    invariant_count: block.exhale_on_entry.len() + block.inhale_after_label.len(),
+                    successor: SuccessorShape::from(&block.successor),
+                })
+                .collect(),
+        };
+        serde_json::to_string_pretty(&shape).unwrap()
+    }
+}
@@ -6,7 +6,11 @@
 
 use crate::{
     common::identifier::WithIdentifier,
-    polymorphic::{ast::*, gather_labels::gather_labels},
+    polymorphic::{
+        ast::*,
+        cfg::errors::{CfgError, CfgResult},
+        gather_labels::gather_labels,
+    },
 };
 use log::{debug, trace};
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -22,6 +26,10 @@ pub struct CfgMethod {
     pub(crate) uuid: Uuid,
     pub(crate) method_name: String,
     pub(crate) formal_returns: Vec<LocalVar>,
+    /// Preconditions emitted as `requires` clauses of the Viper method.
+    pub(crate) pres: Vec<Expr>,
+    /// Postconditions emitted as `ensures` clauses of the Viper method.
+    pub(crate) posts: Vec<Expr>,
     // FIXME: This should be pub(in super::super). However, the optimization
     // that depends on snapshots needs to modify this field.
     pub local_vars: Vec<LocalVar>,
@@ -30,6 +38,11 @@ pub struct CfgMethod {
     pub(crate) reserved_labels: FxHashSet<String>,
     pub basic_blocks: Vec<CfgBlock>, // FIXME: Hack, should be pub(super).
     pub(crate) basic_blocks_labels: Vec<String>,
+    /// The label that marks the end of the method, targeted by `Return`.
+    pub(crate) return_label: String,
+    /// The formal return that tells apart the blocks with branch-specific
+    /// postconditions, see `add_return_postcondition`.
+    pub(crate) return_path: Option<LocalVar>,
     #[serde(skip)]
     pub(crate) fresh_var_index: i32,
     #[serde(skip)]
@@ -40,13 +53,28 @@ pub struct CfgMethod {
 pub struct CfgBlock {
     pub stmts: Vec<Stmt>, // FIXME: Hack, should be pub(super).
     pub successor: Successor,
+    /// The position of the source code from which the block originates. It is
+    /// attached to the label of the block.
+    pub position: Position,
+    /// Invariants that are exhaled on every edge that enters the block, for
+    /// example, on the entry and back edges of a loop.
+    pub exhale_on_entry: Vec<Expr>,
+    /// Invariants that are inhaled right after the label of the block.
+    pub inhale_after_label: Vec<Expr>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum Successor {
     Undefined,
     Return,
+    /// The end of the block is unreachable by design, for example, because it
+    /// originates from `unreachable!()`. It is lowered to `assert false` at the
+    /// given position.
+    IntentionalUnreachable(Position),
     Goto(CfgBlockIndex),
+    /// The guards are checked in order and the first one that holds determines
+    /// the target, so the guards may overlap. If none of them holds, the
+    /// default target is taken.
     GotoSwitch(Vec<(Expr, CfgBlockIndex)>, CfgBlockIndex),
 }
 
@@ -57,9 +85,19 @@ pub struct CfgBlockIndex {
     pub block_index: usize,
 }
 
+/// The default format prints only the block number, because it may end up in
+/// the generated program, which must not depend on the random method UUID. The
+/// alternate format (`{:#?}`) additionally prints the first segment of the UUID
+/// of the method the block belongs to, which helps to tell apart indices of
+/// different methods in logs.
 impl fmt::Debug for CfgBlockIndex {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "cfg:{}", self.block_index)
+        if f.alternate() {
+            let uuid = self.method_uuid.simple().to_string();
+            write!(f, "cfg:{}@{}", self.block_index, &uuid[..8])
+        } else {
+            write!(f, "cfg:{}", self.block_index)
+        }
     }
 }
 
@@ -70,7 +108,9 @@ impl Successor {
 
     pub fn get_following(&self) -> Vec<CfgBlockIndex> {
         match &self {
-            Successor::Undefined | Successor::Return => vec![],
+            Successor::Undefined | Successor::Return | Successor::IntentionalUnreachable(_) => {
+                vec![]
+            }
             Successor::Goto(target) => vec![*target],
             Successor::GotoSwitch(guarded_targets, default_target) => {
                 let mut res: Vec<CfgBlockIndex> = guarded_targets.iter().map(|g| g.1).collect();
@@ -103,6 +143,22 @@ impl Successor {
         }
     }
 
+    /// Apply `f` to every target of the successor.
+    #[must_use]
+    pub fn map_targets<F: FnMut(CfgBlockIndex) -> CfgBlockIndex>(self, mut f: F) -> Self {
+        match self {
+            Successor::Goto(target) => Successor::Goto(f(target)),
+            Successor::GotoSwitch(guarded_targets, default_target) => Successor::GotoSwitch(
+                guarded_targets
+                    .into_iter()
+                    .map(|(guard, target)| (guard, f(target)))
+                    .collect(),
+                f(default_target),
+            ),
+            x => x,
+        }
+    }
+
     pub(super) fn replace_uuid(self, new_uuid: Uuid) -> Self {
         match self {
             Successor::Goto(target) => Successor::Goto(target.set_uuid(new_uuid)),
@@ -118,6 +174,49 @@ impl Successor {
     }
 }
 
+/// A helper for constructing `Successor::GotoSwitch`. The arms are kept in the
+/// order in which they are added.
+#[derive(Debug, Clone)]
+pub struct SwitchBuilder {
+    guarded_targets: Vec<(Expr, CfgBlockIndex)>,
+    default_target: Option<CfgBlockIndex>,
+}
+
+impl SwitchBuilder {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        SwitchBuilder {
+            guarded_targets: vec![],
+            default_target: None,
+        }
+    }
+
+    #[must_use]
+    pub fn arm(mut self, guard: Expr, target: CfgBlockIndex) -> Self {
+        self.guarded_targets.push((guard, target));
+        self
+    }
+
+    #[must_use]
+    #[allow(clippy::should_implement_trait)]
+    pub fn default(mut self, target: CfgBlockIndex) -> Self {
+        assert!(
+            self.default_target.is_none(),
+            "The default target of the switch is already set"
+        );
+        self.default_target = Some(target);
+        self
+    }
+
+    /// Panics if the default target was not set.
+    pub fn build(self) -> Successor {
+        let default_target = self
+            .default_target
+            .expect("The default target of the switch is not set");
+        Successor::GotoSwitch(self.guarded_targets, default_target)
+    }
+}
+
 impl CfgBlockIndex {
     pub(super) fn set_uuid(self, method_uuid: Uuid) -> Self {
         CfgBlockIndex {
@@ -144,11 +243,15 @@ impl CfgMethod {
             uuid: Uuid::new_v4(),
             method_name,
             formal_returns,
+            pres: vec![],
+            posts: vec![],
             local_vars,
             labels: FxHashSet::default(),
             reserved_labels: FxHashSet::from_iter(reserved_labels),
             basic_blocks: vec![],
             basic_blocks_labels: vec![],
+            return_label: RETURN_LABEL.to_string(),
+            return_path: None,
             fresh_var_index: 0,
             fresh_label_index: 0,
         }
@@ -166,10 +269,154 @@ impl CfgMethod {
         &self.basic_blocks_labels
     }
 
+    pub fn return_label(&self) -> &str {
+        &self.return_label
+    }
+
+    /// Use `label` instead of the default `end_of_method` to mark the end of
+    /// the method, e.g. to avoid a clash with a block label. Must be called
+    /// before any block with this label is added.
+    pub fn set_return_label(&mut self, label: &str) {
+        assert!(
+            self.is_fresh_local_name(label) && !self.reserved_labels.contains(label),
+            "label {} is not fresh",
+            label
+        );
+        self.return_label = label.to_string();
+    }
+
     pub fn get_formal_returns(&self) -> &Vec<LocalVar> {
         &self.formal_returns
     }
 
+    pub fn get_preconditions(&self) -> &Vec<Expr> {
+        &self.pres
+    }
+
+    pub fn get_postconditions(&self) -> &Vec<Expr> {
+        &self.posts
+    }
+
+    /// Add a `requires` clause to the method. The body of the method is not
+    /// affected.
+    pub fn add_precondition(&mut self, expr: Expr) {
+        self.pres.push(expr);
+    }
+
+    /// Add an `ensures` clause to the method. The body of the method is not
+    /// affected.
+    pub fn add_postcondition(&mut self, expr: Expr) {
+        self.try_add_postcondition(expr)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `add_postcondition`, but reports a free variable of `expr` that is
+    /// not one of the formal returns as an error instead of panicking. The
+    /// method has no formal arguments, so the formal returns are the only
+    /// variables in scope of an `ensures` clause. A variable matches a formal
+    /// return only if both its name and its type are the same.
+    pub fn try_add_postcondition(&mut self, expr: Expr) -> CfgResult<()> {
+        self.check_return_variables(&expr)?;
+        self.posts.push(expr);
+        Ok(())
+    }
+
+    fn check_return_variables(&self, expr: &Expr) -> CfgResult<()> {
+        let mut collector = FreeVariablesCollector::default();
+        collector.walk(expr);
+        if let Some(var) = collector
+            .free
+            .into_iter()
+            .find(|var| !self.formal_returns.contains(var))
+        {
+            return Err(CfgError::UnknownReturnVariable(var.name));
+        }
+        Ok(())
+    }
+
+    /// Add an `ensures` clause that only needs to hold when the method
+    /// returns through the given block, which must have a `Return` successor.
+    ///
+    /// The returning blocks are told apart by an `Int` formal return that is
+    /// set to zero at the start of the method and to a distinct positive
+    /// number by each block with branch-specific postconditions. The clause
+    /// becomes a postcondition of the method that is guarded by the number of
+    /// its block, so the postconditions of the method are a case split over
+    /// the returning block. Because the number is a formal return, the first
+    /// call adds a return value to the signature of the encoded Viper method.
+    ///
+    /// Like `try_add_postcondition`, this reports a free variable of `expr`
+    /// that is not one of the formal returns as an error. The method is left
+    /// unchanged if an error is reported.
+    pub fn add_return_postcondition(&mut self, index: CfgBlockIndex, expr: Expr) -> CfgResult<()> {
+        assert_eq!(
+            self.uuid, index.method_uuid,
+            "The provided CfgBlockIndex doesn't belong to this CfgMethod"
+        );
+        if !self.basic_blocks[index.block_index].successor.is_return() {
+            return Err(CfgError::NotAReturnBlock(
+                self.basic_blocks_labels[index.block_index].clone(),
+            ));
+        }
+        self.check_return_variables(&expr)?;
+        let return_path = match &self.return_path {
+            Some(return_path) => return_path.clone(),
+            None => {
+                let mut name = "return_path".to_string();
+                let mut suffix = 1;
+                while !self.is_fresh_local_name(&name) || self.reserved_labels.contains(&name) {
+                    name = format!("return_path_{}", suffix);
+                    suffix += 1;
+                }
+                let return_path = LocalVar::new(name, Type::Int);
+                self.formal_returns.push(return_path.clone());
+                self.return_path = Some(return_path.clone());
+                let entry = self.block_index(0);
+                self.prepend_stmt(entry, Self::assign_return_path(&return_path, 0));
+                return_path
+            }
+        };
+        let path_of_block = |block: &CfgBlock| {
+            block.stmts.iter().find_map(|stmt| match stmt {
+                Stmt::Assign(Assign {
+                    target: Expr::Local(Local { variable, .. }),
+                    source:
+                        Expr::Const(ConstExpr {
+                            value: Const::Int(path),
+                            ..
+                        }),
+                    ..
+                }) if variable == &return_path && *path > 0 => Some(*path),
+                _ => None,
+            })
+        };
+        let path = match path_of_block(&self.basic_blocks[index.block_index]) {
+            Some(path) => path,
+            None => {
+                let path = self
+                    .basic_blocks
+                    .iter()
+                    .filter_map(path_of_block)
+                    .max()
+                    .unwrap_or(0)
+                    + 1;
+                self.add_stmt(index, Self::assign_return_path(&return_path, path));
+                path
+            }
+        };
+        let guard = Expr::eq_cmp(Expr::local(return_path), path.into());
+        self.posts.push(Expr::implies(guard, expr));
+        Ok(())
+    }
+
+    fn assign_return_path(return_path: &LocalVar, path: i64) -> Stmt {
+        Stmt::Assign(Assign {
+            target: Expr::local(return_path.clone()),
+            source: path.into(),
+            kind: AssignKind::Copy,
+        })
+    }
+
     pub(super) fn block_index(&self, index: usize) -> CfgBlockIndex {
         CfgBlockIndex {
             method_uuid: self.uuid,
@@ -232,8 +479,30 @@ impl CfgMethod {
     }
 
     pub fn add_local_var(&mut self, name: &str, typ: Type) {
-        assert!(self.is_fresh_local_name(name));
+        self.try_add_local_var(name, typ)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `add_local_var`, but reports a name clash with an existing local
+    /// variable, formal return, or label as an error instead of panicking.
+    pub fn try_add_local_var(&mut self, name: &str, typ: Type) -> CfgResult<()> {
+        if !self.is_fresh_local_name(name) {
+            return Err(CfgError::DuplicateVariable(name.to_string()));
+        }
         self.local_vars.push(LocalVar::new(name, typ));
+        Ok(())
+    }
+
+    /// Check that the formal returns and local variables, e.g. the ones given
+    /// to `CfgMethod::new`, have pairwise distinct names.
+    pub fn check_local_vars(&self) -> CfgResult<()> {
+        let mut names = FxHashSet::default();
+        for var in self.formal_returns.iter().chain(self.local_vars.iter()) {
+            if !names.insert(&var.name) {
+                return Err(CfgError::DuplicateVariable(var.name.clone()));
+            }
+        }
+        Ok(())
     }
 
     pub fn add_formal_return(&mut self, name: &str, typ: Type) {
@@ -241,8 +510,30 @@ impl CfgMethod {
         self.formal_returns.push(LocalVar::new(name, typ));
     }
 
+    /// Append a statement to an already existing block. This allows a block to
+    /// be grown incrementally after its creation by `add_block`.
     pub fn add_stmt(&mut self, index: CfgBlockIndex, stmt: Stmt) {
-        for label_name in gather_labels(&stmt) {
+        assert_eq!(
+            self.uuid, index.method_uuid,
+            "The provided CfgBlockIndex doesn't belong to this CfgMethod"
+        );
+        self.register_stmt_labels(&stmt);
+        self.basic_blocks[index.block_index].stmts.push(stmt);
+    }
+
+    /// Insert a statement before all the statements of an already existing
+    /// block, e.g. to havoc or inhale something on entry to the block.
+    pub fn prepend_stmt(&mut self, index: CfgBlockIndex, stmt: Stmt) {
+        assert_eq!(
+            self.uuid, index.method_uuid,
+            "The provided CfgBlockIndex doesn't belong to this CfgMethod"
+        );
+        self.register_stmt_labels(&stmt);
+        self.basic_blocks[index.block_index].stmts.insert(0, stmt);
+    }
+
+    fn register_stmt_labels(&mut self, stmt: &Stmt) {
+        for label_name in gather_labels(stmt) {
             assert!(
                 self.is_fresh_local_name(&label_name),
                 "label {} is not fresh",
@@ -250,7 +541,6 @@ impl CfgMethod {
             );
             self.labels.insert(label_name);
         }
-        self.basic_blocks[index.block_index].stmts.push(stmt);
     }
 
     pub fn add_stmts(&mut self, index: CfgBlockIndex, stmts: Vec<Stmt>) {
@@ -260,24 +550,65 @@ impl CfgMethod {
     }
 
     pub fn add_block(&mut self, label: &str, stmts: Vec<Stmt>) -> CfgBlockIndex {
-        assert!(label.chars().take(1).all(|c| c.is_alphabetic() || c == '_'));
-        assert!(label
-            .chars()
-            .skip(1)
-            .all(|c| c.is_alphanumeric() || c == '_'));
-        assert!(
-            self.basic_blocks_labels.iter().all(|l| l != label),
-            "Label {} is already used",
-            label
-        );
-        assert!(label != RETURN_LABEL);
+        self.add_block_with_pos(label, stmts, Position::default())
+    }
+
+    /// Like `add_block`, but takes the statements from an iterator, so that
+    /// the caller does not need to collect them first.
+    pub fn add_block_stmts(
+        &mut self,
+        label: &str,
+        stmts: impl IntoIterator<Item = Stmt>,
+    ) -> CfgBlockIndex {
+        self.add_block(label, stmts.into_iter().collect())
+    }
+
+    pub fn add_block_with_pos(
+        &mut self,
+        label: &str,
+        stmts: Vec<Stmt>,
+        position: Position,
+    ) -> CfgBlockIndex {
+        self.try_add_block_with_pos(label, stmts, position)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `add_block`, but reports an invalid, duplicate, or reserved label
+    /// as an error instead of panicking.
+    pub fn try_add_block(&mut self, label: &str, stmts: Vec<Stmt>) -> CfgResult<CfgBlockIndex> {
+        self.try_add_block_with_pos(label, stmts, Position::default())
+    }
+
+    pub fn try_add_block_with_pos(
+        &mut self,
+        label: &str,
+        stmts: Vec<Stmt>,
+        position: Position,
+    ) -> CfgResult<CfgBlockIndex> {
+        let is_valid_identifier = label.chars().take(1).all(|c| c.is_alphabetic() || c == '_')
+            && label
+                .chars()
+                .skip(1)
+                .all(|c| c.is_alphanumeric() || c == '_');
+        if !is_valid_identifier {
+            return Err(CfgError::InvalidLabel(label.to_string()));
+        }
+        if self.basic_blocks_labels.iter().any(|l| l == label) {
+            return Err(CfgError::DuplicateLabel(label.to_string()));
+        }
+        if label == self.return_label {
+            return Err(CfgError::ReservedLabel(label.to_string()));
+        }
         let index = self.basic_blocks.len();
         self.basic_blocks_labels.push(label.to_string());
         self.basic_blocks.push(CfgBlock {
             stmts,
             successor: Successor::Undefined,
+            position,
+            exhale_on_entry: vec![],
+            inhale_after_label: vec![],
         });
-        self.block_index(index)
+        Ok(self.block_index(index))
     }
 
     #[allow(dead_code)]
@@ -298,6 +629,85 @@ impl CfgMethod {
         self.basic_blocks[index.block_index].successor = successor;
     }
 
+    /// Redirects every edge that points at `from` to `to`: the `Goto`
+    /// targets, the `GotoSwitch` arms, and the `GotoSwitch` defaults. The
+    /// block `from` itself is kept, even if it becomes unreachable.
+    pub fn redirect_edges(&mut self, from: CfgBlockIndex, to: CfgBlockIndex) {
+        assert_eq!(
+            self.uuid, from.method_uuid,
+            "The provided CfgBlockIndex doesn't belong to this CfgMethod"
+        );
+        for block in &mut self.basic_blocks {
+            let successor = std::mem::replace(&mut block.successor, Successor::Undefined);
+            block.successor = successor.replace_target(from, to);
+        }
+    }
+
+    /// Sets the invariants of the block: `exhale_on_entry` is exhaled on
+    /// every edge that enters the block and `inhale_after_label` is inhaled
+    /// right after its label.
+    pub fn set_block_invariants(
+        &mut self,
+        index: CfgBlockIndex,
+        exhale_on_entry: Vec<Expr>,
+        inhale_after_label: Vec<Expr>,
+    ) {
+        assert_eq!(
+            self.uuid, index.method_uuid,
+            "The provided CfgBlockIndex doesn't belong to this CfgMethod"
+        );
+        let block = &mut self.basic_blocks[index.block_index];
+        block.exhale_on_entry = exhale_on_entry;
+        block.inhale_after_label = inhale_after_label;
+    }
+
+    /// Makes the invariants inhaled after block labels well-defined by
+    /// asserting the preconditions of the pure functions they call. A
+    /// precondition is checked under the pure conditions that guard the call,
+    /// for example, `a ==> f(x)` requires the precondition of `f` only if `a`
+    /// holds, while the permissions on the left of `acc(..) && f(x)` do not
+    /// guard it. The inhales are moved to the start of the block statements,
+    /// each followed by the checks of its invariant, so that the checks can
+    /// use the inhaled permissions and a failing check points at the call.
+    /// Calls inside quantifiers and `let` expressions are not checked, and the
+    /// invariants exhaled on entry are already checked by Viper.
+    pub fn insert_invariant_precondition_checks(&mut self, functions: &[Function]) {
+        for block in &mut self.basic_blocks {
+            let mut stmts = Vec::new();
+            let mut has_checks = false;
+            for invariant in &block.inhale_after_label {
+                let mut collector = PreconditionCollector {
+                    functions,
+                    guards: Vec::new(),
+                    checks: Vec::new(),
+                };
+                collector.walk(invariant);
+                stmts.push(Stmt::inhale(invariant.clone()));
+                for (check, position) in collector.checks {
+                    let position = if position == Position::default() {
+                        invariant.pos()
+                    } else {
+                        position
+                    };
+                    stmts.push(Stmt::Assert(Assert {
+                        expr: check,
+                        position,
+                    }));
+                    has_checks = true;
+                }
+            }
+            if has_checks {
+                block.inhale_after_label.clear();
+                stmts.append(&mut block.stmts);
+                block.stmts = stmts;
+            }
+        }
+    }
+
+    /// Returns the blocks that have an edge to `target_index`, i.e. whose
+    /// `Goto` target, `GotoSwitch` arm, or `GotoSwitch` default is the given
+    /// block. Each predecessor is reported once, even if it has several edges
+    /// to the target. `Return` and `Undefined` successors contribute no edges.
     pub fn get_preceding(&self, target_index: CfgBlockIndex) -> Vec<CfgBlockIndex> {
         assert_eq!(
             self.uuid, target_index.method_uuid,
@@ -311,6 +721,9 @@ impl CfgMethod {
             .collect()
     }
 
+    /// Returns the predecessors of all blocks that have at least one
+    /// predecessor, keyed by block index. See `get_preceding` for the
+    /// predecessors of a single block.
     #[allow(dead_code)]
     pub fn predecessors(&self) -> FxHashMap<usize, Vec<usize>> {
         let mut result = FxHashMap::default();
@@ -330,11 +743,825 @@ impl CfgMethod {
             .collect()
     }
 
-    #[allow(dead_code)]
-    pub fn get_block_label(&self, index: CfgBlockIndex) -> &str {
+    /// Returns the label of the given block.
+    pub fn block_label(&self, index: CfgBlockIndex) -> &str {
+        assert_eq!(
+            self.uuid, index.method_uuid,
+            "The provided CfgBlockIndex doesn't belong to this CfgMethod"
+        );
         &self.basic_blocks_labels[index.block_index]
     }
 
+    /// Returns for each block whether it can be reached from the entry block.
+    pub fn get_reachable_blocks(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.basic_blocks.len()];
+        if self.basic_blocks.is_empty() {
+            return reachable;
+        }
+        let mut to_visit = VecDeque::new();
+        reachable[0] = true;
+        to_visit.push_back(0);
+        while let Some(curr_index) = to_visit.pop_front() {
+            for succ in self.basic_blocks[curr_index].successor.get_following() {
+                if !reachable[succ.index()] {
+                    reachable[succ.index()] = true;
+                    to_visit.push_back(succ.index());
+                }
+            }
+        }
+        reachable
+    }
+
+    /// Returns the immediate dominator of each block that is reachable from
+    /// the entry block. The entry block is its own immediate dominator.
+    ///
+    /// The dominators are computed with the iterative algorithm of Cooper,
+    /// Harvey, and Kennedy over the successor edges.
+    pub fn dominators(&self) -> FxHashMap<CfgBlockIndex, CfgBlockIndex> {
+        if self.basic_blocks.is_empty() {
+            return FxHashMap::default();
+        }
+        // Compute a postorder of the reachable blocks.
+        let mut postorder = Vec::new();
+        let mut visited = vec![false; self.basic_blocks.len()];
+        let mut stack = vec![(0, self.basic_blocks[0].successor.get_following())];
+        visited[0] = true;
+        while let Some((index, successors)) = stack.last_mut() {
+            if let Some(successor) = successors.pop() {
+                let successor = successor.index();
+                if !visited[successor] {
+                    visited[successor] = true;
+                    let following = self.basic_blocks[successor].successor.get_following();
+                    stack.push((successor, following));
+                }
+            } else {
+                postorder.push(*index);
+                stack.pop();
+            }
+        }
+        let mut postorder_number = vec![None; self.basic_blocks.len()];
+        for (number, &index) in postorder.iter().enumerate() {
+            postorder_number[index] = Some(number);
+        }
+        let predecessors = self.predecessors();
+
+        let mut idom: Vec<Option<usize>> = vec![None; self.basic_blocks.len()];
+        idom[0] = Some(0);
+        let intersect = |idom: &[Option<usize>], mut left: usize, mut right: usize| {
+            while left != right {
+                while postorder_number[left] < postorder_number[right] {
+                    left = idom[left].unwrap();
+                }
+                while postorder_number[right] < postorder_number[left] {
+                    right = idom[right].unwrap();
+                }
+            }
+            left
+        };
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &index in postorder.iter().rev().skip(1) {
+                let mut new_idom = None;
+                for &predecessor in predecessors.get(&index).into_iter().flatten() {
+                    if idom[predecessor].is_none() {
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => predecessor,
+                        Some(current) => intersect(&idom, predecessor, current),
+                    });
+                }
+                if idom[index] != new_idom {
+                    idom[index] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        idom.into_iter()
+            .enumerate()
+            .filter_map(|(index, dominator)| {
+                dominator.map(|dominator| (self.block_index(index), self.block_index(dominator)))
+            })
+            .collect()
+    }
+
+    /// Check that no `GotoSwitch` is degenerate, i.e. has no guarded targets
+    /// and thus always jumps to its default target.
+    pub fn check_switches(&self) -> CfgResult<()> {
+        for (index, block) in self.basic_blocks.iter().enumerate() {
+            if let Successor::GotoSwitch(guarded_targets, _) = &block.successor {
+                if guarded_targets.is_empty() {
+                    return Err(CfgError::EmptySwitch(
+                        self.basic_blocks_labels[index].clone(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that every block can be reached from the entry block. Blocks that
+    /// are never reached result in dead Viper code.
+    pub fn check_reachable(&self) -> CfgResult<()> {
+        let unreachable_labels: Vec<String> = self
+            .get_reachable_blocks()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, reachable)| !reachable)
+            .map(|(index, _)| self.basic_blocks_labels[index].clone())
+            .collect();
+        if unreachable_labels.is_empty() {
+            Ok(())
+        } else {
+            Err(CfgError::UnreachableBlocks(unreachable_labels))
+        }
+    }
+
+    /// Returns the blocks that are reachable from the entry block but whose
+    /// successor was never set, i.e. is still `Undefined`.
+    /// Blocks that end in `IntentionalUnreachable` are not reported.
+    pub fn find_missing_successors(&self) -> Vec<CfgBlockIndex> {
+        self.get_reachable_blocks()
+            .into_iter()
+            .enumerate()
+            .filter(|&(index, reachable)| {
+                reachable && self.basic_blocks[index].successor == Successor::Undefined
+            })
+            .map(|(index, _)| self.block_index(index))
+            .collect()
+    }
+
+    /// Check that every block reachable from the entry block has a successor.
+    /// See `find_missing_successors`.
+    pub fn check_successors(&self) -> CfgResult<()> {
+        let missing_labels: Vec<String> = self
+            .find_missing_successors()
+            .into_iter()
+            .map(|index| self.basic_blocks_labels[index.index()].clone())
+            .collect();
+        if missing_labels.is_empty() {
+            Ok(())
+        } else {
+            Err(CfgError::MissingSuccessors(missing_labels))
+        }
+    }
+
+    /// Returns the targets of the back edges, i.e. the edges whose target
+    /// dominates their source, that have neither `exhale_on_entry` nor
+    /// `inhale_after_label` invariants. Each loop head is reported once, in
+    /// the order of the blocks. Blocks that are unreachable from the entry
+    /// block are ignored.
+    pub fn loops_without_invariants(&self) -> Vec<CfgBlockIndex> {
+        let dominators = self.dominators();
+        let dominates = |dominator: CfgBlockIndex, mut index: CfgBlockIndex| loop {
+            if index == dominator {
+                return true;
+            }
+            let immediate_dominator = dominators[&index];
+            if immediate_dominator == index {
+                return false;
+            }
+            index = immediate_dominator;
+        };
+        let mut loop_heads = vec![false; self.basic_blocks.len()];
+        for (index, block) in self.basic_blocks.iter().enumerate() {
+            let source = self.block_index(index);
+            if !dominators.contains_key(&source) {
+                continue;
+            }
+            for target in block.successor.get_following() {
+                if dominates(target, source) {
+                    loop_heads[target.index()] = true;
+                }
+            }
+        }
+        loop_heads
+            .into_iter()
+            .enumerate()
+            .filter(|&(index, is_loop_head)| {
+                let block = &self.basic_blocks[index];
+                is_loop_head
+                    && block.exhale_on_entry.is_empty()
+                    && block.inhale_after_label.is_empty()
+            })
+            .map(|(index, _)| self.block_index(index))
+            .collect()
+    }
+
+    /// Check that every loop head has invariants. See
+    /// `loops_without_invariants`.
+    pub fn check_loop_invariants(&self) -> CfgResult<()> {
+        let loop_head_labels: Vec<String> = self
+            .loops_without_invariants()
+            .into_iter()
+            .map(|index| self.basic_blocks_labels[index.index()].clone())
+            .collect();
+        if loop_head_labels.is_empty() {
+            Ok(())
+        } else {
+            Err(CfgError::LoopsWithoutInvariants(loop_head_labels))
+        }
+    }
+
+    /// Returns the reachable blocks whose `Goto` target or `GotoSwitch`
+    /// default is the block itself and that have neither `exhale_on_entry`
+    /// nor `inhale_after_label` invariants. Such a block loops forever once
+    /// its switch falls through, which is almost always a bug in the
+    /// encoding; a self loop that carries invariants is not reported.
+    pub fn find_self_loops_without_invariants(&self) -> Vec<CfgBlockIndex> {
+        self.get_reachable_blocks()
+            .into_iter()
+            .enumerate()
+            .filter(|&(index, reachable)| {
+                let block = &self.basic_blocks[index];
+                let loops_unconditionally = match block.successor {
+                    Successor::Goto(target) | Successor::GotoSwitch(_, target) => {
+                        target.index() == index
+                    }
+                    _ => false,
+                };
+                reachable
+                    && loops_unconditionally
+                    && block.exhale_on_entry.is_empty()
+                    && block.inhale_after_label.is_empty()
+            })
+            .map(|(index, _)| self.block_index(index))
+            .collect()
+    }
+
+    /// Check that no block unconditionally jumps to itself without
+    /// invariants. See `find_self_loops_without_invariants`.
+    pub fn check_self_loops(&self) -> CfgResult<()> {
+        let self_loop_labels: Vec<String> = self
+            .find_self_loops_without_invariants()
+            .into_iter()
+            .map(|index| self.basic_blocks_labels[index.index()].clone())
+            .collect();
+        if self_loop_labels.is_empty() {
+            Ok(())
+        } else {
+            Err(CfgError::SelfLoopsWithoutInvariants(self_loop_labels))
+        }
+    }
+
+    /// Returns the names of the formal returns that might not be assigned
+    /// on some path from the entry block to a `Return` successor, in the
+    /// order of their declaration. Assigning to a field of a formal return
+    /// or using it as a target of a method call counts as an assignment, and
+    /// so does an assignment in only one branch of an `if` statement. Paths
+    /// that assert or inhale `false` are ignored because they never return.
+    ///
+    /// This is a heuristic: a reported formal return has an unconstrained
+    /// value on some path, which usually indicates a bug in the encoding.
+    pub fn unassigned_returns(&self) -> Vec<String> {
+        if self.formal_returns.is_empty() || self.basic_blocks.is_empty() {
+            return Vec::new();
+        }
+        // The variables assigned by each block, or `None` if the block never
+        // reaches its successor.
+        let assigned_by_block: Vec<Option<FxHashSet<String>>> = self
+            .basic_blocks
+            .iter()
+            .map(|block| {
+                let mut collector = AssignedVarsCollector::default();
+                for stmt in &block.stmts {
+                    collector.walk(stmt);
+                }
+                if collector.diverges {
+                    None
+                } else {
+                    Some(collector.assigned)
+                }
+            })
+            .collect();
+        // The variables assigned on all paths to the entry of each block, or
+        // `None` if the block was not reached yet.
+        let mut assigned_on_entry: Vec<Option<FxHashSet<String>>> =
+            vec![None; self.basic_blocks.len()];
+        assigned_on_entry[0] = Some(FxHashSet::default());
+        let mut to_visit = VecDeque::from(vec![0]);
+        while let Some(curr_index) = to_visit.pop_front() {
+            let mut assigned = assigned_on_entry[curr_index].clone().unwrap();
+            match &assigned_by_block[curr_index] {
+                Some(assigned_by_curr) => assigned.extend(assigned_by_curr.iter().cloned()),
+                None => continue,
+            }
+            for succ in self.basic_blocks[curr_index].successor.get_following() {
+                let updated = match &assigned_on_entry[succ.index()] {
+                    Some(old) => old.intersection(&assigned).cloned().collect(),
+                    None => assigned.clone(),
+                };
+                if assigned_on_entry[succ.index()].as_ref() != Some(&updated) {
+                    assigned_on_entry[succ.index()] = Some(updated);
+                    to_visit.push_back(succ.index());
+                }
+            }
+        }
+        let mut unassigned = FxHashSet::default();
+        for (index, block) in self.basic_blocks.iter().enumerate() {
+            if !block.successor.is_return() {
+                continue;
+            }
+            if let (Some(on_entry), Some(by_block)) =
+                (&assigned_on_entry[index], &assigned_by_block[index])
+            {
+                for var in &self.formal_returns {
+                    if !on_entry.contains(&var.name) && !by_block.contains(&var.name) {
+                        unassigned.insert(var.name.as_str());
+                    }
+                }
+            }
+        }
+        self.formal_returns
+            .iter()
+            .filter(|var| unassigned.contains(var.name.as_str()))
+            .map(|var| var.name.clone())
+            .collect()
+    }
+
+    /// Simplify the `GotoSwitch` successors whose guards are boolean literals.
+    /// Arms guarded by `false` are dropped and an arm guarded by `true` makes
+    /// all later arms and the default target dead. A switch with no arms left
+    /// becomes a `Goto` of the taken target; the position of the block is kept.
+    /// The targets that are no longer jumped to can be removed afterwards by
+    /// `remove_unreachable_blocks`. Returns whether any switch was changed.
+    pub fn fold_constant_switches(&mut self) -> bool {
+        let mut changed = false;
+        for block in &mut self.basic_blocks {
+            if let Successor::GotoSwitch(guarded_targets, default_target) = &block.successor {
+                let mut remaining_targets = Vec::with_capacity(guarded_targets.len());
+                let mut taken_target = *default_target;
+                for (guard, target) in guarded_targets {
+                    match guard {
+                        Expr::Const(ConstExpr {
+                            value: Const::Bool(false),
+                            ..
+                        }) => {}
+                        Expr::Const(ConstExpr {
+                            value: Const::Bool(true),
+                            ..
+                        }) => {
+                            taken_target = *target;
+                            break;
+                        }
+                        _ => remaining_targets.push((guard.clone(), *target)),
+                    }
+                }
+                let successor = if remaining_targets.is_empty() {
+                    Successor::Goto(taken_target)
+                } else {
+                    Successor::GotoSwitch(remaining_targets, taken_target)
+                };
+                if successor != block.successor {
+                    block.successor = successor;
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Sort the arms of the `GotoSwitch` successors by their constants if all
+    /// guards compare the same expression with pairwise distinct integer or
+    /// boolean literals, like the switches on discriminants do. Such guards
+    /// are disjoint, so the first-match semantics does not depend on the
+    /// order of the arms; all other switches are left untouched. This makes
+    /// the encoding independent of the order in which the arms were built.
+    /// Returns whether any switch was changed.
+    pub fn sort_switch_arms(&mut self) -> bool {
+        let mut changed = false;
+        for block in &mut self.basic_blocks {
+            if let Successor::GotoSwitch(guarded_targets, _) = &mut block.successor {
+                let keys: Option<Vec<_>> = guarded_targets
+                    .iter()
+                    .map(|(guard, _)| switch_arm_key(guard))
+                    .collect();
+                let mut values = match keys {
+                    Some(keys) if keys.iter().all(|(scrutinee, _)| *scrutinee == keys[0].0) => {
+                        keys.into_iter().map(|(_, value)| value).collect::<Vec<_>>()
+                    }
+                    _ => continue,
+                };
+                if values.windows(2).all(|pair| pair[0] < pair[1]) {
+                    continue;
+                }
+                values.sort_unstable();
+                if values.windows(2).any(|pair| pair[0] == pair[1]) {
+                    // The guards overlap, so their order matters.
+                    continue;
+                }
+                guarded_targets.sort_by_key(|(guard, _)| switch_arm_key(guard).unwrap().1);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Remove all blocks that cannot be reached from the entry block. The
+    /// remaining blocks keep their relative order, but get new indices: all
+    /// `CfgBlockIndex` values obtained before calling this method are
+    /// invalidated.
+    pub fn remove_unreachable_blocks(&mut self) {
+        let reachable = self.get_reachable_blocks();
+        if reachable.iter().all(|&r| r) {
+            return;
+        }
+        self.retain_blocks(&reachable);
+    }
+
+    /// Keep only the blocks for which `keep` is true. None of the kept blocks
+    /// may jump to a removed one.
+    fn retain_blocks(&mut self, keep: &[bool]) {
+        let mut new_indices = vec![None; self.basic_blocks.len()];
+        let mut next_index = 0;
+        for (index, &is_kept) in keep.iter().enumerate() {
+            if is_kept {
+                new_indices[index] = Some(next_index);
+                next_index += 1;
+            }
+        }
+        let old_blocks = std::mem::take(&mut self.basic_blocks);
+        let old_labels = std::mem::take(&mut self.basic_blocks_labels);
+        for ((block, label), &is_kept) in old_blocks.into_iter().zip(old_labels).zip(keep) {
+            if !is_kept {
+                continue;
+            }
+            let uuid = self.uuid;
+            let successor = block.successor.map_targets(|target| CfgBlockIndex {
+                method_uuid: uuid,
+                block_index: new_indices[target.block_index]
+                    .expect("a kept block jumps to a removed block"),
+            });
+            self.basic_blocks.push(CfgBlock { successor, ..block });
+            self.basic_blocks_labels.push(label);
+        }
+    }
+
+    /// Merge every block that is entered only by a `Goto` from a single
+    /// predecessor into that predecessor: the statements are concatenated and
+    /// the predecessor adopts the successor of the merged block. The entry
+    /// block, blocks with invariants, and blocks whose label is referenced by
+    /// a labelled `old` expression are never merged into their predecessor.
+    ///
+    /// Like `remove_unreachable_blocks`, this invalidates all `CfgBlockIndex`
+    /// values obtained before calling this method. Returns whether any block
+    /// was merged.
+    pub fn inline_single_use_blocks(&mut self) -> bool {
+        let referenced_labels = self.referenced_labels();
+        let predecessors = self.predecessors();
+        let mut keep = vec![true; self.basic_blocks.len()];
+        for index in 0..self.basic_blocks.len() {
+            if !keep[index] {
+                continue;
+            }
+            // The block whose successor `index` currently has.
+            let mut tail = index;
+            while let Successor::Goto(target) = self.basic_blocks[index].successor {
+                let target_index = target.block_index;
+                let target_block = &self.basic_blocks[target_index];
+                if target_index == 0
+                    || target_index == index
+                    || !keep[target_index]
+                    || predecessors[&target_index] != [tail]
+                    || !target_block.exhale_on_entry.is_empty()
+                    || !target_block.inhale_after_label.is_empty()
+                    || referenced_labels.contains(&self.basic_blocks_labels[target_index])
+                {
+                    break;
+                }
+                keep[target_index] = false;
+                let stmts = std::mem::take(&mut self.basic_blocks[target_index].stmts);
+                let successor = std::mem::replace(
+                    &mut self.basic_blocks[target_index].successor,
+                    Successor::Undefined,
+                );
+                let block = &mut self.basic_blocks[index];
+                block.stmts.extend(stmts);
+                block.successor = successor;
+                tail = target_index;
+            }
+        }
+        if keep.iter().all(|&k| k) {
+            return false;
+        }
+        self.retain_blocks(&keep);
+        true
+    }
+
+    /// The labels used by labelled `old` expressions in the body, the block
+    /// invariants, and the postconditions of the method.
+    fn referenced_labels(&self) -> FxHashSet<String> {
+        let mut collector = ReferencedLabelsCollector::default();
+        for block in &self.basic_blocks {
+            for stmt in &block.stmts {
+                StmtWalker::walk(&mut collector, stmt);
+            }
+            for expr in block
+                .exhale_on_entry
+                .iter()
+                .chain(&block.inhale_after_label)
+            {
+                ExprWalker::walk(&mut collector, expr);
+            }
+        }
+        for expr in &self.posts {
+            ExprWalker::walk(&mut collector, expr);
+        }
+        collector.labels
+    }
+
+    /// Bring the CFG into a canonical shape. Empty blocks that only jump to
+    /// another block are collapsed into their target, which also flattens
+    /// chains of such jumps into a single `Goto`. Blocks whose label is
+    /// referenced by a labelled `old` expression are kept. The guards of `GotoSwitch`
+    /// successors are kept intact; only their targets are redirected.
+    ///
+    /// After the call the following invariants hold and can be relied upon by
+    /// later passes:
+    ///
+    /// 1. Every block is reachable from the entry block.
+    /// 2. Every block other than the entry block that has a `Goto` successor
+    ///    has statements or block invariants, unless its label is referenced
+    ///    or it is part of a cycle of empty blocks.
+    /// 3. The entry block is still the first block and the relative order of
+    ///    the remaining blocks is preserved.
+    ///
+    /// Like `remove_unreachable_blocks`, this invalidates all `CfgBlockIndex`
+    /// values obtained before calling this method.
+    pub fn normalize(&mut self) {
+        let referenced_labels = self.referenced_labels();
+        let forwarded_to: Vec<Option<usize>> = self
+            .basic_blocks
+            .iter()
+            .enumerate()
+            .map(|(index, block)| match block.successor {
+                Successor::Goto(target)
+                    if index != 0
+                        && block.stmts.is_empty()
+                        && block.exhale_on_entry.is_empty()
+                        && block.inhale_after_label.is_empty()
+                        && !referenced_labels.contains(&self.basic_blocks_labels[index]) =>
+                {
+                    Some(target.block_index)
+                }
+                _ => None,
+            })
+            .collect();
+        let resolve = |mut index: usize| {
+            let mut visited = FxHashSet::default();
+            while let Some(next) = forwarded_to[index] {
+                if !visited.insert(index) {
+                    // A cycle of empty blocks: keep jumping into it.
+                    break;
+                }
+                index = next;
+            }
+            index
+        };
+        let uuid = self.uuid;
+        for block in &mut self.basic_blocks {
+            let successor = std::mem::replace(&mut block.successor, Successor::Undefined);
+            block.successor = successor.map_targets(|target| CfgBlockIndex {
+                method_uuid: uuid,
+                block_index: resolve(target.block_index),
+            });
+        }
+        self.remove_unreachable_blocks();
+    }
+
+    /// Append the blocks of `other` to this method and return their new
+    /// indices, in the order of `other`. The return successors of the spliced
+    /// blocks are redirected to `continuation`. Block and statement labels of
+    /// `other` that clash with the names of this method get a numeric suffix,
+    /// and the labelled `old[..]` expressions of `other` are updated to match.
+    /// The local variables and formal returns of `other` become local
+    /// variables of this method; variables of the same name and type are
+    /// shared, so clashing ones should be renamed with `rename_local`
+    /// beforehand. The contract of `other` is dropped.
+    ///
+    /// Fails without modifying this method if a variable of `other` has the
+    /// name of a variable of a different type or of a label of this method.
+    pub fn splice(
+        &mut self,
+        other: CfgMethod,
+        continuation: CfgBlockIndex,
+    ) -> CfgResult<Vec<CfgBlockIndex>> {
+        assert_eq!(
+            self.uuid, continuation.method_uuid,
+            "The provided CfgBlockIndex doesn't belong to this CfgMethod"
+        );
+        let mut new_vars: Vec<LocalVar> = Vec::new();
+        for var in other.formal_returns.iter().chain(&other.local_vars) {
+            let existing = self
+                .formal_returns
+                .iter()
+                .chain(&self.local_vars)
+                .chain(&new_vars)
+                .find(|existing| existing.name == var.name);
+            match existing {
+                Some(existing) if existing.typ != var.typ => {
+                    return Err(CfgError::ConflictingVariableType(var.name.clone()));
+                }
+                Some(_) => {}
+                None if !self.is_fresh_local_name(&var.name)
+                    || self.reserved_labels.contains(&var.name) =>
+                {
+                    return Err(CfgError::DuplicateVariable(var.name.clone()));
+                }
+                None => new_vars.push(var.clone()),
+            }
+        }
+        self.local_vars.extend(new_vars);
+
+        // Choose the new names of all labels of `other` before rewriting any
+        // of them, so that a suffixed name never clashes with another label of
+        // `other`.
+        let other_labels: Vec<String> = other
+            .basic_blocks_labels
+            .iter()
+            .cloned()
+            .chain(
+                other
+                    .basic_blocks
+                    .iter()
+                    .flat_map(|block| block.stmts.iter().flat_map(gather_labels)),
+            )
+            .collect();
+        let mut renaming: FxHashMap<String, String> = FxHashMap::default();
+        let mut taken: FxHashSet<String> = FxHashSet::default();
+        for label in &other_labels {
+            let mut fresh_label = label.clone();
+            let mut suffix = 1;
+            while !self.is_fresh_local_name(&fresh_label)
+                || self.reserved_labels.contains(&fresh_label)
+                || fresh_label == self.return_label
+                || taken.contains(&fresh_label)
+                || (&fresh_label != label && other_labels.contains(&fresh_label))
+            {
+                fresh_label = format!("{}_{}", label, suffix);
+                suffix += 1;
+            }
+            taken.insert(fresh_label.clone());
+            renaming.insert(label.clone(), fresh_label);
+        }
+        let rename = |label: String| Some(renaming.get(&label).cloned().unwrap_or(label));
+        let mut renamer = LabelRenamer {
+            renaming: &renaming,
+        };
+
+        let offset = self.basic_blocks.len();
+        let uuid = self.uuid;
+        let mut indices = Vec::with_capacity(other.basic_blocks.len());
+        for (block, label) in other
+            .basic_blocks
+            .into_iter()
+            .zip(other.basic_blocks_labels)
+        {
+            let stmts: Vec<Stmt> = block
+                .stmts
+                .into_iter()
+                .map(|stmt| renamer.fold(stmt))
+                .collect();
+            for stmt in &stmts {
+                self.labels.extend(gather_labels(stmt));
+            }
+            let successor = match block.successor {
+                Successor::Return => Successor::Goto(continuation),
+                Successor::GotoSwitch(guarded_targets, default_target) => Successor::GotoSwitch(
+                    guarded_targets
+                        .into_iter()
+                        .map(|(guard, target)| (guard.map_labels(rename), target))
+                        .collect(),
+                    default_target,
+                )
+                .map_targets(|target| CfgBlockIndex {
+                    method_uuid: uuid,
+                    block_index: target.block_index + offset,
+                }),
+                successor => successor.map_targets(|target| CfgBlockIndex {
+                    method_uuid: uuid,
+                    block_index: target.block_index + offset,
+                }),
+            };
+            indices.push(self.block_index(self.basic_blocks.len()));
+            self.basic_blocks_labels.push(renaming[&label].clone());
+            self.basic_blocks.push(CfgBlock {
+                stmts,
+                successor,
+                position: block.position,
+                exhale_on_entry: block
+                    .exhale_on_entry
+                    .into_iter()
+                    .map(|expr| expr.map_labels(rename))
+                    .collect(),
+                inhale_after_label: block
+                    .inhale_after_label
+                    .into_iter()
+                    .map(|expr| expr.map_labels(rename))
+                    .collect(),
+            });
+        }
+        Ok(indices)
+    }
+
+    /// Rename the local variable or formal return `old` to `new` and rewrite
+    /// its occurrences in the statements, invariants, and successor guards of
+    /// the blocks and in the contract. This is useful to resolve name clashes
+    /// before calling `splice`.
+    pub fn rename_local(&mut self, old: &str, new: &str) -> CfgResult<()> {
+        if !self.is_fresh_local_name(new) || self.reserved_labels.contains(new) {
+            return Err(CfgError::DuplicateVariable(new.to_string()));
+        }
+        let var = self
+            .formal_returns
+            .iter_mut()
+            .chain(self.local_vars.iter_mut())
+            .find(|var| var.name == old)
+            .ok_or_else(|| CfgError::UnknownVariable(old.to_string()))?;
+        var.name = new.to_string();
+        if let Some(return_path) = &mut self.return_path {
+            if return_path.name == old {
+                return_path.name = new.to_string();
+            }
+        }
+
+        struct LocalRenamer<'a> {
+            old: &'a str,
+            new: &'a str,
+        }
+        impl<'a> LocalRenamer<'a> {
+            fn rename_var(&self, var: LocalVar) -> LocalVar {
+                if var.name == self.old {
+                    LocalVar::new(self.new, var.typ)
+                } else {
+                    var
+                }
+            }
+
+            fn rename_exprs(&mut self, exprs: &mut Vec<Expr>) {
+                *exprs = std::mem::take(exprs)
+                    .into_iter()
+                    .map(|expr| self.fold_expr(expr))
+                    .collect();
+            }
+        }
+        impl<'a> StmtFolder for LocalRenamer<'a> {
+            fn fold_expr(&mut self, expr: Expr) -> Expr {
+                expr.rename_local_var(self.old, self.new)
+            }
+
+            fn fold_method_call(&mut self, statement: MethodCall) -> Stmt {
+                let MethodCall {
+                    method_name,
+                    arguments,
+                    targets,
+                } = statement;
+                Stmt::MethodCall(MethodCall {
+                    method_name,
+                    arguments: arguments.into_iter().map(|e| self.fold_expr(e)).collect(),
+                    targets: targets.into_iter().map(|t| self.rename_var(t)).collect(),
+                })
+            }
+
+            fn fold_package_magic_wand(&mut self, statement: PackageMagicWand) -> Stmt {
+                let PackageMagicWand {
+                    magic_wand,
+                    package_stmts,
+                    label,
+                    variables,
+                    position,
+                } = statement;
+                Stmt::PackageMagicWand(PackageMagicWand {
+                    magic_wand: self.fold_expr(magic_wand),
+                    package_stmts: package_stmts.into_iter().map(|x| self.fold(x)).collect(),
+                    label,
+                    variables: variables.into_iter().map(|v| self.rename_var(v)).collect(),
+                    position,
+                })
+            }
+        }
+
+        let mut renamer = LocalRenamer { old, new };
+        renamer.rename_exprs(&mut self.pres);
+        renamer.rename_exprs(&mut self.posts);
+        for block in &mut self.basic_blocks {
+            block.stmts = std::mem::take(&mut block.stmts)
+                .into_iter()
+                .map(|stmt| renamer.fold(stmt))
+                .collect();
+            renamer.rename_exprs(&mut block.exhale_on_entry);
+            renamer.rename_exprs(&mut block.inhale_after_label);
+            if let Successor::GotoSwitch(guarded_targets, _) = &mut block.successor {
+                for (guard, _) in guarded_targets.iter_mut() {
+                    *guard = renamer.fold_expr(std::mem::replace(guard, true.into()));
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn has_loops(&self) -> bool {
         let mut in_degree = vec![0; self.basic_blocks.len()];
 
@@ -460,3 +1687,270 @@ impl WithIdentifier for CfgMethod {
         self.method_name.clone()
     }
 }
+
+/// Renames the statement labels and the labels of the `old[..]` expressions
+/// of the statements. Labels without an entry in `renaming` are kept.
+struct LabelRenamer<'a> {
+    renaming: &'a FxHashMap<String, String>,
+}
+
+impl<'a> StmtFolder for LabelRenamer<'a> {
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        expr.map_labels(|label| Some(self.renaming.get(&label).cloned().unwrap_or(label)))
+    }
+
+    fn fold_label(&mut self, Label { label }: Label) -> Stmt {
+        Stmt::Label(Label {
+            label: self.renaming.get(&label).cloned().unwrap_or(label),
+        })
+    }
+}
+
+/// Collects the variables that occur free in the walked expressions, in the
+/// order of their first occurrence.
+#[derive(Default)]
+struct FreeVariablesCollector {
+    bound: Vec<LocalVar>,
+    free: Vec<LocalVar>,
+}
+
+impl FreeVariablesCollector {
+    fn walk_binder(&mut self, variables: &[LocalVar], walk_scope: impl FnOnce(&mut Self)) {
+        let bound_len = self.bound.len();
+        self.bound.extend(variables.iter().cloned());
+        walk_scope(self);
+        self.bound.truncate(bound_len);
+    }
+}
+
+impl ExprWalker for FreeVariablesCollector {
+    fn walk_local(&mut self, expr: &Local) {
+        if !self.bound.contains(&expr.variable) && !self.free.contains(&expr.variable) {
+            self.free.push(expr.variable.clone());
+        }
+    }
+    fn walk_forall(&mut self, expr: &ForAll) {
+        self.walk_binder(&expr.variables, |this| {
+            for set in &expr.triggers {
+                for element in set.elements() {
+                    this.walk(element);
+                }
+            }
+            this.walk(&expr.body);
+        });
+    }
+    fn walk_exists(&mut self, expr: &Exists) {
+        self.walk_binder(&expr.variables, |this| {
+            for set in &expr.triggers {
+                for element in set.elements() {
+                    this.walk(element);
+                }
+            }
+            this.walk(&expr.body);
+        });
+    }
+    fn walk_let_expr(&mut self, expr: &LetExpr) {
+        self.walk(&expr.def);
+        self.walk_binder(std::slice::from_ref(&expr.variable), |this| {
+            this.walk(&expr.body)
+        });
+    }
+}
+
+/// Collects the labels of the labelled `old` expressions in the walked
+/// statements and expressions.
+#[derive(Default)]
+struct ReferencedLabelsCollector {
+    labels: FxHashSet<String>,
+}
+
+impl ExprWalker for ReferencedLabelsCollector {
+    fn walk_labelled_old(&mut self, expr: &LabelledOld) {
+        self.labels.insert(expr.label.clone());
+        ExprWalker::walk(self, &expr.base);
+    }
+}
+
+impl StmtWalker for ReferencedLabelsCollector {
+    fn walk_expr(&mut self, expr: &Expr) {
+        ExprWalker::walk(self, expr);
+    }
+}
+
+/// Splits a switch guard of the shape `scrutinee == literal` into the
+/// scrutinee and the value of the literal.
+fn switch_arm_key(guard: &Expr) -> Option<(&Expr, i128)> {
+    if let Expr::BinOp(BinOp {
+        op_kind: BinaryOpKind::EqCmp,
+        left,
+        right,
+        ..
+    }) = guard
+    {
+        match (literal_value(left), literal_value(right)) {
+            (None, Some(value)) => Some((&**left, value)),
+            (Some(value), None) => Some((&**right, value)),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+fn literal_value(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Const(ConstExpr {
+            value: Const::Bool(value),
+            ..
+        }) => Some(i128::from(*value)),
+        Expr::Const(ConstExpr {
+            value: Const::Int(value),
+            ..
+        }) => Some(i128::from(*value)),
+        Expr::Const(ConstExpr {
+            value: Const::BigInt(value),
+            ..
+        }) => value.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Collects the variables that are assigned by statements and whether the
+/// statements assert or inhale `false`.
+#[derive(Default)]
+struct AssignedVarsCollector {
+    assigned: FxHashSet<String>,
+    diverges: bool,
+}
+
+impl AssignedVarsCollector {
+    fn walk_condition(&mut self, expr: &Expr) {
+        if let Expr::Const(ConstExpr {
+            value: Const::Bool(false),
+            ..
+        }) = expr
+        {
+            self.diverges = true;
+        }
+    }
+}
+
+impl StmtWalker for AssignedVarsCollector {
+    fn walk_assign(&mut self, statement: &Assign) {
+        self.assigned.insert(statement.target.get_base().name);
+    }
+
+    fn walk_method_call(&mut self, statement: &MethodCall) {
+        for target in &statement.targets {
+            self.assigned.insert(target.name.clone());
+        }
+    }
+
+    fn walk_inhale(&mut self, statement: &Inhale) {
+        self.walk_condition(&statement.expr);
+    }
+
+    fn walk_assert(&mut self, statement: &Assert) {
+        self.walk_condition(&statement.expr);
+    }
+
+    fn walk_if(&mut self, statement: &If) {
+        let mut then_collector = Self::default();
+        for stmt in &statement.then_stmts {
+            then_collector.walk(stmt);
+        }
+        let mut else_collector = Self::default();
+        for stmt in &statement.else_stmts {
+            else_collector.walk(stmt);
+        }
+        self.assigned.extend(then_collector.assigned);
+        self.assigned.extend(else_collector.assigned);
+        self.diverges |= then_collector.diverges && else_collector.diverges;
+    }
+}
+
+/// Collects the preconditions of the functions called in an expression
+/// together with the positions of the calls.
+struct PreconditionCollector<'a> {
+    functions: &'a [Function],
+    /// The pure conditions under which the currently visited subexpression is
+    /// evaluated.
+    guards: Vec<Expr>,
+    checks: Vec<(Expr, Position)>,
+}
+
+impl<'a> PreconditionCollector<'a> {
+    fn walk_guarded(&mut self, guard: Expr, expr: &Expr) {
+        self.guards.push(guard);
+        self.walk(expr);
+        self.guards.pop();
+    }
+}
+
+impl<'a> ExprWalker for PreconditionCollector<'a> {
+    fn walk_bin_op(&mut self, expr: &BinOp) {
+        let BinOp {
+            op_kind,
+            left,
+            right,
+            ..
+        } = expr;
+        self.walk(left);
+        match op_kind {
+            // Permissions do not guard the evaluation of the right side.
+            BinaryOpKind::And | BinaryOpKind::Implies if !left.is_pure() => self.walk(right),
+            BinaryOpKind::And | BinaryOpKind::Implies => {
+                self.walk_guarded((**left).clone(), right);
+            }
+            BinaryOpKind::Or => {
+                self.walk_guarded(Expr::not((**left).clone()), right);
+            }
+            _ => self.walk(right),
+        }
+    }
+    fn walk_cond(&mut self, expr: &Cond) {
+        let Cond {
+            guard,
+            then_expr,
+            else_expr,
+            ..
+        } = expr;
+        self.walk(guard);
+        self.walk_guarded((**guard).clone(), then_expr);
+        self.walk_guarded(Expr::not((**guard).clone()), else_expr);
+    }
+    fn walk_func_app(&mut self, expr: &FuncApp) {
+        for argument in &expr.arguments {
+            self.walk(argument);
+        }
+        let function = self.functions.iter().find(|function| {
+            function.name == expr.function_name && function.type_arguments == expr.type_arguments
+        });
+        if let Some(function) = function {
+            if function.pres.is_empty() {
+                return;
+            }
+            let replacements: Vec<_> = function
+                .formal_args
+                .iter()
+                .cloned()
+                .map(Expr::local)
+                .zip(expr.arguments.iter().cloned())
+                .collect();
+            let precondition = function
+                .pres
+                .iter()
+                .cloned()
+                .conjoin()
+                .replace_multiple_places(&replacements);
+            let check = match self.guards.iter().cloned().reduce(Expr::and) {
+                Some(guard) => Expr::implies(guard, precondition),
+                None => precondition,
+            };
+            self.checks.push((check, expr.position));
+        }
+    }
+    fn walk_forall(&mut self, _expr: &ForAll) {}
+    fn walk_exists(&mut self, _expr: &Exists) {}
+    fn walk_let_expr(&mut self, _expr: &LetExpr) {}
+}
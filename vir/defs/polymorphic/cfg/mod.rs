@@ -4,9 +4,11 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-pub use self::{assigned_vars::*, display::*, method::*, visitor::*};
+pub use self::{assigned_vars::*, display::*, errors::*, method::*, visitor::*};
 
 mod assigned_vars;
 mod display;
+mod errors;
+mod json;
 mod method;
 mod visitor;
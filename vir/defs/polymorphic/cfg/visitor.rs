@@ -105,6 +105,9 @@ pub trait CfgReplacer<PathCtxt: Debug + Clone, Action: CheckNoOpAction + Debug>
             cfg.local_vars.clone(),
             cfg.get_all_labels(),
         );
+        new_cfg.pres = cfg.pres.clone();
+        new_cfg.posts = cfg.posts.clone();
+        new_cfg.return_label = cfg.return_label.clone();
 
         // Initialize the blocks of the new cfg
         for (index, _block) in cfg.basic_blocks.iter().enumerate() {
@@ -300,6 +303,9 @@ pub trait SuccessorFolder {
         match s {
             Successor::Undefined => self.fold_undefined(),
             Successor::Return => self.fold_return(),
+            Successor::IntentionalUnreachable(position) => {
+                self.fold_intentional_unreachable(position)
+            }
             Successor::Goto(target) => self.fold_goto(target),
             Successor::GotoSwitch(guarded_targets, default_target) => {
                 self.fold_goto_switch(guarded_targets, default_target)
@@ -323,6 +329,10 @@ pub trait SuccessorFolder {
         Successor::Undefined
     }
 
+    fn fold_intentional_unreachable(&mut self, position: Position) -> Successor {
+        Successor::IntentionalUnreachable(position)
+    }
+
     fn fold_goto(&mut self, target: CfgBlockIndex) -> Successor {
         Successor::Goto(self.fold_target(target))
     }
@@ -111,7 +111,10 @@ pub fn walk_method(method: &CfgMethod, walker: &mut (impl StmtWalker + ExprWalke
         StmtWalker::walk(walker, stmt);
     });
     method.walk_successors(|successor| match successor {
-        cfg::Successor::Undefined | cfg::Successor::Return | cfg::Successor::Goto(_) => {}
+        cfg::Successor::Undefined
+        | cfg::Successor::Return
+        | cfg::Successor::IntentionalUnreachable(_)
+        | cfg::Successor::Goto(_) => {}
         cfg::Successor::GotoSwitch(conditional_targets, _) => {
             for (ref expr, _) in conditional_targets {
                 ExprWalker::walk(walker, expr);
@@ -155,7 +158,10 @@ pub fn fallible_walk_method<E>(
     method.fallible_walk_statements(|stmt| FallibleStmtWalker::fallible_walk(walker, stmt))?;
     method.fallible_walk_successors(|successor| {
         match successor {
-            cfg::Successor::Undefined | cfg::Successor::Return | cfg::Successor::Goto(_) => {}
+            cfg::Successor::Undefined
+            | cfg::Successor::Return
+            | cfg::Successor::IntentionalUnreachable(_)
+            | cfg::Successor::Goto(_) => {}
             cfg::Successor::GotoSwitch(conditional_targets, _) => {
                 for (ref expr, _) in conditional_targets {
                     FallibleExprWalker::fallible_walk(walker, expr)?;
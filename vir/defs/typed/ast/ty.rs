@@ -198,6 +198,8 @@ pub struct Closure {
     // /// Type arguments.
     // FIXME: We are currently ignoring type arguments.
     // pub arguments: Vec<Type>,
+    /// The lifetimes of the captured references.
+    pub lifetimes: Vec<LifetimeConst>,
 }
 
 #[display(fmt = "{}", name)]
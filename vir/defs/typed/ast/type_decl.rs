@@ -119,7 +119,7 @@ pub struct Pointer {
 #[display(fmt = "{}", name)]
 pub struct Closure {
     pub name: String,
-    // pub lifetimes: Vec<LifetimeConst>,
+    pub lifetimes: Vec<LifetimeConst>,
     // pub const_parameters: Vec<VariableDecl>,
     /// The tuple of captured arguments.
     pub arguments: Vec<Type>,
@@ -473,15 +473,25 @@ impl Typed for BinaryOp {
             | BinaryOpKind::Mul
             | BinaryOpKind::Div
             | BinaryOpKind::Mod
+            | BinaryOpKind::BitAnd
+            | BinaryOpKind::BitOr
+            | BinaryOpKind::BitXor
             | BinaryOpKind::LifetimeIntersection => {
                 let ty1 = self.left.get_type();
                 let ty2 = self.right.get_type();
                 assert_eq!(ty1, ty2, "expr: {:?}", self);
                 ty1
             }
+            // The shift amount may have a different type than the shifted
+            // value.
+            BinaryOpKind::Shl | BinaryOpKind::Shr => self.left.get_type(),
         }
     }
     fn set_type(&mut self, new_type: Type) {
+        if matches!(self.op_kind, BinaryOpKind::Shl | BinaryOpKind::Shr) {
+            self.left.set_type(new_type);
+            return;
+        }
         self.left.set_type(new_type.clone());
         self.right.set_type(new_type);
     }
@@ -37,7 +37,7 @@ impl TypeDecl {
             // // Self::FnPointer => &[],
             // Self::Never => &[],
             // // Self::Str => &[],
-            // Self::Closure(decl) => &decl.lifetimes,
+            Self::Closure(decl) => &decl.lifetimes,
             // // Self::Projection(_decl) => &[],
             // Self::Unsupported(_decl) => &[],
             Self::Trusted(decl) => &decl.lifetimes,
@@ -730,6 +730,12 @@ impl From<polymorphic::CfgMethod> for legacy::CfgMethod {
                 .into_iter()
                 .map(|formal_return| formal_return.into())
                 .collect(),
+            pres: cfg_method.pres.into_iter().map(|pre| pre.into()).collect(),
+            posts: cfg_method
+                .posts
+                .into_iter()
+                .map(|post| post.into())
+                .collect(),
             local_vars: cfg_method
                 .local_vars
                 .into_iter()
@@ -742,6 +748,7 @@ impl From<polymorphic::CfgMethod> for legacy::CfgMethod {
                 .map(|basic_block| basic_block.into())
                 .collect(),
             basic_blocks_labels: cfg_method.basic_blocks_labels.into_iter().collect(),
+            return_label: cfg_method.return_label,
         }
     }
 }
@@ -755,6 +762,17 @@ impl From<polymorphic::CfgBlock> for legacy::CfgBlock {
                 .map(|stmt| stmt.into())
                 .collect(),
             successor: cfg_block.successor.into(),
+            position: cfg_block.position.into(),
+            exhale_on_entry: cfg_block
+                .exhale_on_entry
+                .into_iter()
+                .map(|expr| expr.into())
+                .collect(),
+            inhale_after_label: cfg_block
+                .inhale_after_label
+                .into_iter()
+                .map(|expr| expr.into())
+                .collect(),
         }
     }
 }
@@ -764,6 +782,9 @@ impl From<polymorphic::Successor> for legacy::Successor {
         match successor {
             polymorphic::Successor::Undefined => legacy::Successor::Undefined,
             polymorphic::Successor::Return => legacy::Successor::Return,
+            polymorphic::Successor::IntentionalUnreachable(position) => {
+                legacy::Successor::IntentionalUnreachable(position.into())
+            }
             polymorphic::Successor::Goto(cfg_block_index) => {
                 legacy::Successor::Goto(cfg_block_index.into())
             }
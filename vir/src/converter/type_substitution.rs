@@ -758,6 +758,16 @@ impl Generic for CfgMethod {
             .into_iter()
             .map(|formal_return| formal_return.substitute(map))
             .collect();
+        cfg_method.pres = cfg_method
+            .pres
+            .into_iter()
+            .map(|pre| pre.substitute(map))
+            .collect();
+        cfg_method.posts = cfg_method
+            .posts
+            .into_iter()
+            .map(|post| post.substitute(map))
+            .collect();
         cfg_method.local_vars = cfg_method
             .local_vars
             .into_iter()
@@ -781,6 +791,16 @@ impl Generic for CfgBlock {
             .map(|stmt| stmt.substitute(map))
             .collect();
         cfg_block.successor = cfg_block.successor.substitute(map);
+        cfg_block.exhale_on_entry = cfg_block
+            .exhale_on_entry
+            .into_iter()
+            .map(|expr| expr.substitute(map))
+            .collect();
+        cfg_block.inhale_after_label = cfg_block
+            .inhale_after_label
+            .into_iter()
+            .map(|expr| expr.substitute(map))
+            .collect();
         cfg_block
     }
 }
@@ -788,7 +808,7 @@ impl Generic for CfgBlock {
 impl Generic for Successor {
     fn substitute(self, map: &FxHashMap<TypeVar, Type>) -> Self {
         match self {
-            Successor::Undefined | Successor::Return => self,
+            Successor::Undefined | Successor::Return | Successor::IntentionalUnreachable(_) => self,
             Successor::Goto(cfg_block_index) => Successor::Goto(cfg_block_index.substitute(map)),
             Successor::GotoSwitch(expr_indices, cfg_block_index) => Successor::GotoSwitch(
                 expr_indices
@@ -3811,6 +3831,9 @@ mod tests {
                     block_index: 123,
                 },
             ),
+            position,
+            exhale_on_entry: vec![],
+            inhale_after_label: vec![],
         };
         let expected = CfgBlock {
             stmts: vec![
@@ -3869,6 +3892,9 @@ mod tests {
                     block_index: 123,
                 },
             ),
+            position,
+            exhale_on_entry: vec![],
+            inhale_after_label: vec![],
         };
         test(source, expected, &SUBSTITUTION_MAP);
     }
@@ -3893,6 +3919,14 @@ mod tests {
                     typ: Type::type_var("E"),
                 },
             ],
+            pres: vec![Expr::Local(Local {
+                variable: LocalVar {
+                    name: String::from("_v1"),
+                    typ: Type::type_var("T"),
+                },
+                position,
+            })],
+            posts: vec![],
             local_vars: vec![
                 LocalVar {
                     name: String::from("_v3"),
@@ -3966,8 +4000,13 @@ mod tests {
                         block_index: 123,
                     },
                 ),
+                position,
+                exhale_on_entry: vec![],
+                inhale_after_label: vec![],
             }],
             basic_blocks_labels: vec![String::from("bbl1"), String::from("bbl2")],
+            return_label: String::from("end_of_method"),
+            return_path: None,
             fresh_var_index: 1,
             fresh_label_index: 2,
         };
@@ -3984,6 +4023,14 @@ mod tests {
                     typ: Type::Bool,
                 },
             ],
+            pres: vec![Expr::Local(Local {
+                variable: LocalVar {
+                    name: String::from("_v1"),
+                    typ: Type::Int,
+                },
+                position,
+            })],
+            posts: vec![],
             local_vars: vec![
                 LocalVar {
                     name: String::from("_v3"),
@@ -4057,8 +4104,13 @@ mod tests {
                         block_index: 123,
                     },
                 ),
+                position,
+                exhale_on_entry: vec![],
+                inhale_after_label: vec![],
             }],
             basic_blocks_labels: vec![String::from("bbl1"), String::from("bbl2")],
+            return_label: String::from("end_of_method"),
+            return_path: None,
             fresh_var_index: 1,
             fresh_label_index: 2,
         };
@@ -19,6 +19,12 @@ impl fmt::Display for CfgMethod {
                 .collect::<Vec<String>>()
                 .join(", ")
         )?;
+        for pre in self.pres.iter() {
+            writeln!(f, "    requires {pre}")?;
+        }
+        for post in self.posts.iter() {
+            writeln!(f, "    ensures {post}")?;
+        }
         writeln!(f, "{{")?;
         for local_var in self.local_vars.iter() {
             writeln!(f, "    {local_var:?}")?;
@@ -30,12 +36,18 @@ impl fmt::Display for CfgMethod {
                 "  label {} // {}",
                 self.basic_blocks_labels[index], index
             )?;
+            for invariant in &block.exhale_on_entry {
+                writeln!(f, "    // exhaled on entry: {invariant}")?;
+            }
+            for invariant in &block.inhale_after_label {
+                writeln!(f, "    inhale {invariant}")?;
+            }
             for stmt in &block.stmts {
                 writeln!(f, "    {stmt}")?;
             }
             writeln!(f, "    {:?}", block.successor)?;
         }
-        writeln!(f, "  label {RETURN_LABEL}")?;
+        writeln!(f, "  label {}", self.return_label)?;
         writeln!(f, "}}")
     }
 }
@@ -45,6 +57,9 @@ impl fmt::Display for Successor {
         match self {
             Successor::Undefined => writeln!(f, "Undefined"),
             Successor::Return => writeln!(f, "Return"),
+            Successor::IntentionalUnreachable(position) => {
+                writeln!(f, "IntentionalUnreachable({position:?})")
+            }
             Successor::Goto(ref target) => writeln!(f, "Goto({target})"),
             Successor::GotoSwitch(ref guarded_targets, ref default_target) => writeln!(
                 f,
@@ -23,24 +23,41 @@ pub struct CfgMethod {
     #[derivative(Hash = "ignore", PartialEq = "ignore")]
     pub method_name: String,
     pub formal_returns: Vec<LocalVar>,
+    pub pres: Vec<Expr>,
+    pub posts: Vec<Expr>,
     pub local_vars: Vec<LocalVar>,
     #[derivative(Hash = "ignore", PartialEq = "ignore")]
     pub labels: FxHashSet<String>,
     pub basic_blocks: Vec<CfgBlock>,
     pub basic_blocks_labels: Vec<String>,
+    /// The label that marks the end of the method, targeted by `Return`.
+    pub return_label: String,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize, Hash)]
 pub struct CfgBlock {
     pub stmts: Vec<Stmt>,
     pub successor: Successor,
+    pub position: Position,
+    /// Invariants that are exhaled on every edge that enters the block, for
+    /// example, on the entry and back edges of a loop.
+    pub exhale_on_entry: Vec<Expr>,
+    /// Invariants that are inhaled right after the label of the block.
+    pub inhale_after_label: Vec<Expr>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize, Hash)]
 pub enum Successor {
     Undefined,
     Return,
+    /// The end of the block is unreachable by design, for example, because it
+    /// originates from `unreachable!()`. It is lowered to `assert false` at the
+    /// given position.
+    IntentionalUnreachable(Position),
     Goto(CfgBlockIndex),
+    /// The guards are checked in order and the first one that holds determines
+    /// the target, so the guards may overlap. If none of them holds, the
+    /// default target is taken.
     GotoSwitch(Vec<(Expr, CfgBlockIndex)>, CfgBlockIndex),
 }
 
@@ -64,7 +81,9 @@ impl Successor {
 
     pub fn get_following(&self) -> Vec<CfgBlockIndex> {
         match &self {
-            Successor::Undefined | Successor::Return => vec![],
+            Successor::Undefined | Successor::Return | Successor::IntentionalUnreachable(_) => {
+                vec![]
+            }
             Successor::Goto(target) => vec![*target],
             Successor::GotoSwitch(guarded_targets, default_target) => {
                 let mut res: Vec<CfgBlockIndex> = guarded_targets.iter().map(|g| g.1).collect();
@@ -93,10 +112,13 @@ impl CfgMethod {
         CfgMethod {
             method_name,
             formal_returns,
+            pres: vec![],
+            posts: vec![],
             local_vars,
             labels: FxHashSet::default(),
             basic_blocks: vec![],
             basic_blocks_labels: vec![],
+            return_label: RETURN_LABEL.to_string(),
         }
     }
 
@@ -112,10 +134,22 @@ impl CfgMethod {
         &self.basic_blocks_labels
     }
 
+    pub fn return_label(&self) -> &str {
+        &self.return_label
+    }
+
     pub fn get_formal_returns(&self) -> &Vec<LocalVar> {
         &self.formal_returns
     }
 
+    pub fn get_preconditions(&self) -> &Vec<Expr> {
+        &self.pres
+    }
+
+    pub fn get_postconditions(&self) -> &Vec<Expr> {
+        &self.posts
+    }
+
     pub(super) fn block_index(&self, index: usize) -> CfgBlockIndex {
         CfgBlockIndex { block_index: index }
     }
@@ -180,12 +214,15 @@ impl CfgMethod {
             self.basic_blocks_labels.iter().all(|l| l != label),
             "Label {label} is already used"
         );
-        assert!(label != RETURN_LABEL);
+        assert!(label != self.return_label);
         let index = self.basic_blocks.len();
         self.basic_blocks_labels.push(label.to_string());
         self.basic_blocks.push(CfgBlock {
             stmts,
             successor: Successor::Undefined,
+            position: Position::default(),
+            exhale_on_entry: vec![],
+            inhale_after_label: vec![],
         });
         self.block_index(index)
     }
@@ -310,6 +347,47 @@ impl CfgMethod {
         topo_sorted.push(self.block_index(curr_index))
     }
 
+    /// Returns the blocks in a canonical order that does not depend on the
+    /// order in which they were added: the blocks reachable from the first
+    /// block in reverse postorder with successors ordered by their labels,
+    /// followed by the unreachable blocks sorted by their labels.
+    pub fn get_canonical_order(&self) -> Vec<CfgBlockIndex> {
+        if self.basic_blocks.is_empty() {
+            return Vec::new();
+        }
+        let mut visited: Vec<bool> = vec![false; self.basic_blocks.len()];
+        let mut postorder: Vec<CfgBlockIndex> = vec![];
+        self.canonical_order_impl(&mut visited, &mut postorder, 0);
+        postorder.reverse();
+        let mut unreachable: Vec<_> = (0..self.basic_blocks.len())
+            .filter(|index| !visited[*index])
+            .collect();
+        unreachable.sort_by_key(|index| &self.basic_blocks_labels[*index]);
+        postorder.extend(unreachable.into_iter().map(|index| self.block_index(index)));
+        postorder
+    }
+
+    fn canonical_order_impl(
+        &self,
+        visited: &mut Vec<bool>,
+        postorder: &mut Vec<CfgBlockIndex>,
+        curr_index: usize,
+    ) {
+        visited[curr_index] = true;
+        let mut following = self.basic_blocks[curr_index].successor.get_following();
+        // Visit the successors in the reverse order of their labels so that
+        // they end up sorted by label once the postorder is reversed.
+        following.sort_by(|a, b| {
+            self.basic_blocks_labels[b.block_index].cmp(&self.basic_blocks_labels[a.block_index])
+        });
+        for block_index in following {
+            if !visited[block_index.block_index] {
+                self.canonical_order_impl(visited, postorder, block_index.block_index);
+            }
+        }
+        postorder.push(self.block_index(curr_index));
+    }
+
     /// Find some path from the `start_block` to the `end_block`.
     ///
     /// The returned path includes both `start_block` and `end_block`.
@@ -17,6 +17,9 @@ pub trait SuccessorFolder {
         match s {
             Successor::Undefined => self.fold_undefined(),
             Successor::Return => self.fold_return(),
+            Successor::IntentionalUnreachable(position) => {
+                self.fold_intentional_unreachable(position)
+            }
             Successor::Goto(target) => self.fold_goto(target),
             Successor::GotoSwitch(guarded_targets, default_target) => {
                 self.fold_goto_switch(guarded_targets, default_target)
@@ -40,6 +43,10 @@ pub trait SuccessorFolder {
         Successor::Undefined
     }
 
+    fn fold_intentional_unreachable(&mut self, position: Position) -> Successor {
+        Successor::IntentionalUnreachable(position)
+    }
+
     fn fold_goto(&mut self, target: CfgBlockIndex) -> Successor {
         Successor::Goto(self.fold_target(target))
     }
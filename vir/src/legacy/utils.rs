@@ -100,7 +100,10 @@ pub fn walk_method(method: &CfgMethod, walker: &mut (impl StmtWalker + ExprWalke
         StmtWalker::walk(walker, stmt);
     });
     method.walk_successors(|successor| match successor {
-        cfg::Successor::Undefined | cfg::Successor::Return | cfg::Successor::Goto(_) => {}
+        cfg::Successor::Undefined
+        | cfg::Successor::Return
+        | cfg::Successor::IntentionalUnreachable(_)
+        | cfg::Successor::Goto(_) => {}
         cfg::Successor::GotoSwitch(conditional_targets, _) => {
             for (expr, _) in conditional_targets {
                 ExprWalker::walk(walker, expr);
@@ -0,0 +1,1273 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use vir::polymorphic::{
+    Assert, Assign, AssignKind, CfgError, CfgMethod, Expr, Field, Function, LocalVar, MethodCall,
+    PermAmount, Position, Stmt, Successor, SwitchBuilder, Type,
+};
+
+fn new_method() -> CfgMethod {
+    CfgMethod::new("test".to_string(), vec![], vec![], vec![])
+}
+
+#[test]
+fn remove_unreachable_blocks_with_goto() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let dead = method.add_block("dead", vec![]);
+    let end = method.add_block("end", vec![]);
+    method.set_successor(start, Successor::Goto(end));
+    method.set_successor(dead, Successor::Goto(end));
+    method.set_successor(end, Successor::Return);
+
+    method.remove_unreachable_blocks();
+
+    assert_eq!(method.basic_blocks_labels(), &["start", "end"]);
+    let indices = method.get_indices();
+    assert_eq!(
+        method.basic_blocks[0].successor,
+        Successor::Goto(indices[1])
+    );
+    assert_eq!(method.basic_blocks[1].successor, Successor::Return);
+}
+
+#[test]
+fn remove_unreachable_blocks_with_switch() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let dead_head = method.add_block("dead_head", vec![]);
+    let left = method.add_block("left", vec![]);
+    let dead_tail = method.add_block("dead_tail", vec![]);
+    let right = method.add_block("right", vec![]);
+    method.set_successor(
+        start,
+        Successor::GotoSwitch(vec![(Expr::from(true), left)], right),
+    );
+    method.set_successor(
+        dead_head,
+        Successor::GotoSwitch(vec![(Expr::from(false), dead_tail)], left),
+    );
+    method.set_successor(dead_tail, Successor::Goto(right));
+    method.set_successor(left, Successor::Goto(right));
+    method.set_successor(right, Successor::Return);
+
+    method.remove_unreachable_blocks();
+
+    assert_eq!(method.basic_blocks_labels(), &["start", "left", "right"]);
+    let indices = method.get_indices();
+    assert_eq!(
+        method.basic_blocks[0].successor,
+        Successor::GotoSwitch(vec![(Expr::from(true), indices[1])], indices[2])
+    );
+    assert_eq!(
+        method.basic_blocks[1].successor,
+        Successor::Goto(indices[2])
+    );
+    assert!(method.check_reachable().is_ok());
+}
+
+#[test]
+fn remove_unreachable_blocks_keeps_loops() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let head = method.add_block("head", vec![]);
+    let end = method.add_block("end", vec![]);
+    method.set_successor(start, Successor::Goto(head));
+    method.set_successor(
+        head,
+        Successor::GotoSwitch(vec![(Expr::from(true), head)], end),
+    );
+    method.set_successor(end, Successor::Return);
+
+    method.remove_unreachable_blocks();
+
+    assert_eq!(method.basic_blocks_labels(), &["start", "head", "end"]);
+}
+
+#[test]
+fn switch_builder_keeps_arm_order() {
+    let mut method = new_method();
+    let first = method.add_block("first", vec![]);
+    let second = method.add_block("second", vec![]);
+    let fallback = method.add_block("fallback", vec![]);
+
+    let successor = SwitchBuilder::new()
+        .arm(Expr::from(true), first)
+        .arm(Expr::from(false), second)
+        .default(fallback)
+        .build();
+
+    assert_eq!(
+        successor,
+        Successor::GotoSwitch(
+            vec![(Expr::from(true), first), (Expr::from(false), second)],
+            fallback
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "The default target of the switch is not set")]
+fn switch_builder_requires_default() {
+    let mut method = new_method();
+    let target = method.add_block("target", vec![]);
+    SwitchBuilder::new().arm(Expr::from(true), target).build();
+}
+
+#[test]
+fn splice_rebases_successors() {
+    let mut caller = new_method();
+    let start = caller.add_block("start", vec![]);
+    let end = caller.add_block("end", vec![]);
+    caller.set_successor(end, Successor::Return);
+
+    let mut callee = CfgMethod::new("callee".to_string(), vec![], vec![], vec![]);
+    let callee_start = callee.add_block("start", vec![]);
+    let callee_end = callee.add_block("body", vec![]);
+    callee.set_successor(callee_start, Successor::Goto(callee_end));
+    callee.set_successor(callee_end, Successor::Return);
+
+    let indices = caller.splice(callee, end).unwrap();
+    caller.set_successor(start, Successor::Goto(indices[0]));
+
+    assert_eq!(
+        caller.basic_blocks_labels(),
+        &["start", "end", "start_1", "body"]
+    );
+    assert_eq!(indices, &caller.get_indices()[2..]);
+    assert_eq!(
+        caller.basic_blocks[2].successor,
+        Successor::Goto(indices[1])
+    );
+    assert_eq!(caller.basic_blocks[3].successor, Successor::Goto(end));
+    assert!(caller.check_reachable().is_ok());
+}
+
+#[test]
+fn splice_renames_clashing_labels() {
+    let x = LocalVar::new("x", Type::Bool);
+    let field = Expr::local(x.clone()).field(Field::new("val_bool", Type::Bool));
+    let mut caller = CfgMethod::new("caller".to_string(), vec![], vec![x.clone()], vec![]);
+    let start = caller.add_block("start", vec![Stmt::label("l0")]);
+    caller.set_successor(start, Successor::Return);
+
+    let mut callee = CfgMethod::new("callee".to_string(), vec![], vec![x], vec![]);
+    let assert = Stmt::Assert(Assert {
+        expr: Expr::eq_cmp(
+            Expr::labelled_old("start", field.clone()),
+            Expr::labelled_old("l0", field.clone()),
+        ),
+        position: Position::default(),
+    });
+    let callee_start = callee.add_block("start", vec![Stmt::label("l0"), assert]);
+    callee.set_successor(callee_start, Successor::Return);
+
+    let indices = caller.splice(callee, start).unwrap();
+
+    assert_eq!(caller.basic_blocks_labels(), &["start", "start_1"]);
+    assert!(caller.labels().contains("l0_1"));
+    let stmts = &caller.basic_blocks[indices[0].index()].stmts;
+    assert_eq!(stmts[0], Stmt::label("l0_1"));
+    assert_eq!(
+        stmts[1],
+        Stmt::Assert(Assert {
+            expr: Expr::eq_cmp(
+                Expr::labelled_old("start_1", field.clone()),
+                Expr::labelled_old("l0_1", field),
+            ),
+            position: Position::default(),
+        })
+    );
+}
+
+#[test]
+fn splice_rejects_variables_of_another_type() {
+    let mut caller = CfgMethod::new(
+        "caller".to_string(),
+        vec![],
+        vec![LocalVar::new("x", Type::Bool)],
+        vec![],
+    );
+    let start = caller.add_block("start", vec![]);
+    caller.set_successor(start, Successor::Return);
+
+    let mut callee = CfgMethod::new(
+        "callee".to_string(),
+        vec![],
+        vec![LocalVar::new("y", Type::Int), LocalVar::new("x", Type::Int)],
+        vec![],
+    );
+    let callee_start = callee.add_block("start", vec![]);
+    callee.set_successor(callee_start, Successor::Return);
+
+    assert_eq!(
+        caller.splice(callee, start),
+        Err(CfgError::ConflictingVariableType("x".to_string()))
+    );
+    // The failed splice leaves the method unchanged.
+    assert_eq!(caller.basic_blocks_labels(), &["start"]);
+    assert_eq!(caller.get_all_vars(), vec![LocalVar::new("x", Type::Bool)]);
+}
+
+#[test]
+fn splice_rejects_variables_named_like_labels() {
+    let mut caller = new_method();
+    let start = caller.add_block("start", vec![]);
+    caller.set_successor(start, Successor::Return);
+
+    let mut callee = CfgMethod::new(
+        "callee".to_string(),
+        vec![],
+        vec![LocalVar::new("start", Type::Int)],
+        vec![],
+    );
+    let callee_body = callee.add_block("body", vec![]);
+    callee.set_successor(callee_body, Successor::Return);
+
+    assert_eq!(
+        caller.splice(callee, start),
+        Err(CfgError::DuplicateVariable("start".to_string()))
+    );
+    assert_eq!(caller.basic_blocks_labels(), &["start"]);
+}
+
+#[test]
+fn fold_constant_switches_takes_true_arm() {
+    let mut method = new_method();
+    let position = Position::new(3, 7, 42);
+    let start = method.add_block_with_pos("start", vec![], position);
+    let taken = method.add_block("taken", vec![]);
+    let skipped = method.add_block("skipped", vec![]);
+    method.set_successor(
+        start,
+        Successor::GotoSwitch(vec![(Expr::from(true), taken)], skipped),
+    );
+    method.set_successor(taken, Successor::Return);
+    method.set_successor(skipped, Successor::Return);
+
+    assert!(method.fold_constant_switches());
+    assert_eq!(method.basic_blocks[0].successor, Successor::Goto(taken));
+    assert_eq!(method.basic_blocks[0].position, position);
+
+    method.remove_unreachable_blocks();
+    assert_eq!(method.basic_blocks_labels(), &["start", "taken"]);
+}
+
+#[test]
+fn fold_constant_switches_skips_false_arms() {
+    let mut method = new_method();
+    let position = Position::new(5, 1, 43);
+    let start = method.add_block_with_pos("start", vec![], position);
+    let skipped = method.add_block("skipped", vec![]);
+    let taken = method.add_block("taken", vec![]);
+    method.set_successor(
+        start,
+        Successor::GotoSwitch(vec![(Expr::from(false), skipped)], taken),
+    );
+    method.set_successor(skipped, Successor::Return);
+    method.set_successor(taken, Successor::Return);
+
+    assert!(method.fold_constant_switches());
+    assert_eq!(method.basic_blocks[0].successor, Successor::Goto(taken));
+    assert_eq!(method.basic_blocks[0].position, position);
+    assert!(!method.fold_constant_switches());
+}
+
+fn discriminant_is(value: i64) -> Expr {
+    Expr::eq_cmp(Expr::local(LocalVar::new("d", Type::Int)), value.into())
+}
+
+#[test]
+fn sort_switch_arms_orders_disjoint_literals() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let two = method.add_block("two", vec![]);
+    let zero = method.add_block("zero", vec![]);
+    let one = method.add_block("one", vec![]);
+    let default = method.add_block("default", vec![]);
+    method.set_successor(
+        start,
+        Successor::GotoSwitch(
+            vec![
+                (discriminant_is(2), two),
+                (discriminant_is(0), zero),
+                (discriminant_is(1), one),
+            ],
+            default,
+        ),
+    );
+
+    assert!(method.sort_switch_arms());
+    assert_eq!(
+        method.basic_blocks[0].successor,
+        Successor::GotoSwitch(
+            vec![
+                (discriminant_is(0), zero),
+                (discriminant_is(1), one),
+                (discriminant_is(2), two),
+            ],
+            default,
+        )
+    );
+    assert!(!method.sort_switch_arms());
+}
+
+#[test]
+fn sort_switch_arms_keeps_overlapping_guards() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let first = method.add_block("first", vec![]);
+    let second = method.add_block("second", vec![]);
+    let default = method.add_block("default", vec![]);
+    let duplicate = Successor::GotoSwitch(
+        vec![(discriminant_is(1), first), (discriminant_is(1), second)],
+        default,
+    );
+    method.set_successor(start, duplicate.clone());
+    assert!(!method.sort_switch_arms());
+    assert_eq!(method.basic_blocks[0].successor, duplicate);
+
+    let other = Expr::eq_cmp(Expr::local(LocalVar::new("e", Type::Int)), 0.into());
+    let mixed = Successor::GotoSwitch(vec![(discriminant_is(1), first), (other, second)], default);
+    method.set_successor(start, mixed.clone());
+    assert!(!method.sort_switch_arms());
+    assert_eq!(method.basic_blocks[0].successor, mixed);
+
+    let non_literal = Successor::GotoSwitch(
+        vec![(discriminant_is(1), first), (Expr::from(true), second)],
+        default,
+    );
+    method.set_successor(start, non_literal.clone());
+    assert!(!method.sort_switch_arms());
+    assert_eq!(method.basic_blocks[0].successor, non_literal);
+}
+
+#[test]
+fn remove_unreachable_blocks_keeps_block_invariants() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let dead = method.add_block("dead", vec![]);
+    let head = method.add_block("head", vec![]);
+    method.set_successor(start, Successor::Goto(head));
+    method.set_successor(dead, Successor::Goto(head));
+    method.set_successor(head, Successor::Return);
+    method.set_block_invariants(head, vec![Expr::from(true)], vec![Expr::from(false)]);
+
+    method.remove_unreachable_blocks();
+
+    assert_eq!(method.basic_blocks_labels(), &["start", "head"]);
+    assert_eq!(
+        method.basic_blocks[1].exhale_on_entry,
+        vec![Expr::from(true)]
+    );
+    assert_eq!(
+        method.basic_blocks[1].inhale_after_label,
+        vec![Expr::from(false)]
+    );
+    assert!(method.basic_blocks[0].exhale_on_entry.is_empty());
+}
+
+#[test]
+fn dominators_of_diamond() {
+    let mut method = new_method();
+    let split = method.add_block("split", vec![]);
+    let left = method.add_block("left", vec![]);
+    let right = method.add_block("right", vec![]);
+    let merge = method.add_block("merge", vec![]);
+    let dead = method.add_block("dead", vec![]);
+    method.set_successor(
+        split,
+        Successor::GotoSwitch(vec![(Expr::from(true), left)], right),
+    );
+    method.set_successor(left, Successor::Goto(merge));
+    method.set_successor(right, Successor::Goto(merge));
+    method.set_successor(merge, Successor::Return);
+    method.set_successor(dead, Successor::Goto(merge));
+
+    let dominators = method.dominators();
+
+    assert_eq!(dominators.len(), 4);
+    assert_eq!(dominators[&split], split);
+    assert_eq!(dominators[&left], split);
+    assert_eq!(dominators[&right], split);
+    assert_eq!(dominators[&merge], split);
+    assert!(!dominators.contains_key(&dead));
+}
+
+#[test]
+fn to_json_records_block_shapes() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let end = method.add_block("end", vec![]);
+    method.set_successor(
+        start,
+        Successor::GotoSwitch(vec![(Expr::from(true), end)], end),
+    );
+    method.set_successor(end, Successor::Return);
+    method.set_block_invariants(end, vec![Expr::from(true)], vec![]);
+
+    let json = method.to_json();
+
+    assert_eq!(json, method.clone().to_json());
+    assert!(json.contains(r#""label": "start""#));
+    assert!(json.contains(r#""kind": "goto_switch""#));
+    assert!(json.contains(r#""invariant_count": 1"#));
+    assert!(json.contains(r#""kind": "return""#));
+}
+
+#[test]
+fn find_missing_successors_ignores_unreachable_blocks() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let forgotten = method.add_block("forgotten", vec![]);
+    let _dead = method.add_block("dead", vec![]);
+    method.set_successor(start, Successor::Goto(forgotten));
+
+    assert_eq!(method.find_missing_successors(), vec![forgotten]);
+    assert_eq!(
+        method.check_successors(),
+        Err(CfgError::MissingSuccessors(vec!["forgotten".to_string()]))
+    );
+
+    method.set_successor(forgotten, Successor::Return);
+    assert!(method.find_missing_successors().is_empty());
+    assert!(method.check_successors().is_ok());
+}
+
+#[test]
+fn find_missing_successors_accepts_intentional_unreachable() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let unreachable = method.add_block("unreachable", vec![]);
+    method.set_successor(start, Successor::Goto(unreachable));
+    method.set_successor(
+        unreachable,
+        Successor::IntentionalUnreachable(Position::new(7, 5, 44)),
+    );
+
+    assert!(method.find_missing_successors().is_empty());
+    assert!(method.get_successor(unreachable).get_following().is_empty());
+}
+
+#[test]
+fn add_block_stmts_collects_statements() {
+    let mut method = new_method();
+    let comments = ["first", "second"].into_iter().map(Stmt::comment);
+    let block = method.add_block_stmts("start", comments);
+
+    assert_eq!(method.get_block_label(block), "start");
+    assert_eq!(
+        method.basic_blocks[0].stmts,
+        vec![Stmt::comment("first"), Stmt::comment("second")]
+    );
+}
+
+#[test]
+fn normalize_collapses_empty_goto_chains() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let first = method.add_block("first", vec![]);
+    let second = method.add_block("second", vec![]);
+    let guarded = method.add_block("guarded", vec![]);
+    let end = method.add_block("end", vec![Stmt::comment("end")]);
+    method.set_successor(
+        start,
+        Successor::GotoSwitch(vec![(Expr::from(true), first)], guarded),
+    );
+    method.set_successor(first, Successor::Goto(second));
+    method.set_successor(second, Successor::Goto(end));
+    method.set_block_invariants(guarded, vec![Expr::from(true)], vec![]);
+    method.set_successor(guarded, Successor::Goto(end));
+    method.set_successor(end, Successor::Return);
+
+    method.normalize();
+
+    assert_eq!(method.basic_blocks_labels(), &["start", "guarded", "end"]);
+    let indices = method.get_indices();
+    assert_eq!(
+        method.basic_blocks[0].successor,
+        Successor::GotoSwitch(vec![(Expr::from(true), indices[2])], indices[1])
+    );
+    assert_eq!(
+        method.basic_blocks[1].successor,
+        Successor::Goto(indices[2])
+    );
+}
+
+#[test]
+fn normalize_keeps_cycles_of_empty_blocks() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let head = method.add_block("head", vec![]);
+    let tail = method.add_block("tail", vec![]);
+    method.set_successor(start, Successor::Goto(head));
+    method.set_successor(head, Successor::Goto(tail));
+    method.set_successor(tail, Successor::Goto(head));
+
+    method.normalize();
+
+    assert_eq!(method.basic_blocks_labels(), &["start", "head", "tail"]);
+    assert!(method.has_loops());
+}
+
+#[test]
+fn try_add_postcondition_accepts_formal_returns() {
+    let result = LocalVar::new("result", Type::Bool);
+    let mut method = CfgMethod::new("test".to_string(), vec![result.clone()], vec![], vec![]);
+    let bound = LocalVar::new("b", Type::Bool);
+    let post = Expr::and(
+        Expr::eq_cmp(Expr::local(result), Expr::from(true)),
+        Expr::forall(vec![bound.clone()], vec![], Expr::local(bound)),
+    );
+
+    assert_eq!(method.try_add_postcondition(post.clone()), Ok(()));
+    assert_eq!(method.get_postconditions(), &[post]);
+}
+
+#[test]
+fn try_add_postcondition_rejects_other_variables() {
+    let result = LocalVar::new("result", Type::Bool);
+    let local = LocalVar::new("x", Type::Bool);
+    let mut method = CfgMethod::new(
+        "test".to_string(),
+        vec![result],
+        vec![local.clone()],
+        vec![],
+    );
+
+    assert_eq!(
+        method.try_add_postcondition(Expr::local(local)),
+        Err(CfgError::UnknownReturnVariable("x".to_string()))
+    );
+    assert!(method.get_postconditions().is_empty());
+}
+
+/// `function f(p: Bool): Bool requires p`
+fn function_requiring_argument() -> Function {
+    let parameter = LocalVar::new("p", Type::Bool);
+    Function {
+        name: "f".to_string(),
+        type_arguments: vec![],
+        formal_args: vec![parameter.clone()],
+        return_type: Type::Bool,
+        pres: vec![Expr::local(parameter)],
+        posts: vec![],
+        body: None,
+    }
+}
+
+fn call(function: &Function, argument: Expr, position: Position) -> Expr {
+    Expr::func_app(
+        function.name.clone(),
+        vec![],
+        vec![argument],
+        function.formal_args.clone(),
+        Type::Bool,
+        position,
+    )
+}
+
+#[test]
+fn insert_invariant_precondition_checks_asserts_at_the_label() {
+    let function = function_requiring_argument();
+    let x = Expr::local(LocalVar::new("x", Type::Bool));
+    let position = Position::new(3, 4, 5);
+    let invariant = call(&function, x.clone(), position);
+    let mut method = new_method();
+    let head = method.add_block("head", vec![Stmt::comment("body")]);
+    method.set_successor(head, Successor::Return);
+    method.set_block_invariants(head, vec![], vec![invariant.clone()]);
+
+    method.insert_invariant_precondition_checks(&[function]);
+
+    assert!(method.basic_blocks[0].inhale_after_label.is_empty());
+    assert_eq!(
+        method.basic_blocks[0].stmts,
+        vec![
+            Stmt::inhale(invariant),
+            Stmt::Assert(Assert { expr: x, position }),
+            Stmt::comment("body"),
+        ]
+    );
+}
+
+#[test]
+fn insert_invariant_precondition_checks_respects_guards() {
+    let function = function_requiring_argument();
+    let x = Expr::local(LocalVar::new("x", Type::Bool));
+    let y = Expr::local(LocalVar::new("y", Type::Bool));
+    let position = Position::new(3, 4, 5);
+    let invariant = Expr::implies(y.clone(), call(&function, x.clone(), position));
+    let mut method = new_method();
+    let head = method.add_block("head", vec![]);
+    method.set_successor(head, Successor::Return);
+    method.set_block_invariants(head, vec![], vec![invariant.clone()]);
+
+    method.insert_invariant_precondition_checks(&[function]);
+
+    assert_eq!(
+        method.basic_blocks[0].stmts,
+        vec![
+            Stmt::inhale(invariant),
+            Stmt::Assert(Assert {
+                expr: Expr::implies(y, x),
+                position,
+            }),
+        ]
+    );
+}
+
+#[test]
+fn insert_invariant_precondition_checks_ignores_permission_guards() {
+    let function = function_requiring_argument();
+    let r = Expr::local(LocalVar::new("r", Type::Bool)).field(Field::new("val_bool", Type::Bool));
+    let position = Position::new(3, 4, 5);
+    let invariant = Expr::and(
+        Expr::acc_permission(r.clone(), PermAmount::Read),
+        call(&function, r.clone(), position),
+    );
+    let mut method = new_method();
+    let head = method.add_block("head", vec![]);
+    method.set_successor(head, Successor::Return);
+    method.set_block_invariants(head, vec![], vec![invariant.clone()]);
+
+    method.insert_invariant_precondition_checks(&[function]);
+
+    assert_eq!(
+        method.basic_blocks[0].stmts,
+        vec![
+            Stmt::inhale(invariant),
+            Stmt::Assert(Assert { expr: r, position }),
+        ]
+    );
+}
+
+#[test]
+fn insert_invariant_precondition_checks_keeps_blocks_without_calls() {
+    let function = function_requiring_argument();
+    let mut method = new_method();
+    let head = method.add_block("head", vec![]);
+    method.set_successor(head, Successor::Return);
+    method.set_block_invariants(head, vec![], vec![Expr::from(true)]);
+
+    method.insert_invariant_precondition_checks(&[function]);
+
+    assert_eq!(
+        method.basic_blocks[0].inhale_after_label,
+        vec![Expr::from(true)]
+    );
+    assert!(method.basic_blocks[0].stmts.is_empty());
+}
+
+#[test]
+fn inline_single_use_blocks_merges_goto_chains() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![Stmt::comment("start")]);
+    let middle = method.add_block("middle", vec![Stmt::comment("middle")]);
+    let end = method.add_block("end", vec![Stmt::comment("end")]);
+    method.set_successor(start, Successor::Goto(middle));
+    method.set_successor(middle, Successor::Goto(end));
+    method.set_successor(end, Successor::Return);
+
+    assert!(method.inline_single_use_blocks());
+
+    assert_eq!(method.basic_blocks_labels(), &["start"]);
+    assert_eq!(
+        method.basic_blocks[0].stmts,
+        vec![
+            Stmt::comment("start"),
+            Stmt::comment("middle"),
+            Stmt::comment("end"),
+        ]
+    );
+    assert_eq!(method.basic_blocks[0].successor, Successor::Return);
+}
+
+#[test]
+fn inline_single_use_blocks_skips_join_points() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let left = method.add_block("left", vec![]);
+    let right = method.add_block("right", vec![]);
+    let join = method.add_block("join", vec![]);
+    method.set_successor(
+        start,
+        Successor::GotoSwitch(
+            vec![(Expr::local(LocalVar::new("c", Type::Bool)), left)],
+            right,
+        ),
+    );
+    method.set_successor(left, Successor::Goto(join));
+    method.set_successor(right, Successor::Goto(join));
+    method.set_successor(join, Successor::Return);
+
+    assert!(!method.inline_single_use_blocks());
+    assert_eq!(
+        method.basic_blocks_labels(),
+        &["start", "left", "right", "join"]
+    );
+}
+
+#[test]
+fn inline_single_use_blocks_skips_blocks_with_invariants() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let head = method.add_block("head", vec![]);
+    method.set_successor(start, Successor::Goto(head));
+    method.set_successor(head, Successor::Return);
+    method.set_block_invariants(head, vec![Expr::from(true)], vec![]);
+
+    assert!(!method.inline_single_use_blocks());
+    assert_eq!(method.basic_blocks_labels(), &["start", "head"]);
+}
+
+#[test]
+fn inline_single_use_blocks_keeps_referenced_labels() {
+    let x = Expr::local(LocalVar::new("x", Type::Bool));
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let pre_call = method.add_block("pre_call", vec![]);
+    let end = method.add_block(
+        "end",
+        vec![Stmt::Assert(Assert {
+            expr: Expr::labelled_old("pre_call", x),
+            position: Position::default(),
+        })],
+    );
+    method.set_successor(start, Successor::Goto(pre_call));
+    method.set_successor(pre_call, Successor::Goto(end));
+    method.set_successor(end, Successor::Return);
+
+    assert!(method.inline_single_use_blocks());
+
+    assert_eq!(method.basic_blocks_labels(), &["start", "pre_call"]);
+    let indices = method.get_indices();
+    assert_eq!(
+        method.basic_blocks[0].successor,
+        Successor::Goto(indices[1])
+    );
+    assert_eq!(method.basic_blocks[1].stmts.len(), 1);
+}
+
+#[test]
+fn normalize_keeps_referenced_labels() {
+    let x = Expr::local(LocalVar::new("x", Type::Bool));
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let pre_call = method.add_block("pre_call", vec![]);
+    let end = method.add_block(
+        "end",
+        vec![Stmt::Assert(Assert {
+            expr: Expr::labelled_old("pre_call", x),
+            position: Position::default(),
+        })],
+    );
+    method.set_successor(start, Successor::Goto(pre_call));
+    method.set_successor(pre_call, Successor::Goto(end));
+    method.set_successor(end, Successor::Return);
+
+    method.normalize();
+
+    assert_eq!(method.basic_blocks_labels(), &["start", "pre_call", "end"]);
+    let indices = method.get_indices();
+    assert_eq!(
+        method.basic_blocks[0].successor,
+        Successor::Goto(indices[1])
+    );
+    assert_eq!(
+        method.basic_blocks[1].successor,
+        Successor::Goto(indices[2])
+    );
+}
+
+#[test]
+fn rename_local_rewrites_all_occurrences() {
+    let result = LocalVar::new("result", Type::Bool);
+    let x = LocalVar::new("x", Type::Bool);
+    let mut method = CfgMethod::new(
+        "test".to_string(),
+        vec![result.clone()],
+        vec![x.clone()],
+        vec![],
+    );
+    let assert_x = |var: &LocalVar| {
+        Stmt::Assert(Assert {
+            expr: Expr::local(var.clone()),
+            position: Position::default(),
+        })
+    };
+    let start = method.add_block("start", vec![assert_x(&x)]);
+    let end = method.add_block("end", vec![]);
+    method.set_successor(
+        start,
+        Successor::GotoSwitch(vec![(Expr::local(x.clone()), end)], end),
+    );
+    method.set_successor(end, Successor::Return);
+    method.set_block_invariants(end, vec![Expr::local(x.clone())], vec![]);
+    method.add_postcondition(Expr::eq_cmp(
+        Expr::local(result.clone()),
+        Expr::local(result.clone()),
+    ));
+
+    assert_eq!(method.rename_local("x", "y"), Ok(()));
+    assert_eq!(method.rename_local("result", "ret"), Ok(()));
+
+    let y = LocalVar::new("y", Type::Bool);
+    let ret = LocalVar::new("ret", Type::Bool);
+    assert_eq!(method.get_all_vars(), vec![ret.clone(), y.clone()]);
+    assert_eq!(method.basic_blocks[0].stmts, vec![assert_x(&y)]);
+    assert_eq!(
+        method.basic_blocks[0].successor,
+        Successor::GotoSwitch(vec![(Expr::local(y.clone()), end)], end)
+    );
+    assert_eq!(method.basic_blocks[1].exhale_on_entry, vec![Expr::local(y)]);
+    assert_eq!(
+        method.get_postconditions(),
+        &[Expr::eq_cmp(Expr::local(ret.clone()), Expr::local(ret))]
+    );
+}
+
+#[test]
+fn rename_local_keeps_bound_variables() {
+    let x = LocalVar::new("x", Type::Bool);
+    let mut method = CfgMethod::new("test".to_string(), vec![], vec![x.clone()], vec![]);
+    let quantified = Expr::forall(vec![x.clone()], vec![], Expr::local(x.clone()));
+    let start = method.add_block(
+        "start",
+        vec![Stmt::Assert(Assert {
+            expr: Expr::and(Expr::local(x), quantified.clone()),
+            position: Position::default(),
+        })],
+    );
+    method.set_successor(start, Successor::Return);
+
+    assert_eq!(method.rename_local("x", "y"), Ok(()));
+
+    assert_eq!(
+        method.basic_blocks[0].stmts,
+        vec![Stmt::Assert(Assert {
+            expr: Expr::and(Expr::local(LocalVar::new("y", Type::Bool)), quantified),
+            position: Position::default(),
+        })]
+    );
+}
+
+#[test]
+fn rename_local_rejects_unknown_and_used_names() {
+    let mut method = CfgMethod::new(
+        "test".to_string(),
+        vec![],
+        vec![
+            LocalVar::new("x", Type::Bool),
+            LocalVar::new("y", Type::Bool),
+        ],
+        vec![],
+    );
+    method.add_block("start", vec![]);
+
+    assert_eq!(
+        method.rename_local("z", "w"),
+        Err(CfgError::UnknownVariable("z".to_string()))
+    );
+    assert_eq!(
+        method.rename_local("x", "y"),
+        Err(CfgError::DuplicateVariable("y".to_string()))
+    );
+    assert_eq!(
+        method.rename_local("x", "start"),
+        Err(CfgError::DuplicateVariable("start".to_string()))
+    );
+    assert_eq!(
+        method.get_all_vars(),
+        vec![
+            LocalVar::new("x", Type::Bool),
+            LocalVar::new("y", Type::Bool)
+        ]
+    );
+}
+
+fn assign(target: Expr) -> Stmt {
+    Stmt::Assign(Assign {
+        target,
+        source: Expr::from(true),
+        kind: AssignKind::Copy,
+    })
+}
+
+#[test]
+fn unassigned_returns_reports_unassigned_branch() {
+    let result = LocalVar::new("result", Type::Bool);
+    let other = LocalVar::new("other", Type::Bool);
+    let mut method = CfgMethod::new(
+        "test".to_string(),
+        vec![result.clone(), other.clone()],
+        vec![],
+        vec![],
+    );
+    let split = method.add_block("split", vec![assign(Expr::local(other))]);
+    let left = method.add_block("left", vec![assign(Expr::local(result))]);
+    let right = method.add_block("right", vec![]);
+    let merge = method.add_block("merge", vec![]);
+    method.set_successor(
+        split,
+        Successor::GotoSwitch(vec![(Expr::from(true), left)], right),
+    );
+    method.set_successor(left, Successor::Goto(merge));
+    method.set_successor(right, Successor::Goto(merge));
+    method.set_successor(merge, Successor::Return);
+
+    assert_eq!(method.unassigned_returns(), vec!["result".to_string()]);
+}
+
+#[test]
+fn unassigned_returns_accepts_assignments_on_all_paths() {
+    let result = LocalVar::new("result", Type::Ref);
+    let mut method = CfgMethod::new("test".to_string(), vec![result.clone()], vec![], vec![]);
+    let split = method.add_block("split", vec![]);
+    let field = Expr::local(result.clone()).field(Field::new("val_bool", Type::Bool));
+    let left = method.add_block("left", vec![assign(field)]);
+    let right = method.add_block(
+        "right",
+        vec![Stmt::MethodCall(MethodCall {
+            method_name: "havoc".to_string(),
+            arguments: vec![],
+            targets: vec![result],
+        })],
+    );
+    let panic = method.add_block(
+        "panic",
+        vec![Stmt::Assert(Assert {
+            expr: Expr::from(false),
+            position: Position::default(),
+        })],
+    );
+    method.set_successor(
+        split,
+        SwitchBuilder::new()
+            .arm(Expr::from(true), left)
+            .arm(Expr::from(false), panic)
+            .default(right)
+            .build(),
+    );
+    method.set_successor(left, Successor::Return);
+    method.set_successor(right, Successor::Return);
+    method.set_successor(panic, Successor::Return);
+
+    assert!(method.unassigned_returns().is_empty());
+}
+
+#[test]
+fn block_label_returns_the_label() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let end = method.add_block("end", vec![]);
+
+    assert_eq!(method.block_label(start), "start");
+    assert_eq!(method.block_label(end), "end");
+}
+
+#[test]
+#[should_panic(expected = "The provided CfgBlockIndex doesn't belong to this CfgMethod")]
+fn block_label_rejects_blocks_of_other_methods() {
+    let mut other = new_method();
+    let block = other.add_block("start", vec![]);
+    let mut method = new_method();
+    method.add_block("start", vec![]);
+
+    method.block_label(block);
+}
+
+#[test]
+fn loops_without_invariants_finds_loop_heads() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let head = method.add_block("head", vec![]);
+    let body = method.add_block("body", vec![]);
+    let end = method.add_block("end", vec![]);
+    method.set_successor(start, Successor::Goto(head));
+    method.set_successor(
+        head,
+        Successor::GotoSwitch(vec![(Expr::from(true), body)], end),
+    );
+    method.set_successor(body, Successor::Goto(head));
+    method.set_successor(end, Successor::Goto(end));
+
+    assert_eq!(method.loops_without_invariants(), vec![head, end]);
+    assert_eq!(
+        method.check_loop_invariants(),
+        Err(CfgError::LoopsWithoutInvariants(vec![
+            "head".to_string(),
+            "end".to_string()
+        ]))
+    );
+
+    method.set_block_invariants(head, vec![Expr::from(true)], vec![]);
+    method.set_block_invariants(end, vec![], vec![Expr::from(true)]);
+
+    assert!(method.loops_without_invariants().is_empty());
+    assert!(method.check_loop_invariants().is_ok());
+}
+
+#[test]
+fn self_loops_without_invariants_finds_switch_defaults() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let spin = method.add_block("spin", vec![]);
+    let end = method.add_block("end", vec![]);
+    method.set_successor(
+        start,
+        Successor::GotoSwitch(vec![(Expr::from(true), end)], spin),
+    );
+    method.set_successor(
+        spin,
+        Successor::GotoSwitch(vec![(Expr::from(false), end)], spin),
+    );
+    method.set_successor(end, Successor::Return);
+
+    assert_eq!(method.find_self_loops_without_invariants(), vec![spin]);
+    assert_eq!(
+        method.check_self_loops(),
+        Err(CfgError::SelfLoopsWithoutInvariants(vec![
+            "spin".to_string()
+        ]))
+    );
+
+    method.set_block_invariants(spin, vec![Expr::from(true)], vec![]);
+
+    assert!(method.find_self_loops_without_invariants().is_empty());
+    assert!(method.check_self_loops().is_ok());
+}
+
+#[test]
+fn self_loops_without_invariants_ignores_longer_loops() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let head = method.add_block("head", vec![]);
+    let body = method.add_block("body", vec![]);
+    let end = method.add_block("end", vec![]);
+    method.set_successor(start, Successor::Goto(head));
+    method.set_successor(
+        head,
+        Successor::GotoSwitch(vec![(Expr::from(true), body)], end),
+    );
+    method.set_successor(body, Successor::Goto(head));
+    method.set_successor(end, Successor::Return);
+
+    // A loop through another block is left to `check_loop_invariants`.
+    assert!(method.find_self_loops_without_invariants().is_empty());
+    assert!(method.check_self_loops().is_ok());
+}
+
+#[test]
+fn loops_without_invariants_ignores_join_points() {
+    let mut method = new_method();
+    let split = method.add_block("split", vec![]);
+    let left = method.add_block("left", vec![]);
+    let right = method.add_block("right", vec![]);
+    let merge = method.add_block("merge", vec![]);
+    method.set_successor(
+        split,
+        Successor::GotoSwitch(vec![(Expr::from(true), left)], right),
+    );
+    method.set_successor(left, Successor::Goto(merge));
+    method.set_successor(right, Successor::Goto(left));
+    method.set_successor(merge, Successor::Return);
+
+    assert!(method.loops_without_invariants().is_empty());
+}
+
+fn assign_var(target: &LocalVar, source: Expr) -> Stmt {
+    Stmt::Assign(Assign {
+        target: Expr::local(target.clone()),
+        source,
+        kind: AssignKind::Copy,
+    })
+}
+
+#[test]
+fn add_return_postcondition_splits_on_returning_block() {
+    let result = LocalVar::new("result", Type::Int);
+    let a = LocalVar::new("a", Type::Int);
+    let b = LocalVar::new("b", Type::Int);
+    let mut method = CfgMethod::new(
+        "max".to_string(),
+        vec![result.clone(), a.clone(), b.clone()],
+        vec![],
+        vec![],
+    );
+    let split = method.add_block("split", vec![]);
+    let left = method.add_block("left", vec![assign_var(&result, Expr::local(a.clone()))]);
+    let right = method.add_block("right", vec![assign_var(&result, Expr::local(b.clone()))]);
+    let a_ge_b = Expr::ge_cmp(Expr::local(a.clone()), Expr::local(b.clone()));
+    method.set_successor(split, Successor::GotoSwitch(vec![(a_ge_b, left)], right));
+    method.set_successor(left, Successor::Return);
+    method.set_successor(right, Successor::Return);
+
+    let result_is =
+        |var: &LocalVar| Expr::eq_cmp(Expr::local(result.clone()), Expr::local(var.clone()));
+    let result_ge_b = Expr::ge_cmp(Expr::local(result.clone()), Expr::local(b.clone()));
+    assert_eq!(method.add_return_postcondition(left, result_is(&a)), Ok(()));
+    assert_eq!(
+        method.add_return_postcondition(right, result_is(&b)),
+        Ok(())
+    );
+    assert_eq!(
+        method.add_return_postcondition(left, result_ge_b.clone()),
+        Ok(())
+    );
+
+    let path = LocalVar::new("return_path", Type::Int);
+    assert_eq!(
+        method.get_formal_returns(),
+        &[result.clone(), a.clone(), b.clone(), path.clone()]
+    );
+    assert_eq!(
+        method.basic_blocks[0].stmts,
+        vec![assign_var(&path, 0.into())]
+    );
+    assert_eq!(
+        method.basic_blocks[1].stmts,
+        vec![
+            assign_var(&result, Expr::local(a.clone())),
+            assign_var(&path, 1.into())
+        ]
+    );
+    assert_eq!(
+        method.basic_blocks[2].stmts,
+        vec![
+            assign_var(&result, Expr::local(b.clone())),
+            assign_var(&path, 2.into())
+        ]
+    );
+    let on_path = |number: i64, expr: Expr| {
+        Expr::implies(Expr::eq_cmp(Expr::local(path.clone()), number.into()), expr)
+    };
+    assert_eq!(
+        method.get_postconditions(),
+        &[
+            on_path(1, result_is(&a)),
+            on_path(2, result_is(&b)),
+            on_path(1, result_ge_b)
+        ]
+    );
+    assert!(method.unassigned_returns().is_empty());
+}
+
+#[test]
+fn add_return_postcondition_rejects_other_blocks() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let end = method.add_block("end", vec![]);
+    method.set_successor(start, Successor::Goto(end));
+    method.set_successor(end, Successor::Return);
+
+    assert_eq!(
+        method.add_return_postcondition(start, Expr::from(true)),
+        Err(CfgError::NotAReturnBlock("start".to_string()))
+    );
+    assert!(method.get_postconditions().is_empty());
+    assert!(method.get_formal_returns().is_empty());
+}
+
+#[test]
+fn add_return_postcondition_rejects_unknown_variables_without_changes() {
+    let mut method = new_method();
+    let end = method.add_block("end", vec![]);
+    method.set_successor(end, Successor::Return);
+    let x = LocalVar::new("x", Type::Int);
+
+    assert_eq!(
+        method.add_return_postcondition(end, Expr::local(x)),
+        Err(CfgError::UnknownReturnVariable("x".to_string()))
+    );
+    assert!(method.get_postconditions().is_empty());
+    assert!(method.get_formal_returns().is_empty());
+    assert!(method.basic_blocks[0].stmts.is_empty());
+}
+
+#[test]
+fn redirect_edges_rewrites_goto() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let old = method.add_block("old", vec![]);
+    let new = method.add_block("new", vec![]);
+    method.set_successor(start, Successor::Goto(old));
+    method.set_successor(old, Successor::Goto(old));
+    method.set_successor(new, Successor::Return);
+
+    method.redirect_edges(old, new);
+
+    assert_eq!(method.basic_blocks[0].successor, Successor::Goto(new));
+    assert_eq!(method.basic_blocks[1].successor, Successor::Goto(new));
+    assert_eq!(method.basic_blocks[2].successor, Successor::Return);
+    assert!(method.get_preceding(old).is_empty());
+}
+
+#[test]
+fn redirect_edges_rewrites_switch_arms_and_default() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let other = method.add_block("other", vec![]);
+    let old = method.add_block("old", vec![]);
+    let new = method.add_block("new", vec![]);
+    method.set_successor(
+        start,
+        Successor::GotoSwitch(
+            vec![
+                (Expr::from(true), old),
+                (Expr::from(false), other),
+                (Expr::from(true), old),
+            ],
+            old,
+        ),
+    );
+    method.set_successor(
+        other,
+        Successor::GotoSwitch(vec![(Expr::from(true), new)], other),
+    );
+    method.set_successor(old, Successor::Return);
+    method.set_successor(new, Successor::Return);
+
+    method.redirect_edges(old, new);
+
+    assert_eq!(
+        method.basic_blocks[0].successor,
+        Successor::GotoSwitch(
+            vec![
+                (Expr::from(true), new),
+                (Expr::from(false), other),
+                (Expr::from(true), new),
+            ],
+            new,
+        )
+    );
+    assert_eq!(
+        method.basic_blocks[1].successor,
+        Successor::GotoSwitch(vec![(Expr::from(true), new)], other)
+    );
+}
+
+#[test]
+fn redirect_edges_keeps_other_successors() {
+    let mut method = new_method();
+    let start = method.add_block("start", vec![]);
+    let unreachable = method.add_block("unreachable", vec![]);
+    let undefined = method.add_block("undefined", vec![]);
+    method.set_successor(start, Successor::Return);
+    method.set_successor(
+        unreachable,
+        Successor::IntentionalUnreachable(Position::default()),
+    );
+
+    method.redirect_edges(start, undefined);
+
+    assert_eq!(method.basic_blocks[0].successor, Successor::Return);
+    assert_eq!(
+        method.basic_blocks[1].successor,
+        Successor::IntentionalUnreachable(Position::default())
+    );
+    assert_eq!(method.basic_blocks[2].successor, Successor::Undefined);
+}
@@ -0,0 +1,57 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use vir::legacy::{CfgMethod, Const, Expr, Position, Successor};
+
+fn labels_in_canonical_order(method: &CfgMethod) -> Vec<&str> {
+    method
+        .get_canonical_order()
+        .into_iter()
+        .map(|index| method.basic_blocks_labels()[index.index()].as_str())
+        .collect()
+}
+
+#[test]
+fn canonical_order_is_independent_of_insertion_order() {
+    let mut method = CfgMethod::new("test".to_string(), vec![], vec![]);
+    let start = method.add_block("start", vec![]);
+    let z_dead = method.add_block("z_dead", vec![]);
+    let join = method.add_block("d", vec![]);
+    let right = method.add_block("c", vec![]);
+    let left = method.add_block("b", vec![]);
+    let a_dead = method.add_block("a_dead", vec![]);
+    let guard = Expr::Const(Const::Bool(true), Position::default());
+    method.set_successor(start, Successor::GotoSwitch(vec![(guard, right)], left));
+    method.set_successor(left, Successor::Goto(join));
+    method.set_successor(right, Successor::Goto(join));
+    method.set_successor(join, Successor::Return);
+    method.set_successor(z_dead, Successor::Goto(join));
+    method.set_successor(a_dead, Successor::Return);
+
+    assert_eq!(
+        labels_in_canonical_order(&method),
+        ["start", "b", "c", "d", "a_dead", "z_dead"]
+    );
+}
+
+#[test]
+fn canonical_order_handles_loops() {
+    let mut method = CfgMethod::new("test".to_string(), vec![], vec![]);
+    let start = method.add_block("start", vec![]);
+    let exit = method.add_block("exit", vec![]);
+    let body = method.add_block("body", vec![]);
+    let head = method.add_block("head", vec![]);
+    let guard = Expr::Const(Const::Bool(true), Position::default());
+    method.set_successor(start, Successor::Goto(head));
+    method.set_successor(head, Successor::GotoSwitch(vec![(guard, body)], exit));
+    method.set_successor(body, Successor::Goto(head));
+    method.set_successor(exit, Successor::Return);
+
+    assert_eq!(
+        labels_in_canonical_order(&method),
+        ["start", "head", "body", "exit"]
+    );
+}